@@ -0,0 +1,234 @@
+use crate::ipc::{
+    EVERYTHING_IPC_EXIT, EVERYTHING_IPC_GET_TARGET_MACHINE, EVERYTHING_IPC_IS_ADMIN,
+    EVERYTHING_IPC_IS_APPDATA, EVERYTHING_IPC_IS_DB_BUSY, EVERYTHING_IPC_IS_NTFS_DRIVE_INDEXED,
+    EVERYTHING_IPC_QUEUE_REBUILD_DB, EVERYTHING_IPC_REBUILD_DB, EVERYTHING_IPC_SAVE_DB,
+    EVERYTHING_IPC_TARGET_MACHINE_ARM, EVERYTHING_IPC_TARGET_MACHINE_ARM64,
+    EVERYTHING_IPC_TARGET_MACHINE_X64, EVERYTHING_IPC_TARGET_MACHINE_X86,
+    EVERYTHING_IPC_UPDATE_ALL_FOLDER_INDEXES,
+};
+use crate::{Everything, EverythingError, EverythingResult, EverythingSDKError};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// The architecture Everything was built for, see `Admin::target_machine`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub enum TargetMachine {
+    X86,
+    X64,
+    Arm,
+    Arm64,
+    /// A value this crate does not know, for example from a newer Everything.
+    Unknown(u32),
+}
+
+impl From<TargetMachine> for u32 {
+    fn from(target_machine: TargetMachine) -> Self {
+        match target_machine {
+            TargetMachine::X86 => EVERYTHING_IPC_TARGET_MACHINE_X86,
+            TargetMachine::X64 => EVERYTHING_IPC_TARGET_MACHINE_X64,
+            TargetMachine::Arm => EVERYTHING_IPC_TARGET_MACHINE_ARM,
+            TargetMachine::Arm64 => EVERYTHING_IPC_TARGET_MACHINE_ARM64,
+            TargetMachine::Unknown(value) => value,
+        }
+    }
+}
+
+/// Values without a variant become `TargetMachine::Unknown`.
+impl From<u32> for TargetMachine {
+    fn from(target_machine: u32) -> Self {
+        match target_machine {
+            EVERYTHING_IPC_TARGET_MACHINE_X86 => TargetMachine::X86,
+            EVERYTHING_IPC_TARGET_MACHINE_X64 => TargetMachine::X64,
+            EVERYTHING_IPC_TARGET_MACHINE_ARM => TargetMachine::Arm,
+            EVERYTHING_IPC_TARGET_MACHINE_ARM64 => TargetMachine::Arm64,
+            value => TargetMachine::Unknown(value),
+        }
+    }
+}
+
+/// Checks on and maintains the Everything service, see `Everything::admin`.
+///
+/// Every call fails with `EverythingSDKError::Ipc` if Everything is not running.
+/// The actions fail with `EverythingError::CommandRejected` if Everything did not carry them out,
+/// for example because it is too old to know the command.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::{Everything, InMemoryBackend, InMemoryEntry, TargetMachine};
+///
+/// let everything = Everything::with_backend(InMemoryBackend::new(vec![InMemoryEntry::volume("C:")]));
+/// let admin = everything.admin();
+///
+/// assert_eq!(admin.target_machine().unwrap(), Some(TargetMachine::X64));
+/// assert!(admin.is_ntfs_drive_indexed('c').unwrap());
+/// if !admin.is_db_busy().unwrap() {
+///     admin.update_all_folder_indexes().unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Admin<'a> {
+    everything: &'a Everything,
+}
+
+impl Admin<'_> {
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_isadmin/>
+    pub fn is_admin(&self) -> EverythingResult<bool> {
+        self.query(EVERYTHING_IPC_IS_ADMIN, 0)
+    }
+
+    /// Whether Everything stores its settings and data in `%APPDATA%` rather than next to the executable.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_isappdata/>
+    pub fn is_appdata(&self) -> EverythingResult<bool> {
+        self.query(EVERYTHING_IPC_IS_APPDATA, 0)
+    }
+
+    /// Returns `None` if Everything is older than 1.4.1 and does not report it.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_gettargetmachine/>
+    pub fn target_machine(&self) -> EverythingResult<Option<TargetMachine>> {
+        let target_machine = self.send(EVERYTHING_IPC_GET_TARGET_MACHINE, 0)?;
+        Ok((target_machine != 0).then(|| TargetMachine::from(target_machine)))
+    }
+
+    /// Whether the database is busy. Starting another action cancels the current one if possible.
+    pub fn is_db_busy(&self) -> EverythingResult<bool> {
+        self.query(EVERYTHING_IPC_IS_DB_BUSY, 0)
+    }
+
+    /// Whether the NTFS volume with the letter `drive` is indexed, case insensitive.
+    /// Fails with `EverythingSDKError::InvalidParameter` if `drive` is not a letter from A to Z.
+    pub fn is_ntfs_drive_indexed(&self, drive: char) -> EverythingResult<bool> {
+        if !drive.is_ascii_alphabetic() {
+            return Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidParameter,
+            ));
+        }
+        let index = drive.to_ascii_uppercase() as u32 - 'A' as u32;
+        self.query(EVERYTHING_IPC_IS_NTFS_DRIVE_INDEXED, index)
+    }
+
+    /// Saves the database to disk.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_savedb/>
+    pub fn save_db(&self) -> EverythingResult<()> {
+        self.action(EVERYTHING_IPC_SAVE_DB)
+    }
+
+    /// Rescans every index now, the database is unavailable until it finishes.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_rebuilddb/>
+    pub fn rebuild_db(&self) -> EverythingResult<()> {
+        self.action(EVERYTHING_IPC_REBUILD_DB)
+    }
+
+    /// Rescans every index once the database is ready, instead of cancelling what it is busy with.
+    pub fn queue_rebuild_db(&self) -> EverythingResult<()> {
+        self.action(EVERYTHING_IPC_QUEUE_REBUILD_DB)
+    }
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_updateallfolderindexes/>
+    pub fn update_all_folder_indexes(&self) -> EverythingResult<()> {
+        self.action(EVERYTHING_IPC_UPDATE_ALL_FOLDER_INDEXES)
+    }
+
+    /// Closes Everything. Every call fails with `EverythingSDKError::Ipc` afterwards until it is started again.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_exit/>
+    pub fn exit(&self) -> EverythingResult<()> {
+        self.action(EVERYTHING_IPC_EXIT)
+    }
+
+    fn send(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        self.everything.backend.send_command(command, param)
+    }
+
+    fn query(&self, command: u32, param: u32) -> EverythingResult<bool> {
+        Ok(self.send(command, param)? != 0)
+    }
+
+    pub(crate) fn action(&self, command: u32) -> EverythingResult<()> {
+        match self.send(command, 0)? {
+            0 => Err(EverythingError::CommandRejected(command)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Everything {
+    /// Checks on and maintains the Everything service, see `Admin`.
+    pub fn admin(&self) -> Admin<'_> {
+        Admin { everything: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, LoopbackTransport};
+    use crate::{InMemoryBackend, InMemoryEntry, SearchBackend};
+
+    #[test]
+    fn decodes_target_machines() {
+        for value in 1..=4 {
+            let target_machine = TargetMachine::from(value);
+            assert!(!matches!(target_machine, TargetMachine::Unknown(_)));
+            assert_eq!(u32::from(target_machine), value);
+        }
+        assert_eq!(TargetMachine::from(0x1c4), TargetMachine::Unknown(0x1c4));
+    }
+
+    #[test]
+    fn sends_commands_over_ipc() {
+        let backend = InMemoryBackend::new(vec![
+            InMemoryEntry::volume("C:"),
+            InMemoryEntry::volume("Z:"),
+        ]);
+        backend.set_admin(true);
+        backend.set_db_busy(true);
+        backend.set_target_machine(TargetMachine::Arm64);
+        let everything = Everything::with_backend(IpcClient::new(LoopbackTransport::new(backend)));
+        let admin = everything.admin();
+
+        assert!(admin.is_admin().unwrap());
+        assert!(admin.is_appdata().unwrap());
+        assert!(admin.is_db_busy().unwrap());
+        assert_eq!(admin.target_machine().unwrap(), Some(TargetMachine::Arm64));
+        assert!(admin.is_ntfs_drive_indexed('C').unwrap());
+        assert!(admin.is_ntfs_drive_indexed('z').unwrap());
+        assert!(!admin.is_ntfs_drive_indexed('D').unwrap());
+        assert!(matches!(
+            admin.is_ntfs_drive_indexed('1'),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidParameter
+            ))
+        ));
+
+        admin.save_db().unwrap();
+        admin.rebuild_db().unwrap();
+        admin.queue_rebuild_db().unwrap();
+        admin.update_all_folder_indexes().unwrap();
+        admin.exit().unwrap();
+        assert!(matches!(
+            admin.is_admin(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert!(everything.get_last_error().is_err());
+    }
+
+    #[test]
+    fn reports_rejected_commands() {
+        let backend = InMemoryBackend::new(Vec::new());
+        backend.set_target_machine(TargetMachine::Unknown(0));
+        let everything = Everything::with_backend(backend);
+        let admin = everything.admin();
+        assert_eq!(admin.target_machine().unwrap(), None);
+
+        let backend = InMemoryBackend::new(Vec::new());
+        assert_eq!(backend.send_command(EVERYTHING_IPC_SAVE_DB, 0).unwrap(), 1);
+        assert_eq!(backend.send_command(0xffff, 7).unwrap(), 0);
+        assert_eq!(
+            backend.commands(),
+            [(EVERYTHING_IPC_SAVE_DB, 0), (0xffff, 7)]
+        );
+        assert!(matches!(
+            Everything::with_backend(backend).admin().action(0xffff),
+            Err(EverythingError::CommandRejected(0xffff))
+        ));
+    }
+}
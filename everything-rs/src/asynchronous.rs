@@ -0,0 +1,436 @@
+//! Runs queries off the calling thread and awaits their results.
+//! The futures do not depend on any runtime, the `tokio` feature only adds a way to run the queries on tokio's blocking thread pool.
+//!
+//! # Example
+//! ```rust
+//! use everything_rs::asynchronous::AsyncEverything;
+//! use everything_rs::{Everything, InMemoryBackend, InMemoryEntry, SearchRequest};
+//!
+//! # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+//! #     let waker = std::task::Waker::noop();
+//! #     let mut context = std::task::Context::from_waker(&waker);
+//! #     let mut future = std::pin::pin!(future);
+//! #     loop {
+//! #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+//! #             return output;
+//! #         }
+//! #         std::thread::yield_now();
+//! #     }
+//! # }
+//! let everything = Everything::with_backend(InMemoryBackend::new(vec![
+//!     InMemoryEntry::file("C:\\test", "test1.txt"),
+//! ]));
+//! let everything = AsyncEverything::new(everything);
+//!
+//! let page = block_on(everything.query(SearchRequest::new("test1"))).unwrap();
+//! assert_eq!(page.total_results, 1);
+//! ```
+
+use crate::paged::Pager;
+use crate::sync::lock;
+use crate::{Everything, EverythingError, EverythingResult, PageCursor, ResultPage};
+use crate::{SearchRequest, SearchResult};
+use futures_core::Stream;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+type Job = Box<dyn FnOnce(&Everything) + Send>;
+
+/// Owns an `Everything` and runs the jobs given to it one at a time, away from the caller.
+/// Dropping it stops the worker once the queued jobs are done.
+#[derive(Debug)]
+pub struct AsyncEverything {
+    executor: Executor,
+}
+
+#[derive(Debug)]
+enum Executor {
+    Thread(Sender<Job>),
+    #[cfg(feature = "tokio")]
+    Tokio {
+        everything: Arc<Mutex<Everything>>,
+        handle: tokio::runtime::Handle,
+    },
+}
+
+impl AsyncEverything {
+    /// Moves `everything` to a dedicated worker thread.
+    ///
+    /// # Panics
+    /// When the operating system fails to create the thread, like `std::thread::spawn`.
+    pub fn new(everything: Everything) -> AsyncEverything {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        thread::Builder::new()
+            .name("everything-worker".to_string())
+            .spawn(move || {
+                for job in queue {
+                    // A panicking job drops its completer, the worker keeps serving like tokio's pool does.
+                    let _ = panic::catch_unwind(AssertUnwindSafe(|| job(&everything)));
+                }
+            })
+            .expect("failed to spawn the everything worker thread");
+        AsyncEverything {
+            executor: Executor::Thread(jobs),
+        }
+    }
+
+    /// Runs the jobs on the blocking thread pool of the current tokio runtime instead of a dedicated thread.
+    ///
+    /// # Panics
+    /// When called outside of a tokio runtime.
+    #[cfg(feature = "tokio")]
+    pub fn with_tokio(everything: Everything) -> AsyncEverything {
+        AsyncEverything {
+            executor: Executor::Tokio {
+                everything: Arc::new(Mutex::new(everything)),
+                handle: tokio::runtime::Handle::current(),
+            },
+        }
+    }
+
+    /// Runs `job` with the `Everything` owned by this handle.
+    /// The returned future resolves to `EverythingError::WorkerStopped` if the job panicked, later jobs still run.
+    pub fn run<R, F>(&self, job: F) -> Completion<R>
+    where
+        F: FnOnce(&Everything) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (completer, completion) = completion();
+        match &self.executor {
+            Executor::Thread(jobs) => {
+                // The worker lives as long as this handle, sending only fails if the thread is gone.
+                let _ = jobs.send(Box::new(move |everything| {
+                    completer.complete(job(everything))
+                }));
+            }
+            #[cfg(feature = "tokio")]
+            Executor::Tokio { everything, handle } => {
+                let everything = Arc::clone(everything);
+                handle.spawn_blocking(move || {
+                    let everything = lock(&everything);
+                    completer.complete(job(&everything));
+                });
+            }
+        }
+        completion
+    }
+
+    /// Runs `request`, see `Everything::search`.
+    pub async fn query(&self, request: SearchRequest) -> EverythingResult<ResultPage> {
+        self.run(move |everything| everything.search(&request))
+            .await?
+    }
+
+    /// Streams every result of `request` one page at a time, see `Everything::paged`.
+    pub fn paged(&self, request: impl Into<SearchRequest>, page_size: u32) -> PagedStream<'_> {
+        PagedStream {
+            everything: self,
+            pager: Pager::new(request.into(), page_size),
+            page: None,
+        }
+    }
+}
+
+/// The asynchronous version of `Paged`, returned by `AsyncEverything::paged`.
+/// Queries the next page once the results of the last one were taken.
+#[derive(Debug)]
+pub struct PagedStream<'a> {
+    everything: &'a AsyncEverything,
+    pager: Pager,
+    /// The page being queried.
+    page: Option<Completion<EverythingResult<ResultPage>>>,
+}
+
+impl<'a> PagedStream<'a> {
+    /// The position of the next result, see `Paged::cursor`.
+    pub fn cursor(&self) -> PageCursor {
+        self.pager.cursor()
+    }
+
+    /// Continues from `cursor`, see `Paged::resume`.
+    pub fn resume(mut self, cursor: PageCursor) -> PagedStream<'a> {
+        self.pager.resume(cursor);
+        self.page = None;
+        self
+    }
+
+    /// The next result, for use without a `Stream` combinator library.
+    pub async fn next(&mut self) -> Option<EverythingResult<SearchResult>> {
+        std::future::poll_fn(|context| Pin::new(&mut *self).poll_next(context)).await
+    }
+}
+
+impl Stream for PagedStream<'_> {
+    type Item = EverythingResult<SearchResult>;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.pager.pop() {
+                return Poll::Ready(Some(item));
+            }
+            let page = match &mut this.page {
+                Some(page) => page,
+                None => {
+                    let Some(request) = this.pager.next_request() else {
+                        return Poll::Ready(None);
+                    };
+                    let page = this
+                        .everything
+                        .run(move |everything| everything.search(&request));
+                    this.page.insert(page)
+                }
+            };
+            let Poll::Ready(page) = Pin::new(page).poll(context) else {
+                return Poll::Pending;
+            };
+            this.page = None;
+            this.pager.accept(page.and_then(|page| page));
+        }
+    }
+}
+
+/// The result of a job given to `AsyncEverything::run`.
+#[derive(Debug)]
+pub struct Completion<R> {
+    slot: Arc<Mutex<Slot<R>>>,
+}
+
+#[derive(Debug)]
+struct Slot<R> {
+    value: Option<EverythingResult<R>>,
+    waker: Option<Waker>,
+    /// Set once `value` was filled, it stays set after the value is taken.
+    filled: bool,
+}
+
+/// Fills the slot of a `Completion`, or fills it with `WorkerStopped` when dropped unused.
+struct Completer<R> {
+    slot: Arc<Mutex<Slot<R>>>,
+}
+
+fn completion<R>() -> (Completer<R>, Completion<R>) {
+    let slot = Arc::new(Mutex::new(Slot {
+        value: None,
+        waker: None,
+        filled: false,
+    }));
+    (
+        Completer {
+            slot: Arc::clone(&slot),
+        },
+        Completion { slot },
+    )
+}
+
+impl<R> Completer<R> {
+    fn complete(self, value: R) {
+        self.fill(Ok(value));
+    }
+
+    fn fill(&self, value: EverythingResult<R>) {
+        let mut slot = lock(&self.slot);
+        if slot.filled {
+            return;
+        }
+        slot.value = Some(value);
+        slot.filled = true;
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<R> Drop for Completer<R> {
+    fn drop(&mut self) {
+        self.fill(Err(EverythingError::WorkerStopped));
+    }
+}
+
+impl<R> Future for Completion<R> {
+    type Output = EverythingResult<R>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = lock(&self.slot);
+        match slot.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                slot.waker = Some(context.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, LoopbackTransport};
+    use crate::test_util::test_entries;
+    use crate::{EverythingSort, InMemoryBackend, InMemoryEntry};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+    use std::thread::Thread;
+    use std::time::Duration;
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    fn backend() -> InMemoryBackend {
+        InMemoryBackend::new(test_entries(&["test1.txt", "test2.jpg"]))
+    }
+
+    #[test]
+    fn queries_over_ipc() {
+        let everything = AsyncEverything::new(Everything::with_backend(IpcClient::new(
+            LoopbackTransport::new(backend()),
+        )));
+
+        let request = SearchRequest {
+            sort: EverythingSort::NameDescending,
+            ..SearchRequest::new("test")
+        };
+        let page = block_on(everything.query(request)).unwrap();
+        assert_eq!(page.total_results, 2);
+        let names: Vec<_> = page
+            .results
+            .iter()
+            .map(|result| result.file_name.as_deref().unwrap())
+            .collect();
+        assert_eq!(names, ["test2.jpg", "test1.txt"]);
+
+        let page = block_on(everything.query(SearchRequest::new("missing"))).unwrap();
+        assert_eq!(
+            page,
+            ResultPage {
+                total_results: 0,
+                total_folders: Some(0),
+                total_files: Some(0),
+                offset: 0,
+                results: vec![],
+                warnings: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn runs_jobs_in_order() {
+        let everything = AsyncEverything::new(Everything::with_backend(backend()));
+        static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+        let completions: Vec<_> = (0..8)
+            .map(|i| {
+                everything.run(move |_| {
+                    assert_eq!(STARTED.fetch_add(1, Ordering::SeqCst), i);
+                    i
+                })
+            })
+            .collect();
+        let threads: Vec<_> = completions
+            .into_iter()
+            .map(|completion| thread::spawn(move || block_on(completion).unwrap()))
+            .collect();
+        let results: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+        assert_eq!(results, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn times_out_slow_queries() {
+        let transport = LoopbackTransport::new(backend());
+        transport.set_delay(Duration::from_millis(200));
+        let everything = AsyncEverything::new(Everything::with_backend(IpcClient::new(transport)));
+
+        let request = SearchRequest {
+            timeout: Some(Duration::from_millis(10)),
+            ..SearchRequest::new("test1")
+        };
+        assert!(matches!(
+            block_on(everything.query(request)),
+            Err(EverythingError::QueryTimeout)
+        ));
+        let page = block_on(everything.query(SearchRequest::new("test2"))).unwrap();
+        assert_eq!(page.results[0].file_name.as_deref(), Some("test2.jpg"));
+    }
+
+    #[test]
+    fn streams_pages() {
+        let entries = (0..5)
+            .map(|i| InMemoryEntry::file("C:\\test", &format!("{i}.txt")))
+            .collect();
+        let everything = AsyncEverything::new(Everything::with_backend(IpcClient::new(
+            LoopbackTransport::new(InMemoryBackend::new(entries)),
+        )));
+
+        let mut paged = everything.paged(SearchRequest::new("txt"), 2);
+        let first = block_on(paged.next()).unwrap().unwrap();
+        assert_eq!(first.file_name.as_deref(), Some("0.txt"));
+        let cursor = paged.cursor();
+        assert_eq!(cursor.offset, 1);
+
+        let mut names = Vec::new();
+        let mut resumed = everything
+            .paged(SearchRequest::new("txt"), 2)
+            .resume(cursor);
+        while let Some(result) = block_on(resumed.next()) {
+            names.push(result.unwrap().file_name.unwrap());
+        }
+        assert_eq!(names, ["1.txt", "2.txt", "3.txt", "4.txt"]);
+        assert_eq!(resumed.cursor().total_results, Some(5));
+    }
+
+    #[test]
+    fn survives_a_panicking_job() {
+        let everything = AsyncEverything::new(Everything::with_backend(backend()));
+
+        let panicked = everything.run(|_| -> u32 { panic!("job failed") });
+        assert!(matches!(
+            block_on(panicked),
+            Err(EverythingError::WorkerStopped)
+        ));
+        let after = everything.run(|everything| everything.get_max_results());
+        assert_eq!(block_on(after).unwrap(), u32::MAX);
+        let page = block_on(everything.query(SearchRequest::new("test1"))).unwrap();
+        assert_eq!(page.total_results, 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn queries_on_tokio() {
+        let everything = AsyncEverything::with_tokio(Everything::with_backend(backend()));
+        let (first, second) = tokio::join!(
+            everything.query(SearchRequest::new("test1")),
+            everything.query(SearchRequest::new("test")),
+        );
+        assert_eq!(first.unwrap().total_results, 1);
+        assert_eq!(second.unwrap().total_results, 2);
+
+        let panicked = everything.run(|_| -> u32 { panic!("job failed") }).await;
+        assert!(matches!(panicked, Err(EverythingError::WorkerStopped)));
+        assert_eq!(
+            everything.run(|e| e.get_max_results()).await.unwrap(),
+            u32::MAX
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+bitflags! {
+    /// The `FILE_ATTRIBUTE_*` bits of a result, returned by `Everything::get_result_attributes`.
+    /// Bits without a name here are kept as is.
+    /// See <https://learn.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants>
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct FileAttributes: u32 {
+        const ReadOnly = 0x00000001;
+        const Hidden = 0x00000002;
+        const System = 0x00000004;
+        const Directory = 0x00000010;
+        const Archive = 0x00000020;
+        const Device = 0x00000040;
+        const Normal = 0x00000080;
+        const Temporary = 0x00000100;
+        const SparseFile = 0x00000200;
+        const ReparsePoint = 0x00000400;
+        const Compressed = 0x00000800;
+        const Offline = 0x00001000;
+        const NotContentIndexed = 0x00002000;
+        const Encrypted = 0x00004000;
+        const IntegrityStream = 0x00008000;
+        const Virtual = 0x00010000;
+        const NoScrubData = 0x00020000;
+        /// Shares its value with `FILE_ATTRIBUTE_RECALL_ON_OPEN`.
+        const ExtendedAttributes = 0x00040000;
+        const Pinned = 0x00080000;
+        const Unpinned = 0x00100000;
+        const RecallOnDataAccess = 0x00400000;
+        const StrictlySequential = 0x20000000;
+    }
+}
+
+impl FileAttributes {
+    pub fn is_read_only(self) -> bool {
+        self.contains(FileAttributes::ReadOnly)
+    }
+
+    pub fn is_hidden(self) -> bool {
+        self.contains(FileAttributes::Hidden)
+    }
+
+    pub fn is_system(self) -> bool {
+        self.contains(FileAttributes::System)
+    }
+
+    pub fn is_directory(self) -> bool {
+        self.contains(FileAttributes::Directory)
+    }
+
+    /// True for any reparse point.
+    /// The attributes do not carry the reparse tag so junctions and other reparse points are included too.
+    pub fn is_symlink(self) -> bool {
+        self.contains(FileAttributes::ReparsePoint)
+    }
+
+    pub fn is_compressed(self) -> bool {
+        self.contains(FileAttributes::Compressed)
+    }
+
+    pub fn is_encrypted(self) -> bool {
+        self.contains(FileAttributes::Encrypted)
+    }
+
+    /// True when the contents are not available locally, such as cloud files that have not been downloaded.
+    pub fn is_offline(self) -> bool {
+        self.intersects(FileAttributes::Offline | FileAttributes::RecallOnDataAccess)
+    }
+}
+
+/// Serialized as the raw bits, like the SDK returns them.
+impl Serialize for FileAttributes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for FileAttributes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer).map(FileAttributes::from_bits_retain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_attribute_bits() {
+        // A hidden system junction, as found in a user profile.
+        let attributes = FileAttributes::from_bits_retain(0x416);
+        assert!(attributes.is_hidden());
+        assert!(attributes.is_system());
+        assert!(attributes.is_directory());
+        assert!(attributes.is_symlink());
+        assert!(!attributes.is_read_only());
+        assert!(!attributes.is_offline());
+
+        let unknown = FileAttributes::from_bits_retain(0x0200_0000 | 0x20);
+        assert_eq!(unknown.bits(), 0x0200_0020);
+        assert!(unknown.contains(FileAttributes::Archive));
+        assert!(FileAttributes::RecallOnDataAccess.is_offline());
+
+        let json = serde_json::to_string(&unknown).unwrap();
+        assert_eq!(json, "33554464");
+        assert_eq!(
+            serde_json::from_str::<FileAttributes>(&json).unwrap(),
+            unknown
+        );
+    }
+}
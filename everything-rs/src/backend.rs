@@ -0,0 +1,241 @@
+//! The `SearchBackend` trait that `Everything` forwards its calls to.
+//! `IpcClient` talks to Everything directly, `FfiBackend` goes through the Everything SDK dll when the `ffi` feature is enabled.
+//! `InMemoryBackend` answers searches from a list of fixture entries and works on any platform.
+//! `ReconnectingBackend` wraps another backend and waits for Everything to come back after it restarts.
+
+#[cfg(all(target_os = "windows", feature = "ffi"))]
+mod ffi;
+mod memory;
+mod reconnect;
+
+#[cfg(all(target_os = "windows", feature = "ffi"))]
+pub use ffi::FfiBackend;
+pub use memory::{InMemoryBackend, InMemoryEntry};
+pub use reconnect::{Backoff, ConnectionState, ReconnectingBackend};
+
+use crate::{
+    EverythingError, EverythingRequestFlags, EverythingResult, EverythingSDKError, EverythingSort,
+    FileAttributes, FileTime, QueryLimits,
+};
+
+/// Fails with `EverythingSDKError::InvalidParameter` for a search containing a null character,
+/// which Everything would silently cut off there and run a different search.
+pub(crate) fn check_search(search: &str) -> EverythingResult<()> {
+    if search.contains('\0') {
+        Err(EverythingError::SDKError(
+            EverythingSDKError::InvalidParameter,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Fails with `EverythingSDKError::InvalidParameter` for an empty file name or one containing a null character.
+pub(crate) fn check_file_name(file_name: &str) -> EverythingResult<()> {
+    if file_name.is_empty() {
+        Err(EverythingError::SDKError(
+            EverythingSDKError::InvalidParameter,
+        ))
+    } else {
+        check_search(file_name)
+    }
+}
+
+/// The operations `Everything` needs from something that can answer searches.
+/// Methods mirror the Everything SDK functions of the same name and follow the same state model:
+/// search parameters are set first, `query` runs the search and the result accessors read from the last query.
+/// See <https://www.voidtools.com/support/everything/sdk/> for the semantics of each call.
+pub trait SearchBackend: Send {
+    /// Returns `Ok` if the last call succeeded, otherwise the error reported by the backend.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getlasterror/>
+    fn get_last_error(&self) -> EverythingResult<()>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_isdbloaded/>
+    fn is_db_loaded(&self) -> bool;
+
+    /// Returns the major, minor, revision and build number of the service.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmajorversion/>
+    fn version(&self) -> (u32, u32, u32, u32);
+
+    /// Sends an `EVERYTHING_IPC_*` command with `param` and returns the reply, 0 for commands Everything does not know.
+    /// Fails with `EverythingSDKError::Ipc` if Everything is not running.
+    /// See `Everything-SDK/ipc/everything_ipc.h` for the commands.
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32>;
+
+    /// Returns how many times the file with the full path `file_name` was run, 0 if it never was.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getruncountfromfilename/>
+    fn get_run_count(&self, file_name: &str) -> EverythingResult<u32>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setruncountfromfilename/>
+    fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()>;
+
+    /// Adds one to the run count of `file_name` and returns the new run count.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_incruncountfromfilename/>
+    fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setsearch/>
+    fn set_search(&self, search: &str);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getsearch/>
+    fn get_search(&self) -> EverythingResult<String>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setsort/>
+    fn set_sort(&self, sort: EverythingSort);
+
+    /// Returns `None` if the backend holds a sort code that `EverythingSort` does not know.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getsort/>
+    fn get_sort(&self) -> Option<EverythingSort>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_isfastsort/>
+    fn is_fast_sort(&self, sort: EverythingSort) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setmatchcase/>
+    fn set_match_case(&self, enable: bool);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmatchcase/>
+    fn get_match_case(&self) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setmatchpath/>
+    fn set_match_path(&self, enable: bool);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmatchpath/>
+    fn get_match_path(&self) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setmatchwholeword/>
+    fn set_match_whole_word(&self, enable: bool);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmatchwholeword/>
+    fn get_match_whole_word(&self) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setregex/>
+    fn set_regex(&self, enable: bool);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getregex/>
+    fn get_regex(&self) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setmax/>
+    fn set_max_results(&self, max_results: u32);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmax/>
+    fn get_max_results(&self) -> u32;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setoffset/>
+    fn set_result_offset(&self, offset: u32);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getoffset/>
+    fn get_result_offset(&self) -> u32;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setrequestflags/>
+    fn set_request_flags(&self, request_flags: EverythingRequestFlags);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getrequestflags/>
+    fn get_request_flags(&self) -> EverythingRequestFlags;
+
+    /// The request flags the last query was answered with, which may differ from the ones requested.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultlistrequestflags/>
+    fn get_result_list_request_flags(&self) -> EverythingRequestFlags;
+
+    /// Runs the search and blocks until the results are available.
+    /// Fails with `EverythingSDKError::InvalidParameter` if the search contains a null character.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_query/>
+    fn query(&self) -> EverythingResult<()> {
+        self.query_with(&QueryLimits::default())
+    }
+
+    /// Runs the search like `query` but gives up with `EverythingError::Cancelled` or `EverythingError::QueryTimeout` once `limits` say so.
+    /// The result list is empty after giving up and a reply that arrives later is discarded.
+    fn query_with(&self, limits: &QueryLimits) -> EverythingResult<()>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_reset/>
+    fn reset(&self);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getnumresults/>
+    fn get_num_results(&self) -> u32;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_gettotresults/>
+    fn get_total_results(&self) -> u32;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getnumfileresults/>
+    fn get_num_file_results(&self) -> u32;
+
+    /// Volumes are counted as folders.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getnumfolderresults/>
+    fn get_num_folder_results(&self) -> u32;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_gettotfileresults/>
+    fn get_total_file_results(&self) -> u32;
+
+    /// Volumes are counted as folders.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_gettotfolderresults/>
+    fn get_total_folder_results(&self) -> u32;
+
+    /// Sorts the current results by path then file name, without running the query again.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_sortresultsbypath/>
+    fn sort_results_by_path(&self);
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_isfileresult/>
+    fn is_result_file(&self, index: u32) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_isfolderresult/>
+    fn is_result_folder(&self, index: u32) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_isvolumeresult/>
+    fn is_result_volume(&self, index: u32) -> bool;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultfullpathname/>
+    fn get_result_full_path(&self, index: u32) -> EverythingResult<String>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultpath/>
+    fn get_result_path(&self, index: u32) -> EverythingResult<String>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultfilename/>
+    fn get_result_file_name(&self, index: u32) -> EverythingResult<String>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultextension/>
+    fn get_result_extension(&self, index: u32) -> EverythingResult<String>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultsize/>
+    fn get_result_size(&self, index: u32) -> EverythingResult<u64>;
+
+    /// Returns the created date.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdatecreated/>
+    fn get_result_created_date(&self, index: u32) -> EverythingResult<FileTime>;
+
+    /// Returns the modified date.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdatemodified/>
+    fn get_result_modified_date(&self, index: u32) -> EverythingResult<FileTime>;
+
+    /// Returns the accessed date.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdateaccessed/>
+    fn get_result_accessed_date(&self, index: u32) -> EverythingResult<FileTime>;
+
+    /// Returns the last time the result was run from Everything.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdaterun/>
+    fn get_result_run_date(&self, index: u32) -> EverythingResult<FileTime>;
+
+    /// Returns the last time the result was changed.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdaterecentlychanged/>
+    fn get_result_recently_changed_date(&self, index: u32) -> EverythingResult<FileTime>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultattributes/>
+    fn get_result_attributes(&self, index: u32) -> EverythingResult<FileAttributes>;
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultruncount/>
+    fn get_result_run_count(&self, index: u32) -> EverythingResult<u32>;
+
+    /// The file list the result came from, empty if it is not from a file list.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultfilelistfilename/>
+    fn get_result_file_list_file_name(&self, index: u32) -> EverythingResult<String>;
+
+    /// The file name with the matched text surrounded by `*`, a literal `*` is doubled.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresulthighlightedfilename/>
+    fn get_result_highlighted_file_name(&self, index: u32) -> EverythingResult<String>;
+
+    /// The path with the matched text surrounded by `*`, a literal `*` is doubled.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresulthighlightedpath/>
+    fn get_result_highlighted_path(&self, index: u32) -> EverythingResult<String>;
+
+    /// The full path with the matched text surrounded by `*`, a literal `*` is doubled.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresulthighlightedfullpathandfilename/>
+    fn get_result_highlighted_full_path(&self, index: u32) -> EverythingResult<String>;
+}
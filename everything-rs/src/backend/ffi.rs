@@ -0,0 +1,883 @@
+use super::{check_file_name, check_search, SearchBackend};
+use crate::ipc::{IpcTransport, WindowsTransport};
+use crate::result::read_result;
+use crate::session::{GlobalState, Session, SessionGuard};
+use crate::sync::lock;
+use crate::sys::*;
+use crate::{
+    EverythingError, EverythingRequestFlags, EverythingResult, EverythingSDKError, EverythingSort,
+    FileAttributes, FileTime, Highlighted, QueryLimits, ResultKind, SearchResult,
+};
+use std::sync::{Mutex, MutexGuard};
+use widestring::{U16CStr, U16CString};
+
+impl From<FILETIME> for FileTime {
+    fn from(file_time: FILETIME) -> FileTime {
+        FileTime::from_raw(
+            ((file_time.dwHighDateTime as u64) << 32) | (file_time.dwLowDateTime as u64),
+        )
+    }
+}
+
+/// See <https://www.voidtools.com/support/everything/sdk/everything_getlasterror/>
+fn get_last_error() -> EverythingResult<()> {
+    let error_code = unsafe { Everything_GetLastError() };
+    match EverythingSDKError::from_code(error_code) {
+        EverythingSDKError::Ok => Ok(()),
+        err => Err(EverythingError::SDKError(err)),
+    }
+}
+
+/// Reads a null terminated u16 string returned by the Everything API.
+/// Fails with `EverythingError::NullPointerError` if `ptr` is null.
+/// # Arguments
+///
+/// * `ptr` - A pointer to a u16 string returned by the Everything API.
+fn parse_string_ptr(ptr: *const u16) -> EverythingResult<String> {
+    if ptr.is_null() {
+        return Err(EverythingError::NullPointerError);
+    }
+
+    Ok(unsafe { U16CStr::from_ptr_str(ptr).to_string_lossy() })
+}
+
+/// Reads a string result, reporting the SDK error if the pointer is null and the SDK recorded one.
+fn result_string(ptr: *const u16) -> EverythingResult<String> {
+    if ptr.is_null() {
+        get_last_error()?;
+    }
+
+    parse_string_ptr(ptr)
+}
+
+/// Reads a FILETIME result with one of the `Everything_GetResultDate*` functions.
+fn result_date(
+    index: u32,
+    get_date: unsafe extern "C" fn(DWORD, *mut FILETIME) -> BOOL,
+) -> EverythingResult<FileTime> {
+    let mut file_time: FILETIME = FILETIME {
+        dwLowDateTime: 0,
+        dwHighDateTime: 0,
+    };
+
+    let success = unsafe { get_date(index, &mut file_time) };
+
+    if success == 0 {
+        get_last_error()?;
+    }
+
+    Ok(file_time.into())
+}
+
+/// The SDK's process-global state, cleaned up when the last `FfiBackend` is dropped.
+static SDK: GlobalState = GlobalState::new(|| unsafe { Everything_CleanUp() });
+
+/// Direct calls into the SDK, only made while holding its `SessionGuard`.
+struct Sdk<'a> {
+    _guard: &'a SessionGuard<'a>,
+    transport: &'a WindowsTransport,
+}
+
+impl SearchBackend for Sdk<'_> {
+    fn get_last_error(&self) -> EverythingResult<()> {
+        get_last_error()
+    }
+
+    fn is_db_loaded(&self) -> bool {
+        unsafe { Everything_IsDBLoaded() != 0 }
+    }
+
+    fn version(&self) -> (u32, u32, u32, u32) {
+        unsafe {
+            (
+                Everything_GetMajorVersion(),
+                Everything_GetMinorVersion(),
+                Everything_GetRevision(),
+                Everything_GetBuildNumber(),
+            )
+        }
+    }
+
+    /// The dll has no function for `EVERYTHING_IPC_*` commands, so they are sent as the window message Everything reads them from.
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        self.transport.send_command(command, param)
+    }
+
+    /// The dll returns 0 both for a file that was never run and when Everything is not running,
+    /// only the latter records an error.
+    fn get_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        check_file_name(file_name)?;
+        let wide_file_name = U16CString::from_str_truncate(file_name);
+        let run_count = unsafe { Everything_GetRunCountFromFileNameW(wide_file_name.as_ptr()) };
+
+        if run_count == 0 {
+            get_last_error()?;
+        }
+
+        Ok(run_count)
+    }
+
+    fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()> {
+        check_file_name(file_name)?;
+        let wide_file_name = U16CString::from_str_truncate(file_name);
+        let success =
+            unsafe { Everything_SetRunCountFromFileNameW(wide_file_name.as_ptr(), run_count) };
+
+        if success == 0 {
+            get_last_error()?;
+        }
+
+        Ok(())
+    }
+
+    /// The dll returns 0 on failure but only records an error when Everything is not running.
+    fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        check_file_name(file_name)?;
+        let wide_file_name = U16CString::from_str_truncate(file_name);
+        let run_count = unsafe { Everything_IncRunCountFromFileNameW(wide_file_name.as_ptr()) };
+
+        if run_count == 0 {
+            get_last_error()?;
+            return Err(EverythingError::SDKError(EverythingSDKError::InvalidCall));
+        }
+
+        Ok(run_count)
+    }
+
+    fn set_search(&self, search: &str) {
+        // `FfiBackend` does not query searches containing null characters, see `check_search`.
+        let wide_search = U16CString::from_str_truncate(search);
+        unsafe {
+            Everything_SetSearchW(wide_search.as_ptr());
+        }
+    }
+
+    fn get_search(&self) -> EverythingResult<String> {
+        let search_ptr = unsafe { Everything_GetSearchW() };
+        parse_string_ptr(search_ptr)
+    }
+
+    fn set_sort(&self, sort: EverythingSort) {
+        unsafe {
+            Everything_SetSort(sort.into());
+        }
+    }
+
+    fn get_sort(&self) -> Option<EverythingSort> {
+        let sort = unsafe { Everything_GetSort() };
+        sort.try_into().ok()
+    }
+
+    fn is_fast_sort(&self, sort: EverythingSort) -> bool {
+        unsafe { Everything_IsFastSort(sort.into()) != 0 }
+    }
+
+    fn set_match_case(&self, enable: bool) {
+        unsafe {
+            Everything_SetMatchCase(enable as BOOL);
+        }
+    }
+
+    fn get_match_case(&self) -> bool {
+        unsafe { Everything_GetMatchCase() != 0 }
+    }
+
+    fn set_match_path(&self, enable: bool) {
+        unsafe {
+            Everything_SetMatchPath(enable as BOOL);
+        }
+    }
+
+    fn get_match_path(&self) -> bool {
+        unsafe { Everything_GetMatchPath() != 0 }
+    }
+
+    fn set_match_whole_word(&self, enable: bool) {
+        unsafe {
+            Everything_SetMatchWholeWord(enable as BOOL);
+        }
+    }
+
+    fn get_match_whole_word(&self) -> bool {
+        unsafe { Everything_GetMatchWholeWord() != 0 }
+    }
+
+    fn set_regex(&self, enable: bool) {
+        unsafe {
+            Everything_SetRegex(enable as BOOL);
+        }
+    }
+
+    fn get_regex(&self) -> bool {
+        unsafe { Everything_GetRegex() != 0 }
+    }
+
+    fn set_max_results(&self, max_results: u32) {
+        unsafe {
+            Everything_SetMax(max_results);
+        }
+    }
+
+    fn get_max_results(&self) -> u32 {
+        unsafe { Everything_GetMax() }
+    }
+
+    fn set_result_offset(&self, offset: u32) {
+        unsafe {
+            Everything_SetOffset(offset);
+        }
+    }
+
+    fn get_result_offset(&self) -> u32 {
+        unsafe { Everything_GetOffset() }
+    }
+
+    fn set_request_flags(&self, request_flags: EverythingRequestFlags) {
+        unsafe {
+            Everything_SetRequestFlags(request_flags.bits());
+        }
+    }
+
+    fn get_request_flags(&self) -> EverythingRequestFlags {
+        let request_flags = unsafe { Everything_GetRequestFlags() };
+        EverythingRequestFlags::from_bits_truncate(request_flags)
+    }
+
+    fn get_result_list_request_flags(&self) -> EverythingRequestFlags {
+        let request_flags = unsafe { Everything_GetResultListRequestFlags() };
+        EverythingRequestFlags::from_bits_truncate(request_flags)
+    }
+
+    fn query_with(&self, limits: &QueryLimits) -> EverythingResult<()> {
+        limits.check()?;
+        let result = unsafe { Everything_QueryW(1) };
+        if result == 0 {
+            get_last_error()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn reset(&self) {
+        unsafe {
+            Everything_Reset();
+        }
+    }
+
+    fn get_num_results(&self) -> u32 {
+        unsafe { Everything_GetNumResults() }
+    }
+
+    fn get_total_results(&self) -> u32 {
+        unsafe { Everything_GetTotResults() }
+    }
+
+    fn get_num_file_results(&self) -> u32 {
+        unsafe { Everything_GetNumFileResults() }
+    }
+
+    fn get_num_folder_results(&self) -> u32 {
+        unsafe { Everything_GetNumFolderResults() }
+    }
+
+    fn get_total_file_results(&self) -> u32 {
+        unsafe { Everything_GetTotFileResults() }
+    }
+
+    fn get_total_folder_results(&self) -> u32 {
+        unsafe { Everything_GetTotFolderResults() }
+    }
+
+    fn sort_results_by_path(&self) {
+        unsafe {
+            Everything_SortResultsByPath();
+        }
+    }
+
+    fn is_result_file(&self, index: u32) -> bool {
+        unsafe { Everything_IsFileResult(index) != 0 }
+    }
+
+    fn is_result_folder(&self, index: u32) -> bool {
+        unsafe { Everything_IsFolderResult(index) != 0 }
+    }
+
+    fn is_result_volume(&self, index: u32) -> bool {
+        unsafe { Everything_IsVolumeResult(index) != 0 }
+    }
+
+    fn get_result_full_path(&self, index: u32) -> EverythingResult<String> {
+        let path_length =
+            unsafe { Everything_GetResultFullPathNameW(index, std::ptr::null_mut(), 0) };
+        if path_length == 0 {
+            get_last_error()?;
+        }
+
+        // Length does not include null terminator
+        let mut path_buffer = vec![0u16; path_length as usize + 1];
+        let count_copied = unsafe {
+            Everything_GetResultFullPathNameW(
+                index,
+                path_buffer.as_mut_ptr(),
+                path_buffer.len() as u32,
+            )
+        };
+        path_buffer.truncate(count_copied as usize);
+        Ok(String::from_utf16_lossy(&path_buffer))
+    }
+
+    fn get_result_path(&self, index: u32) -> EverythingResult<String> {
+        result_string(unsafe { Everything_GetResultPathW(index) })
+    }
+
+    fn get_result_file_name(&self, index: u32) -> EverythingResult<String> {
+        result_string(unsafe { Everything_GetResultFileNameW(index) })
+    }
+
+    fn get_result_extension(&self, index: u32) -> EverythingResult<String> {
+        result_string(unsafe { Everything_GetResultExtensionW(index) })
+    }
+
+    fn get_result_size(&self, index: u32) -> EverythingResult<u64> {
+        let mut size: LARGE_INTEGER = LARGE_INTEGER { QuadPart: 0 };
+
+        let success = unsafe { Everything_GetResultSize(index, &mut size) };
+
+        if success == 0 {
+            get_last_error()?;
+        }
+
+        Ok(unsafe { size.QuadPart as u64 })
+    }
+
+    fn get_result_created_date(&self, index: u32) -> EverythingResult<FileTime> {
+        result_date(index, Everything_GetResultDateCreated)
+    }
+
+    fn get_result_modified_date(&self, index: u32) -> EverythingResult<FileTime> {
+        result_date(index, Everything_GetResultDateModified)
+    }
+
+    fn get_result_accessed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        result_date(index, Everything_GetResultDateAccessed)
+    }
+
+    fn get_result_run_date(&self, index: u32) -> EverythingResult<FileTime> {
+        result_date(index, Everything_GetResultDateRun)
+    }
+
+    fn get_result_recently_changed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        result_date(index, Everything_GetResultDateRecentlyChanged)
+    }
+
+    fn get_result_attributes(&self, index: u32) -> EverythingResult<FileAttributes> {
+        self.check_result(index, EverythingRequestFlags::Attributes)?;
+        let attributes = unsafe { Everything_GetResultAttributes(index) };
+        Ok(FileAttributes::from_bits_retain(attributes))
+    }
+
+    fn get_result_run_count(&self, index: u32) -> EverythingResult<u32> {
+        self.check_result(index, EverythingRequestFlags::RunCount)?;
+        Ok(unsafe { Everything_GetResultRunCount(index) })
+    }
+
+    fn get_result_file_list_file_name(&self, index: u32) -> EverythingResult<String> {
+        result_string(unsafe { Everything_GetResultFileListFileNameW(index) })
+    }
+
+    fn get_result_highlighted_file_name(&self, index: u32) -> EverythingResult<String> {
+        result_string(unsafe { Everything_GetResultHighlightedFileNameW(index) })
+    }
+
+    fn get_result_highlighted_path(&self, index: u32) -> EverythingResult<String> {
+        result_string(unsafe { Everything_GetResultHighlightedPathW(index) })
+    }
+
+    fn get_result_highlighted_full_path(&self, index: u32) -> EverythingResult<String> {
+        result_string(unsafe { Everything_GetResultHighlightedFullPathAndFileNameW(index) })
+    }
+}
+
+impl Sdk<'_> {
+    /// `Everything_GetResultAttributes` and `Everything_GetResultRunCount` return a plausible value on failure,
+    /// so the index and request flags are checked up front like the SDK checks them.
+    fn check_result(&self, index: u32, flag: EverythingRequestFlags) -> EverythingResult<()> {
+        let error = if index >= self.get_num_results() {
+            EverythingSDKError::InvalidIndex
+        } else if !self.get_result_list_request_flags().contains(flag) {
+            EverythingSDKError::InvalidRequest
+        } else {
+            return Ok(());
+        };
+        Err(EverythingError::SDKError(error))
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    search: String,
+    match_case: bool,
+    match_path: bool,
+    match_whole_word: bool,
+    regex: bool,
+    sort: EverythingSort,
+    max_results: u32,
+    offset: u32,
+    request_flags: EverythingRequestFlags,
+    last_error: EverythingSDKError,
+    /// The results of the last successful query.
+    list: Option<ResultList>,
+}
+
+/// An owned copy of the SDK's result list, taken before another backend can replace it.
+#[derive(Debug)]
+struct ResultList {
+    request_flags: EverythingRequestFlags,
+    total_results: u32,
+    /// The total files and folders, `None` when the SDK does not know them.
+    totals: Option<(u32, u32)>,
+    rows: Vec<SearchResult>,
+}
+
+impl State {
+    /// The same defaults as the SDK.
+    fn new() -> State {
+        State {
+            search: String::new(),
+            match_case: false,
+            match_path: false,
+            match_whole_word: false,
+            regex: false,
+            sort: EverythingSort::NameAscending,
+            max_results: u32::MAX,
+            offset: 0,
+            request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::Path,
+            last_error: EverythingSDKError::Ok,
+            list: None,
+        }
+    }
+
+    /// Records the outcome of a call so `get_last_error` can report it.
+    /// A query given up on by its limits leaves the error of the previous call, Everything did nothing wrong.
+    fn record<R>(&mut self, result: EverythingResult<R>) -> EverythingResult<R> {
+        self.last_error = match &result {
+            Ok(_) => EverythingSDKError::Ok,
+            Err(EverythingError::SDKError(err)) => *err,
+            Err(EverythingError::Cancelled | EverythingError::QueryTimeout) => self.last_error,
+            Err(_) => EverythingSDKError::Ipc,
+        };
+        result
+    }
+
+    /// Reads from the result list, failing with `InvalidCall` if there is none.
+    fn list<R>(&mut self, read: impl FnOnce(&ResultList) -> R) -> EverythingResult<R> {
+        let value = self.list.as_ref().map(read);
+        self.record(value.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+    }
+
+    /// Looks up a result, failing like the SDK when there is no result list or the index is out of range.
+    fn row(&mut self, index: u32) -> EverythingResult<&SearchResult> {
+        let row = match &self.list {
+            None => Err(EverythingSDKError::InvalidCall),
+            Some(list) => list
+                .rows
+                .get(index as usize)
+                .ok_or(EverythingSDKError::InvalidIndex),
+        };
+        self.last_error = row.err().unwrap_or(EverythingSDKError::Ok);
+        row.map_err(EverythingError::SDKError)
+    }
+
+    /// Reads a field of a result, failing with `InvalidRequest` if the field was not requested.
+    fn field<R>(
+        &mut self,
+        index: u32,
+        read: impl FnOnce(&SearchResult) -> Option<R>,
+    ) -> EverythingResult<R> {
+        let value = read(self.row(index)?);
+        self.record(value.ok_or(EverythingError::SDKError(
+            EverythingSDKError::InvalidRequest,
+        )))
+    }
+}
+
+/// Backend that calls into the Everything SDK dll through `everything-sys-bindgen`.
+///
+/// The SDK keeps a single search state per process. Each `FfiBackend` keeps its own search parameters instead,
+/// and `query` takes exclusive access to the SDK, runs the search and copies the results before releasing it,
+/// so backends on different threads never see each other's searches or results.
+/// The SDK is cleaned up when the last `FfiBackend` is dropped.
+#[derive(Debug)]
+pub struct FfiBackend {
+    session: Session,
+    /// Reaches the same Everything as the dll, which only looks for the unnamed instance.
+    transport: WindowsTransport,
+    state: Mutex<State>,
+}
+
+impl FfiBackend {
+    pub fn new() -> FfiBackend {
+        FfiBackend {
+            session: Session::open(&SDK),
+            transport: WindowsTransport::new(),
+            state: Mutex::new(State::new()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+
+    /// Runs `call` with exclusive access to the SDK.
+    fn with_sdk<R>(&self, call: impl FnOnce(&Sdk) -> R) -> R {
+        let guard = self.session.lock();
+        call(&Sdk {
+            _guard: &guard,
+            transport: &self.transport,
+        })
+    }
+}
+
+impl Default for FfiBackend {
+    fn default() -> FfiBackend {
+        FfiBackend::new()
+    }
+}
+
+/// Applies the search parameters, runs the query and copies the results.
+fn run_query(sdk: &Sdk, state: &State, limits: &QueryLimits) -> EverythingResult<ResultList> {
+    sdk.reset();
+    sdk.set_search(&state.search);
+    sdk.set_match_case(state.match_case);
+    sdk.set_match_path(state.match_path);
+    sdk.set_match_whole_word(state.match_whole_word);
+    sdk.set_regex(state.regex);
+    sdk.set_sort(state.sort);
+    sdk.set_max_results(state.max_results);
+    sdk.set_result_offset(state.offset);
+    sdk.set_request_flags(state.request_flags);
+    sdk.query_with(limits)?;
+
+    let request_flags = sdk.get_result_list_request_flags();
+    let total_results = sdk.get_total_results();
+    // Only known for version 1 queries, otherwise the SDK reports `InvalidCall`.
+    let totals = (sdk.get_total_file_results(), sdk.get_total_folder_results());
+    let totals = sdk.get_last_error().ok().map(|_| totals);
+    let rows = (0..sdk.get_num_results())
+        .map(|index| read_result(sdk, index))
+        .collect::<EverythingResult<_>>()?;
+
+    Ok(ResultList {
+        request_flags,
+        total_results,
+        totals,
+        rows,
+    })
+}
+
+/// The path and name of a result lower cased for sorting, from either the separate fields or the full path.
+fn sort_key(row: &SearchResult) -> Option<(String, String)> {
+    let (path, name) = match (&row.path, &row.file_name, &row.full_path) {
+        (Some(path), Some(name), _) => (path.as_str(), name.as_str()),
+        (_, _, Some(full_path)) => full_path.rsplit_once('\\').unwrap_or(("", full_path)),
+        _ => return None,
+    };
+    Some((path.to_lowercase(), name.to_lowercase()))
+}
+
+impl SearchBackend for FfiBackend {
+    fn get_last_error(&self) -> EverythingResult<()> {
+        match self.state().last_error {
+            EverythingSDKError::Ok => Ok(()),
+            err => Err(EverythingError::SDKError(err)),
+        }
+    }
+
+    fn is_db_loaded(&self) -> bool {
+        self.with_sdk(|sdk| sdk.is_db_loaded())
+    }
+
+    fn version(&self) -> (u32, u32, u32, u32) {
+        self.with_sdk(|sdk| sdk.version())
+    }
+
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        self.with_sdk(|sdk| sdk.send_command(command, param))
+    }
+
+    fn get_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.with_sdk(|sdk| sdk.get_run_count(file_name))
+    }
+
+    fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()> {
+        self.with_sdk(|sdk| sdk.set_run_count(file_name, run_count))
+    }
+
+    fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.with_sdk(|sdk| sdk.inc_run_count(file_name))
+    }
+
+    fn set_search(&self, search: &str) {
+        self.state().search = search.to_string();
+    }
+
+    fn get_search(&self) -> EverythingResult<String> {
+        Ok(self.state().search.clone())
+    }
+
+    fn set_sort(&self, sort: EverythingSort) {
+        self.state().sort = sort;
+    }
+
+    fn get_sort(&self) -> Option<EverythingSort> {
+        Some(self.state().sort)
+    }
+
+    fn is_fast_sort(&self, sort: EverythingSort) -> bool {
+        self.with_sdk(|sdk| sdk.is_fast_sort(sort))
+    }
+
+    fn set_match_case(&self, enable: bool) {
+        self.state().match_case = enable;
+    }
+
+    fn get_match_case(&self) -> bool {
+        self.state().match_case
+    }
+
+    fn set_match_path(&self, enable: bool) {
+        self.state().match_path = enable;
+    }
+
+    fn get_match_path(&self) -> bool {
+        self.state().match_path
+    }
+
+    fn set_match_whole_word(&self, enable: bool) {
+        self.state().match_whole_word = enable;
+    }
+
+    fn get_match_whole_word(&self) -> bool {
+        self.state().match_whole_word
+    }
+
+    fn set_regex(&self, enable: bool) {
+        self.state().regex = enable;
+    }
+
+    fn get_regex(&self) -> bool {
+        self.state().regex
+    }
+
+    fn set_max_results(&self, max_results: u32) {
+        self.state().max_results = max_results;
+    }
+
+    fn get_max_results(&self) -> u32 {
+        self.state().max_results
+    }
+
+    fn set_result_offset(&self, offset: u32) {
+        self.state().offset = offset;
+    }
+
+    fn get_result_offset(&self) -> u32 {
+        self.state().offset
+    }
+
+    fn set_request_flags(&self, request_flags: EverythingRequestFlags) {
+        self.state().request_flags = request_flags;
+    }
+
+    fn get_request_flags(&self) -> EverythingRequestFlags {
+        self.state().request_flags
+    }
+
+    fn get_result_list_request_flags(&self) -> EverythingRequestFlags {
+        self.state()
+            .list(|list| list.request_flags)
+            .unwrap_or(EverythingRequestFlags::empty())
+    }
+
+    /// The SDK blocks until Everything replies, so the limits are checked before and after the query
+    /// and the results of a query that finished too late are thrown away.
+    fn query_with(&self, limits: &QueryLimits) -> EverythingResult<()> {
+        let mut state = self.state();
+        state.list = None;
+        let list = check_search(&state.search)
+            .and_then(|_| self.with_sdk(|sdk| run_query(sdk, &state, limits)));
+        let list = list.and_then(|list| limits.check().map(|_| list));
+        state.list = Some(state.record(list)?);
+        Ok(())
+    }
+
+    fn reset(&self) {
+        *self.state() = State::new();
+    }
+
+    fn get_num_results(&self) -> u32 {
+        self.state()
+            .list(|list| list.rows.len() as u32)
+            .unwrap_or(0)
+    }
+
+    fn get_total_results(&self) -> u32 {
+        self.state().list(|list| list.total_results).unwrap_or(0)
+    }
+
+    fn get_num_file_results(&self) -> u32 {
+        self.state()
+            .list(|list| {
+                let files = list.rows.iter().filter(|row| row.kind == ResultKind::File);
+                files.count() as u32
+            })
+            .unwrap_or(0)
+    }
+
+    fn get_num_folder_results(&self) -> u32 {
+        self.state()
+            .list(|list| {
+                let folders = list.rows.iter().filter(|row| row.kind != ResultKind::File);
+                folders.count() as u32
+            })
+            .unwrap_or(0)
+    }
+
+    fn get_total_file_results(&self) -> u32 {
+        let mut state = self.state();
+        let totals = state.list(|list| list.totals).ok().flatten();
+        state
+            .record(totals.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .map_or(0, |(files, _)| files)
+    }
+
+    fn get_total_folder_results(&self) -> u32 {
+        let mut state = self.state();
+        let totals = state.list(|list| list.totals).ok().flatten();
+        state
+            .record(totals.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .map_or(0, |(_, folders)| folders)
+    }
+
+    fn sort_results_by_path(&self) {
+        let mut state = self.state();
+        let sorted = state
+            .list
+            .as_mut()
+            .map(|list| list.rows.sort_by_cached_key(sort_key));
+        let _ =
+            state.record(sorted.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)));
+    }
+
+    fn is_result_file(&self, index: u32) -> bool {
+        self.state()
+            .row(index)
+            .is_ok_and(|row| row.kind == ResultKind::File)
+    }
+
+    fn is_result_folder(&self, index: u32) -> bool {
+        self.state()
+            .row(index)
+            .is_ok_and(|row| row.kind != ResultKind::File)
+    }
+
+    fn is_result_volume(&self, index: u32) -> bool {
+        self.state()
+            .row(index)
+            .is_ok_and(|row| row.kind == ResultKind::Volume)
+    }
+
+    fn get_result_full_path(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |row| row.full_path.clone())
+    }
+
+    fn get_result_path(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |row| row.path.clone())
+    }
+
+    fn get_result_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |row| row.file_name.clone())
+    }
+
+    fn get_result_extension(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |row| row.extension.clone())
+    }
+
+    fn get_result_size(&self, index: u32) -> EverythingResult<u64> {
+        self.state().field(index, |row| row.size)
+    }
+
+    fn get_result_created_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state().field(index, |row| row.date_created)
+    }
+
+    fn get_result_modified_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state().field(index, |row| row.date_modified)
+    }
+
+    fn get_result_accessed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state().field(index, |row| row.date_accessed)
+    }
+
+    fn get_result_run_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state().field(index, |row| row.date_run)
+    }
+
+    fn get_result_recently_changed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state().field(index, |row| row.date_recently_changed)
+    }
+
+    fn get_result_attributes(&self, index: u32) -> EverythingResult<FileAttributes> {
+        self.state().field(index, |row| row.attributes)
+    }
+
+    fn get_result_run_count(&self, index: u32) -> EverythingResult<u32> {
+        self.state().field(index, |row| row.run_count)
+    }
+
+    fn get_result_file_list_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .field(index, |row| row.file_list_file_name.clone())
+    }
+
+    fn get_result_highlighted_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |row| {
+            row.highlighted_file_name
+                .as_ref()
+                .map(Highlighted::to_markup)
+        })
+    }
+
+    fn get_result_highlighted_path(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |row| {
+            row.highlighted_path.as_ref().map(Highlighted::to_markup)
+        })
+    }
+
+    fn get_result_highlighted_full_path(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |row| {
+            row.highlighted_full_path
+                .as_ref()
+                .map(Highlighted::to_markup)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_string_ptr() {
+        let test_string = "test\0";
+        let test_string_ptr = test_string.encode_utf16().collect::<Vec<u16>>();
+        let test_string_ptr = test_string_ptr.as_ptr();
+        let parsed_string = parse_string_ptr(test_string_ptr).unwrap();
+        assert_eq!(parsed_string, test_string[0..4]);
+
+        assert!(matches!(
+            parse_string_ptr(std::ptr::null()),
+            Err(EverythingError::NullPointerError)
+        ));
+    }
+}
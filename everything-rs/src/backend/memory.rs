@@ -0,0 +1,1171 @@
+mod search;
+
+use super::{check_file_name, check_search, SearchBackend};
+use crate::ipc::{
+    EVERYTHING_IPC_DELETE_RUN_HISTORY, EVERYTHING_IPC_EXIT, EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED,
+    EVERYTHING_IPC_FILE_INFO_FILE_SIZE, EVERYTHING_IPC_GET_BUILD_NUMBER,
+    EVERYTHING_IPC_GET_MAJOR_VERSION, EVERYTHING_IPC_GET_MINOR_VERSION,
+    EVERYTHING_IPC_GET_REVISION, EVERYTHING_IPC_GET_TARGET_MACHINE, EVERYTHING_IPC_IS_ADMIN,
+    EVERYTHING_IPC_IS_APPDATA, EVERYTHING_IPC_IS_DB_BUSY, EVERYTHING_IPC_IS_DB_LOADED,
+    EVERYTHING_IPC_IS_FAST_SORT, EVERYTHING_IPC_IS_FILE_INFO_INDEXED,
+    EVERYTHING_IPC_IS_NTFS_DRIVE_INDEXED, EVERYTHING_IPC_QUEUE_REBUILD_DB,
+    EVERYTHING_IPC_REBUILD_DB, EVERYTHING_IPC_SAVE_DB, EVERYTHING_IPC_SAVE_RUN_HISTORY,
+    EVERYTHING_IPC_UPDATE_ALL_FOLDER_INDEXES,
+};
+use crate::sync::lock;
+use crate::{
+    EverythingError, EverythingRequestFlags, EverythingResult, EverythingSDKError, EverythingSort,
+    FileAttributes, FileTime, QueryLimits, ResultKind, TargetMachine,
+};
+use search::{highlight, MatchOptions, Search};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// A single file, folder or volume held by an `InMemoryBackend`.
+/// Dates are raw FILETIME values, see `FileTime`, and attributes are raw `FILE_ATTRIBUTE_*` bits, see `FileAttributes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InMemoryEntry {
+    pub kind: ResultKind,
+    pub name: String,
+    /// The parent folder without a trailing separator. Empty for volumes.
+    pub path: String,
+    pub size: u64,
+    pub date_created: u64,
+    pub date_modified: u64,
+    pub date_accessed: u64,
+    pub date_run: u64,
+    pub date_recently_changed: u64,
+    pub attributes: u32,
+    pub run_count: u32,
+}
+
+impl InMemoryEntry {
+    fn new(kind: ResultKind, path: &str, name: &str) -> InMemoryEntry {
+        InMemoryEntry {
+            kind,
+            name: name.to_string(),
+            path: path.trim_end_matches('\\').to_string(),
+            size: 0,
+            date_created: 0,
+            date_modified: 0,
+            date_accessed: 0,
+            date_run: 0,
+            date_recently_changed: 0,
+            attributes: 0,
+            run_count: 0,
+        }
+    }
+
+    /// A file named `name` inside the folder `path`.
+    pub fn file(path: &str, name: &str) -> InMemoryEntry {
+        InMemoryEntry::new(ResultKind::File, path, name)
+    }
+
+    /// A folder named `name` inside the folder `path`.
+    pub fn folder(path: &str, name: &str) -> InMemoryEntry {
+        InMemoryEntry::new(ResultKind::Folder, path, name)
+    }
+
+    /// A volume such as `C:`.
+    pub fn volume(name: &str) -> InMemoryEntry {
+        InMemoryEntry::new(ResultKind::Volume, "", name)
+    }
+
+    /// The path and name joined by a `\`.
+    pub fn full_path(&self) -> String {
+        if self.path.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}\\{}", self.path, self.name)
+        }
+    }
+
+    /// Everything treats the text after the last `.` of the name as the extension.
+    pub fn extension(&self) -> &str {
+        match self.name.rfind('.') {
+            Some(dot) if self.kind != ResultKind::Volume => &self.name[dot + 1..],
+            _ => "",
+        }
+    }
+}
+
+/// What the simulated Everything service holds, kept across `reset`.
+#[derive(Debug)]
+struct Service {
+    db_loaded: bool,
+    db_busy: bool,
+    admin: bool,
+    appdata: bool,
+    target_machine: TargetMachine,
+    version: (u32, u32, u32, u32),
+    /// Every command received by `send_command`, in order.
+    commands: Vec<(u32, u32)>,
+    /// Run counts by lower cased full path, starting with the ones of the fixture entries.
+    run_counts: HashMap<String, u32>,
+}
+
+impl Default for Service {
+    fn default() -> Service {
+        Service {
+            db_loaded: true,
+            db_busy: false,
+            admin: false,
+            appdata: true,
+            target_machine: TargetMachine::X64,
+            version: (1, 4, 1, 1024),
+            commands: Vec::new(),
+            run_counts: HashMap::new(),
+        }
+    }
+}
+
+impl Service {
+    fn new(entries: &[InMemoryEntry]) -> Service {
+        let run_counts = entries
+            .iter()
+            .filter(|entry| entry.run_count != 0)
+            .map(|entry| (entry.full_path().to_lowercase(), entry.run_count))
+            .collect();
+        Service {
+            run_counts,
+            ..Service::default()
+        }
+    }
+
+    /// Everything keeps run counts by full path, ignoring case.
+    fn run_count(&self, file_name: &str) -> u32 {
+        self.run_counts
+            .get(&file_name.to_lowercase())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn set_run_count(&mut self, file_name: &str, run_count: u32) {
+        self.run_counts.insert(file_name.to_lowercase(), run_count);
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    search: String,
+    options: MatchOptions,
+    sort: EverythingSort,
+    max_results: u32,
+    offset: u32,
+    request_flags: EverythingRequestFlags,
+    service: Service,
+    last_error: EverythingSDKError,
+    /// Request flags in effect when the result list was produced.
+    result_flags: EverythingRequestFlags,
+    /// Indexes into `entries` for the visible result window.
+    results: Vec<usize>,
+    total_results: u32,
+    total_files: u32,
+}
+
+impl State {
+    fn new(service: Service) -> State {
+        State {
+            search: String::new(),
+            options: MatchOptions::default(),
+            sort: EverythingSort::NameAscending,
+            max_results: u32::MAX,
+            offset: 0,
+            request_flags: DEFAULT_REQUEST_FLAGS,
+            service,
+            last_error: EverythingSDKError::Ok,
+            result_flags: EverythingRequestFlags::empty(),
+            results: Vec::new(),
+            total_results: 0,
+            total_files: 0,
+        }
+    }
+
+    /// Drops the results of the previous query after a failed one, like the SDK does.
+    fn clear_results(&mut self) {
+        self.results.clear();
+        self.total_results = 0;
+        self.total_files = 0;
+    }
+
+    /// Looks up a result, failing like the SDK when the index is out of range or `flags` were not requested.
+    fn entry<'a>(
+        &mut self,
+        entries: &'a [InMemoryEntry],
+        index: u32,
+        flags: EverythingRequestFlags,
+    ) -> EverythingResult<&'a InMemoryEntry> {
+        let error = match self.results.get(index as usize) {
+            None => EverythingSDKError::InvalidIndex,
+            Some(_) if !self.result_flags.contains(flags) => EverythingSDKError::InvalidRequest,
+            Some(&entry) => {
+                self.last_error = EverythingSDKError::Ok;
+                return Ok(&entries[entry]);
+            }
+        };
+        self.last_error = error;
+        Err(EverythingError::SDKError(error))
+    }
+}
+
+/// A `SearchBackend` that answers queries from a fixed list of entries.
+/// Useful for testing code built on `Everything` without a running Everything service.
+///
+/// Searches are read with `query::parse` and evaluated like Everything does for the syntax it covers:
+/// operators and `< >` groups, text and wildcards, the modifiers `case:`, `path:`, `ww:`, `regex:`, `file:` and `folder:`,
+/// the functions `ext:` and `size:` and the filter macros such as `audio:`.
+/// Terms are matched case insensitively against the file name, or the full path if the term contains a `\`.
+/// Terms containing `*` or `?` are wildcards that must match the whole name.
+/// Match case, match path, match whole word and regex are honored.
+/// Other functions such as `dm:` fail the query with `EverythingSDKError::InvalidParameter` instead of matching nothing.
+/// Highlighted results mark the text matched by terms that are not wildcards.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::{Everything, InMemoryBackend, InMemoryEntry, EverythingRequestFlags};
+///
+/// let backend = InMemoryBackend::new(vec![
+///     InMemoryEntry::file("C:\\test\\test_dir", "test1.txt"),
+///     InMemoryEntry::file("C:\\test\\test_dir", "test2.jpg"),
+/// ]);
+/// let everything = Everything::with_backend(backend);
+///
+/// everything.set_search("*.txt");
+/// everything.set_request_flags(EverythingRequestFlags::FullPathAndFileName);
+/// everything.query().unwrap();
+///
+/// assert_eq!(everything.get_result_full_path(0).unwrap(), "C:\\test\\test_dir\\test1.txt");
+/// ```
+#[derive(Debug)]
+pub struct InMemoryBackend {
+    entries: Vec<InMemoryEntry>,
+    state: Mutex<State>,
+}
+
+const DEFAULT_REQUEST_FLAGS: EverythingRequestFlags =
+    EverythingRequestFlags::FileName.union(EverythingRequestFlags::Path);
+
+impl InMemoryBackend {
+    pub fn new(entries: Vec<InMemoryEntry>) -> InMemoryBackend {
+        InMemoryBackend {
+            state: Mutex::new(State::new(Service::new(&entries))),
+            entries,
+        }
+    }
+
+    /// Sets the version reported by `version`. Defaults to 1.4.1.1024.
+    pub fn set_version(&self, major: u32, minor: u32, revision: u32, build: u32) {
+        self.state().service.version = (major, minor, revision, build);
+    }
+
+    /// Simulates the database loading. Defaults to loaded.
+    pub fn set_db_loaded(&self, loaded: bool) {
+        self.state().service.db_loaded = loaded;
+    }
+
+    /// Simulates the database being busy, see `Admin::is_db_busy`. Defaults to not busy.
+    pub fn set_db_busy(&self, busy: bool) {
+        self.state().service.db_busy = busy;
+    }
+
+    /// Simulates Everything running as administrator. Defaults to not.
+    pub fn set_admin(&self, admin: bool) {
+        self.state().service.admin = admin;
+    }
+
+    /// Simulates Everything storing its data in `%APPDATA%`. Defaults to doing so.
+    pub fn set_appdata(&self, appdata: bool) {
+        self.state().service.appdata = appdata;
+    }
+
+    /// Sets the architecture reported to `Admin::target_machine`. Defaults to x64.
+    pub fn set_target_machine(&self, target_machine: TargetMachine) {
+        self.state().service.target_machine = target_machine;
+    }
+
+    /// Every command sent with `SearchBackend::send_command` so far, as the command and its parameter.
+    /// The commands that change the database are only recorded.
+    pub fn commands(&self) -> Vec<(u32, u32)> {
+        self.state().service.commands.clone()
+    }
+
+    /// The fixture entries this backend searches.
+    pub fn entries(&self) -> &[InMemoryEntry] {
+        &self.entries
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl SearchBackend for InMemoryBackend {
+    fn get_last_error(&self) -> EverythingResult<()> {
+        match self.state().last_error {
+            EverythingSDKError::Ok => Ok(()),
+            err => Err(EverythingError::SDKError(err)),
+        }
+    }
+
+    fn is_db_loaded(&self) -> bool {
+        self.state().service.db_loaded
+    }
+
+    fn version(&self) -> (u32, u32, u32, u32) {
+        self.state().service.version
+    }
+
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        let mut state = self.state();
+        let (major, minor, revision, build) = state.service.version;
+        state.service.commands.push((command, param));
+        Ok(match command {
+            EVERYTHING_IPC_GET_MAJOR_VERSION => major,
+            EVERYTHING_IPC_GET_MINOR_VERSION => minor,
+            EVERYTHING_IPC_GET_REVISION => revision,
+            EVERYTHING_IPC_GET_BUILD_NUMBER => build,
+            EVERYTHING_IPC_GET_TARGET_MACHINE => state.service.target_machine.into(),
+            EVERYTHING_IPC_IS_NTFS_DRIVE_INDEXED => {
+                let drive = char::from_u32('A' as u32 + param).filter(|_| param < 26);
+                drive.is_some_and(|drive| {
+                    self.entries.iter().any(|entry| {
+                        entry.kind == ResultKind::Volume
+                            && entry.name.eq_ignore_ascii_case(&format!("{drive}:"))
+                    })
+                }) as u32
+            }
+            EVERYTHING_IPC_IS_DB_LOADED => state.service.db_loaded as u32,
+            EVERYTHING_IPC_IS_DB_BUSY => state.service.db_busy as u32,
+            EVERYTHING_IPC_IS_ADMIN => state.service.admin as u32,
+            EVERYTHING_IPC_IS_APPDATA => state.service.appdata as u32,
+            EVERYTHING_IPC_IS_FAST_SORT => {
+                EverythingSort::try_from(param).is_ok_and(|sort| self.is_fast_sort(sort)) as u32
+            }
+            // Matches the fast sorts: sizes and modification dates are indexed.
+            EVERYTHING_IPC_IS_FILE_INFO_INDEXED => matches!(
+                param,
+                EVERYTHING_IPC_FILE_INFO_FILE_SIZE | EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED
+            ) as u32,
+            EVERYTHING_IPC_DELETE_RUN_HISTORY => {
+                state.service.run_counts.clear();
+                1
+            }
+            EVERYTHING_IPC_EXIT
+            | EVERYTHING_IPC_SAVE_RUN_HISTORY
+            | EVERYTHING_IPC_REBUILD_DB
+            | EVERYTHING_IPC_UPDATE_ALL_FOLDER_INDEXES
+            | EVERYTHING_IPC_SAVE_DB
+            | EVERYTHING_IPC_QUEUE_REBUILD_DB => 1,
+            // Everything returns 0 for commands it does not know.
+            _ => 0,
+        })
+    }
+
+    fn get_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        check_file_name(file_name)?;
+        Ok(self.state().service.run_count(file_name))
+    }
+
+    fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()> {
+        check_file_name(file_name)?;
+        self.state().service.set_run_count(file_name, run_count);
+        Ok(())
+    }
+
+    fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        check_file_name(file_name)?;
+        let mut state = self.state();
+        let run_count = state.service.run_count(file_name).saturating_add(1);
+        state.service.set_run_count(file_name, run_count);
+        Ok(run_count)
+    }
+
+    fn set_search(&self, search: &str) {
+        self.state().search = search.to_string();
+    }
+
+    fn get_search(&self) -> EverythingResult<String> {
+        Ok(self.state().search.clone())
+    }
+
+    fn set_sort(&self, sort: EverythingSort) {
+        self.state().sort = sort;
+    }
+
+    fn get_sort(&self) -> Option<EverythingSort> {
+        Some(self.state().sort)
+    }
+
+    fn is_fast_sort(&self, sort: EverythingSort) -> bool {
+        matches!(
+            sort,
+            EverythingSort::NameAscending
+                | EverythingSort::NameDescending
+                | EverythingSort::PathAscending
+                | EverythingSort::PathDescending
+                | EverythingSort::SizeAscending
+                | EverythingSort::SizeDescending
+                | EverythingSort::DateModifiedAscending
+                | EverythingSort::DateModifiedDescending
+        )
+    }
+
+    fn set_match_case(&self, enable: bool) {
+        self.state().options.case = enable;
+    }
+
+    fn get_match_case(&self) -> bool {
+        self.state().options.case
+    }
+
+    fn set_match_path(&self, enable: bool) {
+        self.state().options.path = enable;
+    }
+
+    fn get_match_path(&self) -> bool {
+        self.state().options.path
+    }
+
+    fn set_match_whole_word(&self, enable: bool) {
+        self.state().options.whole_word = enable;
+    }
+
+    fn get_match_whole_word(&self) -> bool {
+        self.state().options.whole_word
+    }
+
+    fn set_regex(&self, enable: bool) {
+        self.state().options.regex = enable;
+    }
+
+    fn get_regex(&self) -> bool {
+        self.state().options.regex
+    }
+
+    fn set_max_results(&self, max_results: u32) {
+        self.state().max_results = max_results;
+    }
+
+    fn get_max_results(&self) -> u32 {
+        self.state().max_results
+    }
+
+    fn set_result_offset(&self, offset: u32) {
+        self.state().offset = offset;
+    }
+
+    fn get_result_offset(&self) -> u32 {
+        self.state().offset
+    }
+
+    fn set_request_flags(&self, request_flags: EverythingRequestFlags) {
+        self.state().request_flags = request_flags;
+    }
+
+    fn get_request_flags(&self) -> EverythingRequestFlags {
+        self.state().request_flags
+    }
+
+    fn get_result_list_request_flags(&self) -> EverythingRequestFlags {
+        self.state().result_flags
+    }
+
+    fn query_with(&self, limits: &QueryLimits) -> EverythingResult<()> {
+        let mut state = self.state();
+        // Searches finish right away, so the limits only matter when they already fired.
+        // A query given up on by its limits leaves the error of the previous call.
+        let search = limits
+            .check()
+            .and_then(|_| check_search(&state.search))
+            .and_then(|_| Search::parse(&state.search, state.options));
+        let search = match search {
+            Ok(search) => search,
+            Err(err) => {
+                state.clear_results();
+                if let EverythingError::SDKError(code) = &err {
+                    state.last_error = *code;
+                }
+                return Err(err);
+            }
+        };
+        if !state.service.db_loaded {
+            state.clear_results();
+            state.last_error = EverythingSDKError::Ipc;
+            return Err(EverythingError::SDKError(EverythingSDKError::Ipc));
+        }
+
+        let mut matches: Vec<usize> = (0..self.entries.len())
+            .filter(|&index| search.matches(&self.entries[index]))
+            .collect();
+
+        let sort = state.sort;
+        matches.sort_by(|&a, &b| {
+            compare_entries(&self.entries[a], &self.entries[b], sort, &state.service)
+        });
+
+        state.total_results = matches.len() as u32;
+        state.total_files = matches
+            .iter()
+            .filter(|&&index| self.entries[index].kind == ResultKind::File)
+            .count() as u32;
+        state.results = matches
+            .into_iter()
+            .skip(state.offset as usize)
+            .take(state.max_results as usize)
+            .collect();
+        state.result_flags = state.request_flags;
+        state.last_error = EverythingSDKError::Ok;
+        Ok(())
+    }
+
+    fn reset(&self) {
+        let mut state = self.state();
+        let service = std::mem::take(&mut state.service);
+        *state = State::new(service);
+    }
+
+    fn get_num_results(&self) -> u32 {
+        self.state().results.len() as u32
+    }
+
+    fn get_total_results(&self) -> u32 {
+        self.state().total_results
+    }
+
+    fn get_num_file_results(&self) -> u32 {
+        let state = self.state();
+        state
+            .results
+            .iter()
+            .filter(|&&index| self.entries[index].kind == ResultKind::File)
+            .count() as u32
+    }
+
+    fn get_num_folder_results(&self) -> u32 {
+        self.get_num_results() - self.get_num_file_results()
+    }
+
+    fn get_total_file_results(&self) -> u32 {
+        self.state().total_files
+    }
+
+    fn get_total_folder_results(&self) -> u32 {
+        let state = self.state();
+        state.total_results - state.total_files
+    }
+
+    fn sort_results_by_path(&self) {
+        let state = &mut *self.state();
+        state.results.sort_by(|&a, &b| {
+            compare_entries(
+                &self.entries[a],
+                &self.entries[b],
+                EverythingSort::PathAscending,
+                &state.service,
+            )
+        });
+    }
+
+    fn is_result_file(&self, index: u32) -> bool {
+        self.kind(index) == Some(ResultKind::File)
+    }
+
+    fn is_result_folder(&self, index: u32) -> bool {
+        // Like the SDK, volumes are reported as folders too.
+        matches!(
+            self.kind(index),
+            Some(ResultKind::Folder | ResultKind::Volume)
+        )
+    }
+
+    fn is_result_volume(&self, index: u32) -> bool {
+        self.kind(index) == Some(ResultKind::Volume)
+    }
+
+    fn get_result_full_path(&self, index: u32) -> EverythingResult<String> {
+        let mut state = self.state();
+        // The full path can also be built from a separately requested name and path.
+        let flags = if state.result_flags.contains(DEFAULT_REQUEST_FLAGS) {
+            DEFAULT_REQUEST_FLAGS
+        } else {
+            EverythingRequestFlags::FullPathAndFileName
+        };
+        state
+            .entry(&self.entries, index, flags)
+            .map(InMemoryEntry::full_path)
+    }
+
+    fn get_result_path(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::Path)
+            .map(|entry| entry.path.clone())
+    }
+
+    fn get_result_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::FileName)
+            .map(|entry| entry.name.clone())
+    }
+
+    fn get_result_extension(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::Extension)
+            .map(|entry| entry.extension().to_string())
+    }
+
+    fn get_result_size(&self, index: u32) -> EverythingResult<u64> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::Size)
+            .map(|entry| entry.size)
+    }
+
+    fn get_result_created_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::DateCreated)
+            .map(|entry| FileTime::from(entry.date_created))
+    }
+
+    fn get_result_modified_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::DateModified)
+            .map(|entry| FileTime::from(entry.date_modified))
+    }
+
+    fn get_result_accessed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::DateAccessed)
+            .map(|entry| FileTime::from(entry.date_accessed))
+    }
+
+    fn get_result_run_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::DateRun)
+            .map(|entry| FileTime::from(entry.date_run))
+    }
+
+    fn get_result_recently_changed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .entry(
+                &self.entries,
+                index,
+                EverythingRequestFlags::DateRecentlyChanged,
+            )
+            .map(|entry| FileTime::from(entry.date_recently_changed))
+    }
+
+    fn get_result_attributes(&self, index: u32) -> EverythingResult<FileAttributes> {
+        self.state()
+            .entry(&self.entries, index, EverythingRequestFlags::Attributes)
+            .map(|entry| FileAttributes::from_bits_retain(entry.attributes))
+    }
+
+    fn get_result_run_count(&self, index: u32) -> EverythingResult<u32> {
+        let mut state = self.state();
+        let entry = state.entry(&self.entries, index, EverythingRequestFlags::RunCount)?;
+        Ok(state.service.run_count(&entry.full_path()))
+    }
+
+    fn get_result_file_list_file_name(&self, index: u32) -> EverythingResult<String> {
+        // Fixture entries never come from a file list.
+        self.state()
+            .entry(
+                &self.entries,
+                index,
+                EverythingRequestFlags::FileListFileName,
+            )
+            .map(|_| String::new())
+    }
+
+    fn get_result_highlighted_file_name(&self, index: u32) -> EverythingResult<String> {
+        let mut state = self.state();
+        let entry = state.entry(
+            &self.entries,
+            index,
+            EverythingRequestFlags::HighlightedFileName,
+        )?;
+        Ok(highlight(&entry.name, &state.search, state.options))
+    }
+
+    fn get_result_highlighted_path(&self, index: u32) -> EverythingResult<String> {
+        let mut state = self.state();
+        let entry = state.entry(
+            &self.entries,
+            index,
+            EverythingRequestFlags::HighlightedPath,
+        )?;
+        Ok(highlight(&entry.path, &state.search, state.options))
+    }
+
+    fn get_result_highlighted_full_path(&self, index: u32) -> EverythingResult<String> {
+        let mut state = self.state();
+        let entry = state.entry(
+            &self.entries,
+            index,
+            EverythingRequestFlags::HighlightedFullPathAndFileName,
+        )?;
+        Ok(highlight(&entry.full_path(), &state.search, state.options))
+    }
+}
+
+impl InMemoryBackend {
+    /// The entries of the last query and the total number of matches, used to answer loopback IPC queries.
+    pub(crate) fn results(&self) -> (u32, Vec<InMemoryEntry>) {
+        let state = self.state();
+        let results = state
+            .results
+            .iter()
+            .map(|&entry| self.entries[entry].clone())
+            .collect();
+        (state.total_results, results)
+    }
+
+    /// The run count of `entry`, including the changes made through the run history.
+    pub(crate) fn run_count(&self, entry: &InMemoryEntry) -> u32 {
+        self.state().service.run_count(&entry.full_path())
+    }
+
+    /// Marks the text in `text` matched by the last search the same way Everything does, see `get_result_highlighted_file_name`.
+    pub(crate) fn highlight(&self, text: &str) -> String {
+        let state = self.state();
+        highlight(text, &state.search, state.options)
+    }
+
+    fn kind(&self, index: u32) -> Option<ResultKind> {
+        let state = self.state();
+        state
+            .results
+            .get(index as usize)
+            .map(|&entry| self.entries[entry].kind)
+    }
+}
+
+fn compare_names(a: &InMemoryEntry, b: &InMemoryEntry) -> Ordering {
+    a.name.to_lowercase().cmp(&b.name.to_lowercase())
+}
+
+/// Orders two entries by the sort key, falling back to the name like Everything does.
+fn compare_entries(
+    a: &InMemoryEntry,
+    b: &InMemoryEntry,
+    sort: EverythingSort,
+    service: &Service,
+) -> Ordering {
+    use EverythingSort::*;
+
+    let ordering = match sort {
+        NameAscending | NameDescending => compare_names(a, b),
+        PathAscending | PathDescending => a
+            .path
+            .to_lowercase()
+            .cmp(&b.path.to_lowercase())
+            .then_with(|| compare_names(a, b)),
+        SizeAscending | SizeDescending => a.size.cmp(&b.size),
+        ExtensionAscending | ExtensionDescending | TypeNameAscending | TypeNameDescending => a
+            .extension()
+            .to_lowercase()
+            .cmp(&b.extension().to_lowercase()),
+        DateCreatedAscending | DateCreatedDescending => a.date_created.cmp(&b.date_created),
+        DateModifiedAscending | DateModifiedDescending => a.date_modified.cmp(&b.date_modified),
+        AttributesAscending | AttributesDescending => a.attributes.cmp(&b.attributes),
+        FileListFilenameAscending | FileListFilenameDescending => Ordering::Equal,
+        RunCountAscending | RunCountDescending => service
+            .run_count(&a.full_path())
+            .cmp(&service.run_count(&b.full_path())),
+        DateRecentlyChangedAscending | DateRecentlyChangedDescending => {
+            a.date_recently_changed.cmp(&b.date_recently_changed)
+        }
+        DateAccessedAscending | DateAccessedDescending => a.date_accessed.cmp(&b.date_accessed),
+        DateRunAscending | DateRunDescending => a.date_run.cmp(&b.date_run),
+    };
+
+    let descending = u32::from(sort) % 2 == 0;
+    let ordering = if descending {
+        ordering.reverse()
+    } else {
+        ordering
+    };
+
+    ordering.then_with(|| compare_names(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::Comparison;
+    use crate::test_util::{names, test_backend};
+    use crate::{Everything, EverythingFilter, Highlighted, Query};
+
+    #[test]
+    fn searches() {
+        let everything = Everything::with_backend(test_backend());
+
+        everything.set_search("test");
+        assert_eq!(everything.get_search().unwrap(), "test");
+
+        everything.set_max_results(2);
+        assert_eq!(everything.get_max_results(), 2);
+
+        everything.set_result_offset(1);
+        assert_eq!(everything.get_result_offset(), 1);
+
+        let flags = EverythingRequestFlags::FileName
+            | EverythingRequestFlags::FullPathAndFileName
+            | EverythingRequestFlags::DateCreated
+            | EverythingRequestFlags::Size;
+        everything.set_request_flags(flags);
+        assert_eq!(everything.get_request_flags(), flags);
+
+        everything.set_sort(EverythingSort::DateCreatedDescending);
+        assert_eq!(
+            everything.get_sort(),
+            Some(EverythingSort::DateCreatedDescending)
+        );
+
+        everything.query().unwrap();
+
+        assert_eq!(everything.get_total_results(), 5);
+        assert_eq!(everything.get_result_count(), 2);
+        assert_eq!(names(&everything), ["test3.md", "test2.jpg"]);
+        assert_eq!(
+            everything.get_result_created_date(0).unwrap(),
+            FileTime::from_raw(200)
+        );
+        assert_eq!(everything.get_result_size(1).unwrap(), 30);
+    }
+
+    #[test]
+    fn parses_full_path() {
+        let everything = Everything::with_backend(test_backend());
+
+        everything.set_search("C:\\test\\test_dir\\");
+        everything.set_request_flags(EverythingRequestFlags::FullPathAndFileName);
+        everything.query().unwrap();
+
+        let paths: Vec<String> = everything.full_path_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            paths,
+            [
+                "C:\\test\\test_dir\\test1.txt",
+                "C:\\test\\test_dir\\test2.jpg"
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluates_search_syntax() {
+        let everything = Everything::with_backend(test_backend());
+
+        everything.set_search("TEST !dir");
+        everything.query().unwrap();
+        assert_eq!(
+            names(&everything),
+            ["test", "test1.txt", "test2.jpg", "test3.md"]
+        );
+
+        everything.set_search("*.jpg | *.md");
+        everything.query().unwrap();
+        assert_eq!(names(&everything), ["test2.jpg", "test3.md"]);
+
+        everything.set_search("test?.*");
+        everything.query().unwrap();
+        assert_eq!(names(&everything), ["test1.txt", "test2.jpg", "test3.md"]);
+
+        everything.set_search("\"c:\" | \"test_dir\"");
+        everything.query().unwrap();
+        assert_eq!(names(&everything), ["C:", "test_dir"]);
+    }
+
+    #[test]
+    fn evaluates_functions() {
+        let everything = Everything::with_backend(test_backend());
+        let search = |search: &str| {
+            everything.set_search(search);
+            everything.query().unwrap();
+            names(&everything)
+        };
+
+        let text_files = Query::ext(["txt", "md"]).to_string();
+        assert_eq!(search(&text_files), ["test1.txt", "test3.md"]);
+        let pictures = EverythingFilter::Picture.query().to_string();
+        assert_eq!(search(&pictures), ["test2.jpg"]);
+        assert_eq!(search("pic:"), ["test2.jpg"]);
+
+        let larger = Query::size(Comparison::GreaterThan(10)).to_string();
+        assert_eq!(search(&larger), ["test2.jpg", "test3.md"]);
+        assert_eq!(search("size:10..20"), ["test1.txt", "test3.md"]);
+        assert_eq!(
+            search("size:<1kb file:"),
+            ["test1.txt", "test2.jpg", "test3.md"]
+        );
+
+        let in_dir = Query::path("test_dir").to_string();
+        assert_eq!(search(&in_dir), ["test1.txt", "test2.jpg", "test_dir"]);
+        assert_eq!(search("folder: test"), ["test", "test_dir"]);
+        let regex = Query::regex("^test\\d\\.(txt|md)$").to_string();
+        assert_eq!(search(&regex), ["test1.txt", "test3.md"]);
+
+        everything.set_regex(true);
+        assert_eq!(search("^TEST\\d"), ["test1.txt", "test2.jpg", "test3.md"]);
+        everything.set_match_case(true);
+        assert!(search("^TEST").is_empty());
+    }
+
+    #[test]
+    fn rejects_unsupported_syntax() {
+        let everything = Everything::with_backend(test_backend());
+        for search in ["dm:today", "a <b", "size:big"] {
+            everything.set_search(search);
+            assert!(matches!(
+                everything.query(),
+                Err(EverythingError::SDKError(
+                    EverythingSDKError::InvalidParameter
+                ))
+            ));
+        }
+
+        everything.set_regex(true);
+        everything.set_search("(");
+        assert!(everything.query().is_err());
+        assert_eq!(everything.get_num_results(), 0);
+    }
+
+    #[test]
+    fn reports_result_kind() {
+        let everything = Everything::with_backend(test_backend());
+        everything.query().unwrap();
+
+        assert_eq!(names(&everything)[0], "C:");
+        assert!(everything.is_result_volume(0));
+        assert!(everything.is_result_folder(0));
+        assert!(everything.is_result_folder(1));
+        assert!(!everything.is_result_volume(1));
+        assert!(everything.is_result_file(2));
+        assert!(!everything.is_result_file(99));
+    }
+
+    #[test]
+    fn sorts_descending() {
+        let everything = Everything::with_backend(test_backend());
+        everything.set_search("*.*");
+        everything.set_sort(EverythingSort::SizeDescending);
+        everything.query().unwrap();
+        assert_eq!(names(&everything), ["test2.jpg", "test3.md", "test1.txt"]);
+
+        everything.set_sort(EverythingSort::NameDescending);
+        everything.query().unwrap();
+        assert_eq!(names(&everything), ["test3.md", "test2.jpg", "test1.txt"]);
+    }
+
+    #[test]
+    fn requires_request_flags() {
+        let everything = Everything::with_backend(test_backend());
+        everything.set_request_flags(EverythingRequestFlags::FileName);
+        everything.query().unwrap();
+
+        assert!(matches!(
+            everything.get_result_size(0),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidRequest
+            ))
+        ));
+        assert!(matches!(
+            everything.get_last_error(),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidRequest
+            ))
+        ));
+        assert!(matches!(
+            everything.get_result_file_name(99),
+            Err(EverythingError::SDKError(EverythingSDKError::InvalidIndex))
+        ));
+
+        // Flags set after the query do not apply to the current results.
+        everything.set_request_flags(EverythingRequestFlags::Size);
+        assert!(everything.get_result_size(0).is_err());
+        assert!(everything.get_result_file_name(0).is_ok());
+        assert!(everything.get_last_error().is_ok());
+    }
+
+    #[test]
+    fn waits_for_db() {
+        let backend = test_backend();
+        backend.query().unwrap();
+        assert!(backend.get_num_results() > 0);
+        backend.set_db_loaded(false);
+        let everything = Everything::with_backend(backend);
+
+        assert!(matches!(
+            everything.wait_db_loaded(std::time::Duration::from_millis(0)),
+            Err(EverythingError::DatabaseTimeout)
+        ));
+        assert!(everything.query().is_err());
+        // The results of the earlier query are gone with the failed one.
+        assert_eq!(everything.get_num_results(), 0);
+        assert_eq!(everything.get_total_results(), 0);
+        assert_eq!(everything.get_num_file_results(), 0);
+        assert!(everything.get_result_file_name(0).is_err());
+    }
+
+    #[test]
+    fn keeps_last_error_when_cancelled() {
+        let backend = test_backend();
+        backend.query().unwrap();
+        let cancel = crate::CancellationToken::new();
+        cancel.cancel();
+
+        assert!(matches!(
+            backend.query_with(&QueryLimits::cancel(cancel)),
+            Err(EverythingError::Cancelled)
+        ));
+        assert!(backend.get_last_error().is_ok());
+        assert_eq!(backend.get_num_results(), 0);
+    }
+
+    #[test]
+    fn rejects_null_characters_in_searches() {
+        let everything = Everything::with_backend(test_backend());
+        everything.query().unwrap();
+        everything.set_search("test1\0.txt");
+        assert!(matches!(
+            everything.query(),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidParameter
+            ))
+        ));
+        assert!(everything.get_last_error().is_err());
+        assert_eq!(everything.get_num_results(), 0);
+        assert_eq!(everything.get_search().unwrap(), "test1\0.txt");
+    }
+
+    #[test]
+    fn resets_state() {
+        let everything = Everything::with_backend(test_backend());
+        everything.set_search("test1");
+        everything.set_max_results(1);
+        everything.query().unwrap();
+        everything.reset();
+
+        assert_eq!(everything.get_search().unwrap(), "");
+        assert_eq!(everything.get_max_results(), u32::MAX);
+        assert_eq!(everything.get_result_count(), 0);
+        assert_eq!(everything.version().to_string(), "1.4.1.1024");
+    }
+
+    #[test]
+    fn reads_remaining_fields() {
+        let mut entry = InMemoryEntry::file("C:\\test", "run*me.exe");
+        entry.date_accessed = 1;
+        entry.date_run = 2;
+        entry.date_recently_changed = 3;
+        entry.attributes = 0x20;
+        entry.run_count = 4;
+        let everything = Everything::with_backend(InMemoryBackend::new(vec![entry]));
+
+        everything.set_search("me");
+        everything.set_request_flags(EverythingRequestFlags::all());
+        everything.query().unwrap();
+
+        assert_eq!(everything.get_result_path(0).unwrap(), "C:\\test");
+        assert_eq!(
+            everything.get_result_accessed_date(0).unwrap(),
+            FileTime::from_raw(1)
+        );
+        assert_eq!(
+            everything.get_result_run_date(0).unwrap(),
+            FileTime::from_raw(2)
+        );
+        assert_eq!(
+            everything.get_result_recently_changed_date(0).unwrap(),
+            FileTime::from_raw(3)
+        );
+        assert_eq!(
+            everything.get_result_attributes(0).unwrap(),
+            FileAttributes::Archive
+        );
+        assert_eq!(everything.get_result_run_count(0).unwrap(), 4);
+        assert_eq!(everything.get_result_file_list_file_name(0).unwrap(), "");
+        assert_eq!(
+            everything.get_result_highlighted_file_name(0).unwrap(),
+            Highlighted::parse("run***me*.exe")
+        );
+        assert_eq!(everything.get_result_highlighted_path(0).unwrap().spans, []);
+        assert_eq!(
+            everything
+                .get_result_highlighted_full_path(0)
+                .unwrap()
+                .to_markup(),
+            "C:\\test\\run***me*.exe"
+        );
+
+        everything.set_request_flags(EverythingRequestFlags::FileName);
+        everything.query().unwrap();
+        assert!(everything.get_result_attributes(0).is_err());
+        assert!(everything.get_result_highlighted_file_name(0).is_err());
+    }
+
+    #[test]
+    fn counts_files_and_folders() {
+        let everything = Everything::with_backend(test_backend());
+        everything.set_max_results(3);
+        everything.query().unwrap();
+
+        assert_eq!(everything.get_num_file_results(), 1);
+        assert_eq!(everything.get_num_folder_results(), 2);
+        assert_eq!(everything.get_total_file_results(), 3);
+        assert_eq!(everything.get_total_folder_results(), 3);
+
+        everything.set_result_offset(2);
+        everything.query().unwrap();
+        assert_eq!(everything.get_num_file_results(), 3);
+        assert_eq!(everything.get_num_folder_results(), 0);
+    }
+
+    #[test]
+    fn honors_match_options() {
+        let everything = Everything::with_backend(test_backend());
+
+        everything.set_match_case(true);
+        assert!(everything.get_match_case());
+        everything.set_search("TEST");
+        everything.query().unwrap();
+        assert_eq!(everything.get_result_count(), 0);
+
+        everything.set_match_case(false);
+        everything.set_match_whole_word(true);
+        assert!(everything.get_match_whole_word());
+        everything.set_search("test");
+        everything.query().unwrap();
+        assert_eq!(names(&everything), ["test", "test_dir"]);
+
+        everything.set_match_whole_word(false);
+        everything.set_match_path(true);
+        assert!(everything.get_match_path());
+        everything.set_search("test_dir");
+        everything.query().unwrap();
+        assert_eq!(names(&everything), ["test1.txt", "test2.jpg", "test_dir"]);
+
+        everything.set_regex(true);
+        assert!(everything.get_regex());
+
+        everything.reset();
+        assert!(!everything.get_match_case());
+        assert!(!everything.get_match_path());
+        assert!(!everything.get_regex());
+    }
+
+    #[test]
+    fn sorts_results_by_path() {
+        let everything = Everything::with_backend(test_backend());
+        everything.set_search("test");
+        everything.set_sort(EverythingSort::SizeDescending);
+        everything.query().unwrap();
+        assert_eq!(names(&everything)[0], "test2.jpg");
+
+        everything.sort_results_by_path();
+        assert_eq!(
+            names(&everything),
+            ["test", "test3.md", "test_dir", "test1.txt", "test2.jpg"]
+        );
+    }
+}
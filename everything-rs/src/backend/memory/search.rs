@@ -0,0 +1,373 @@
+//! Evaluates searches for `InMemoryBackend`, reading them with `query::parse`.
+
+use super::InMemoryEntry;
+use crate::query::{parse, Comparison, Expr, ExprKind, Value};
+use crate::{EverythingError, EverythingFilter, EverythingResult, EverythingSDKError, ResultKind};
+use regex_lite::{Regex, RegexBuilder};
+
+/// The search options that change how terms are matched.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct MatchOptions {
+    pub(super) case: bool,
+    pub(super) path: bool,
+    pub(super) whole_word: bool,
+    /// Reads the whole search as one regular expression instead of search syntax.
+    pub(super) regex: bool,
+}
+
+/// A parsed search with the options in effect for each of its terms.
+pub(super) enum Search {
+    All(Vec<Search>),
+    Any(Vec<Search>),
+    Not(Box<Search>),
+    /// Text matched against the name, or against the full path with match path or if the text contains a `\`.
+    /// Text containing `*` or `?` is a wildcard that must match the whole name. Lower cased unless matching case.
+    Text {
+        text: String,
+        options: MatchOptions,
+    },
+    /// Matched against the name, or against the full path with match path.
+    Regex {
+        regex: Regex,
+        path: bool,
+    },
+    /// `ext:` or a filter macro such as `audio:`, lower cased.
+    Extension(Vec<String>),
+    /// `size:` in bytes.
+    Size(Comparison<u64>),
+    /// `file:`
+    Files,
+    /// `folder:`, which includes volumes.
+    Folders,
+}
+
+fn invalid() -> EverythingError {
+    EverythingError::SDKError(EverythingSDKError::InvalidParameter)
+}
+
+impl Search {
+    /// Fails with `EverythingSDKError::InvalidParameter` if the search is malformed
+    /// or uses syntax this backend does not evaluate, such as `dm:`.
+    pub(super) fn parse(search: &str, options: MatchOptions) -> EverythingResult<Search> {
+        if options.regex {
+            return Search::regex(search, options);
+        }
+        let expr = parse(search).map_err(|_| invalid())?;
+        Search::compile(&expr, options)
+    }
+
+    fn compile(expr: &Expr, options: MatchOptions) -> EverythingResult<Search> {
+        let compile_all = |exprs: &[Expr]| {
+            exprs
+                .iter()
+                .map(|expr| Search::compile(expr, options))
+                .collect::<EverythingResult<Vec<_>>>()
+        };
+        match &expr.kind {
+            ExprKind::And(exprs) => Ok(Search::All(compile_all(exprs)?)),
+            ExprKind::Or(exprs) => Ok(Search::Any(compile_all(exprs)?)),
+            ExprKind::Not(expr) => Ok(Search::Not(Box::new(Search::compile(expr, options)?))),
+            ExprKind::Group(expr) => Search::compile(expr, options),
+            ExprKind::Modifier { name, expr } => Search::modifier(name, expr, options),
+            ExprKind::Macro(name) => Search::macro_search(name, options),
+            ExprKind::Function { name, value } => Search::function(name, value),
+            ExprKind::Text(text) => Search::text(text, options),
+        }
+    }
+
+    fn text(text: &str, options: MatchOptions) -> EverythingResult<Search> {
+        if options.regex {
+            return Search::regex(text, options);
+        }
+        if text.is_empty() {
+            return Ok(Search::All(Vec::new()));
+        }
+        let text = if options.case {
+            text.to_string()
+        } else {
+            text.to_lowercase()
+        };
+        Ok(Search::Text { text, options })
+    }
+
+    fn regex(pattern: &str, options: MatchOptions) -> EverythingResult<Search> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(!options.case)
+            .build()
+            .map_err(|_| invalid())?;
+        Ok(Search::Regex {
+            regex,
+            path: options.path,
+        })
+    }
+
+    fn modifier(name: &str, expr: &Expr, options: MatchOptions) -> EverythingResult<Search> {
+        let mut options = options;
+        match name.to_ascii_lowercase().as_str() {
+            "case" => options.case = true,
+            "nocase" => options.case = false,
+            "path" => options.path = true,
+            "nopath" => options.path = false,
+            "wholeword" | "ww" => options.whole_word = true,
+            "nowholeword" | "noww" => options.whole_word = false,
+            "regex" => options.regex = true,
+            "noregex" => options.regex = false,
+            "file" | "files" => {
+                return Ok(Search::All(vec![
+                    Search::Files,
+                    Search::compile(expr, options)?,
+                ]))
+            }
+            "folder" | "folders" => {
+                return Ok(Search::All(vec![
+                    Search::Folders,
+                    Search::compile(expr, options)?,
+                ]))
+            }
+            _ => return Err(invalid()),
+        }
+        Search::compile(expr, options)
+    }
+
+    fn macro_search(name: &str, options: MatchOptions) -> EverythingResult<Search> {
+        let name = name.to_ascii_lowercase();
+        let character = match name.as_str() {
+            "file" | "files" => return Ok(Search::Files),
+            "folder" | "folders" => return Ok(Search::Folders),
+            "quot" => "\"",
+            "apos" => "'",
+            "amp" => "&",
+            "lt" => "<",
+            "gt" => ">",
+            name => {
+                let filter = EverythingFilter::ALL
+                    .into_iter()
+                    .find(|filter| filter.macro_name() == Some(name))
+                    .ok_or_else(invalid)?;
+                return Ok(Search::extensions(filter.extensions()));
+            }
+        };
+        Search::text(character, options)
+    }
+
+    fn function(name: &str, value: &Value) -> EverythingResult<Search> {
+        match (name.to_ascii_lowercase().as_str(), value) {
+            ("ext", Value::Text(extensions)) => Ok(Search::extensions(
+                &extensions.split(';').collect::<Vec<_>>(),
+            )),
+            ("size", value) => Ok(Search::Size(parse_size_value(value).ok_or_else(invalid)?)),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn extensions(extensions: &[&str]) -> Search {
+        Search::Extension(
+            extensions
+                .iter()
+                .map(|ext| ext.trim_start_matches('.').to_lowercase())
+                .collect(),
+        )
+    }
+
+    pub(super) fn matches(&self, entry: &InMemoryEntry) -> bool {
+        match self {
+            Search::All(searches) => searches.iter().all(|search| search.matches(entry)),
+            Search::Any(searches) => searches.iter().any(|search| search.matches(entry)),
+            Search::Not(search) => !search.matches(entry),
+            Search::Text { text, options } => text_matches(text, *options, entry),
+            Search::Regex { regex, path } => {
+                if *path {
+                    regex.is_match(&entry.full_path())
+                } else {
+                    regex.is_match(&entry.name)
+                }
+            }
+            Search::Extension(extensions) => extensions.contains(&entry.extension().to_lowercase()),
+            Search::Size(size) => match *size {
+                Comparison::Equal(value) => entry.size == value,
+                Comparison::GreaterThan(value) => entry.size > value,
+                Comparison::AtLeast(value) => entry.size >= value,
+                Comparison::LessThan(value) => entry.size < value,
+                Comparison::AtMost(value) => entry.size <= value,
+                Comparison::Between(start, end) => (start..=end).contains(&entry.size),
+            },
+            Search::Files => entry.kind == ResultKind::File,
+            Search::Folders => entry.kind != ResultKind::File,
+        }
+    }
+
+    /// The text terms Everything highlights in results, negated and wildcard terms are not.
+    fn highlighted_terms<'a>(&'a self, terms: &mut Vec<(&'a str, MatchOptions)>) {
+        match self {
+            Search::All(searches) | Search::Any(searches) => {
+                for search in searches {
+                    search.highlighted_terms(terms);
+                }
+            }
+            Search::Text { text, options } if !is_wildcard(text) => terms.push((text, *options)),
+            _ => {}
+        }
+    }
+}
+
+/// Reads a `size:` value such as `10mb`, `>1gb` or `1kb..2kb`. An open end of a range is unbounded.
+fn parse_size_value(value: &Value) -> Option<Comparison<u64>> {
+    let bound = |text: &str, open: u64| {
+        if text.is_empty() {
+            Some(open)
+        } else {
+            parse_size(text)
+        }
+    };
+    Some(match value {
+        Value::Text(text) => Comparison::Equal(parse_size(text)?),
+        Value::Comparison(Comparison::Equal(text)) => Comparison::Equal(parse_size(text)?),
+        Value::Comparison(Comparison::GreaterThan(text)) => {
+            Comparison::GreaterThan(parse_size(text)?)
+        }
+        Value::Comparison(Comparison::AtLeast(text)) => Comparison::AtLeast(parse_size(text)?),
+        Value::Comparison(Comparison::LessThan(text)) => Comparison::LessThan(parse_size(text)?),
+        Value::Comparison(Comparison::AtMost(text)) => Comparison::AtMost(parse_size(text)?),
+        Value::Comparison(Comparison::Between(start, end)) => {
+            Comparison::Between(bound(start, 0)?, bound(end, u64::MAX)?)
+        }
+    })
+}
+
+/// Reads a size in bytes with an optional `kb`, `mb`, `gb` or `tb` unit of 1024 times the one before.
+fn parse_size(text: &str) -> Option<u64> {
+    const UNITS: [(&str, u64); 4] = [
+        ("kb", 1 << 10),
+        ("mb", 1 << 20),
+        ("gb", 1 << 30),
+        ("tb", 1 << 40),
+    ];
+    let text = text.to_ascii_lowercase();
+    let (number, unit) = UNITS
+        .iter()
+        .find_map(|(suffix, unit)| Some((text.strip_suffix(suffix)?, *unit)))
+        .unwrap_or((&text, 1));
+    number.parse::<u64>().ok()?.checked_mul(unit)
+}
+
+fn is_wildcard(text: &str) -> bool {
+    text.contains(['*', '?'])
+}
+
+fn text_matches(text: &str, options: MatchOptions, entry: &InMemoryEntry) -> bool {
+    let haystack = if options.path || text.contains('\\') {
+        entry.full_path()
+    } else {
+        entry.name.clone()
+    };
+    let haystack = if options.case {
+        haystack
+    } else {
+        haystack.to_lowercase()
+    };
+
+    if is_wildcard(text) {
+        wildcard_match(text, &haystack)
+    } else {
+        let haystack: Vec<char> = haystack.chars().collect();
+        !find_term(&haystack, text, options).is_empty()
+    }
+}
+
+/// Returns the start of every match of `term` in `haystack`.
+/// Whole word matches must not have a letter or digit on either side.
+fn find_term(haystack: &[char], term: &str, options: MatchOptions) -> Vec<usize> {
+    let term: Vec<char> = term.chars().collect();
+    if term.is_empty() || term.len() > haystack.len() {
+        return Vec::new();
+    }
+
+    let same = |a: char, b: char| {
+        if options.case {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+    let is_word = |index: Option<usize>| {
+        index
+            .and_then(|index| haystack.get(index))
+            .is_some_and(|c| c.is_alphanumeric())
+    };
+
+    (0..=haystack.len() - term.len())
+        .filter(|&start| {
+            haystack[start..start + term.len()]
+                .iter()
+                .zip(&term)
+                .all(|(&a, &b)| same(a, b))
+        })
+        .filter(|&start| {
+            !options.whole_word
+                || !(is_word(start.checked_sub(1)) || is_word(Some(start + term.len())))
+        })
+        .collect()
+}
+
+/// Surrounds the text matched by the search with `*` and doubles literal `*` like Everything's highlighted results.
+/// Negated and wildcard terms are not highlighted, nor is anything if the search cannot be evaluated.
+pub(super) fn highlight(text: &str, search: &str, options: MatchOptions) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut marked = vec![false; chars.len()];
+
+    let search = Search::parse(search, options).ok();
+    let mut terms = Vec::new();
+    if let Some(search) = &search {
+        search.highlighted_terms(&mut terms);
+    }
+    for (term, options) in terms {
+        let length = term.chars().count();
+        for start in find_term(&chars, term, options) {
+            marked[start..start + length].fill(true);
+        }
+    }
+
+    let mut highlighted = String::with_capacity(text.len());
+    let mut in_mark = false;
+    for (&c, &mark) in chars.iter().zip(&marked) {
+        if mark != in_mark {
+            highlighted.push('*');
+            in_mark = mark;
+        }
+        if c == '*' {
+            highlighted.push('*');
+        }
+        highlighted.push(c);
+    }
+    if in_mark {
+        highlighted.push('*');
+    }
+
+    highlighted
+}
+
+/// Matches `*` against any run of characters and `?` against a single character.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,551 @@
+use super::SearchBackend;
+use crate::sync::lock;
+use crate::{
+    EverythingError, EverythingRequestFlags, EverythingResult, EverythingSDKError, EverythingSort,
+    FileAttributes, FileTime, QueryLimits,
+};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Whether a `ReconnectingBackend` can reach Everything, see `ReconnectingBackend::subscribe`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    /// Everything answered and its database is loaded.
+    Connected,
+    /// Everything stopped answering, for example because it was restarted.
+    Disconnected,
+    /// Everything answers again but its database is still loading.
+    LoadingDatabase,
+}
+
+/// How long a `ReconnectingBackend` waits between attempts to reach Everything again.
+/// The first attempt waits `initial`, each following one `multiplier` times longer, up to `max`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub multiplier: u32,
+    /// Gives up with `EverythingSDKError::Ipc` after this many attempts, `None` to keep trying.
+    pub max_attempts: Option<u32>,
+}
+
+impl Backoff {
+    /// How long to wait before the attempt numbered `attempt`, counting from 0.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.checked_pow(attempt).unwrap_or(u32::MAX);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// Starts at 250 ms and doubles up to 10 seconds, without giving up.
+impl Default for Backoff {
+    fn default() -> Backoff {
+        Backoff {
+            initial: Duration::from_millis(250),
+            max: Duration::from_secs(10),
+            multiplier: 2,
+            max_attempts: None,
+        }
+    }
+}
+
+/// A `SearchBackend` that survives Everything restarting.
+///
+/// When a query fails with `EverythingSDKError::Ipc` it waits for Everything to answer again
+/// and for its database to load, following its `Backoff`, then runs the query once more.
+/// The search parameters are kept by the wrapped backend so the retried query is the same one.
+/// Waiting counts against the query's `QueryLimits`. Every other call is forwarded as is.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::ipc::{IpcClient, LoopbackTransport};
+/// use everything_rs::{Backoff, ConnectionState, Everything, InMemoryBackend, ReconnectingBackend};
+///
+/// let backend = ReconnectingBackend::new(
+///     IpcClient::new(LoopbackTransport::new(InMemoryBackend::new(Vec::new()))),
+///     Backoff::default(),
+/// );
+/// let states = backend.subscribe();
+/// backend.inner().transport().set_running(false);
+/// assert_eq!(backend.check_health(), ConnectionState::Disconnected);
+/// assert_eq!(states.try_recv(), Ok(ConnectionState::Disconnected));
+///
+/// backend.inner().transport().set_running(true);
+/// let everything = Everything::with_backend(backend);
+/// assert!(everything.query().is_ok());
+/// ```
+#[derive(Debug)]
+pub struct ReconnectingBackend<B: SearchBackend> {
+    inner: B,
+    backoff: Backoff,
+    state: Mutex<ConnectionState>,
+    subscribers: Mutex<Vec<Sender<ConnectionState>>>,
+}
+
+impl<B: SearchBackend> ReconnectingBackend<B> {
+    /// The backend is assumed to be connected until a query or `check_health` finds otherwise.
+    pub fn new(inner: B, backoff: Backoff) -> ReconnectingBackend<B> {
+        ReconnectingBackend {
+            inner,
+            backoff,
+            state: Mutex::new(ConnectionState::Connected),
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        *lock(&self.state)
+    }
+
+    /// Receives every change of `state` from now on. Dropping the receiver unsubscribes.
+    pub fn subscribe(&self) -> Receiver<ConnectionState> {
+        let (sender, receiver) = channel();
+        lock(&self.subscribers).push(sender);
+        receiver
+    }
+
+    /// Asks Everything whether it is running and its database is loaded, without waiting.
+    pub fn check_health(&self) -> ConnectionState {
+        // Everything never reports a major version of 0, the SDK returns 0 when the call fails.
+        let state = if self.inner.version().0 == 0 {
+            ConnectionState::Disconnected
+        } else if !self.inner.is_db_loaded() {
+            ConnectionState::LoadingDatabase
+        } else {
+            ConnectionState::Connected
+        };
+        self.set_state(state);
+        state
+    }
+
+    /// Calls `check_health` following the backoff until Everything is connected again.
+    fn reconnect(&self, limits: &QueryLimits) -> EverythingResult<()> {
+        self.set_state(ConnectionState::Disconnected);
+        for attempt in 0.. {
+            if self.backoff.max_attempts.is_some_and(|max| attempt >= max) {
+                break;
+            }
+            sleep(self.backoff.delay(attempt), limits)?;
+            if self.check_health() == ConnectionState::Connected {
+                return Ok(());
+            }
+        }
+        Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+    }
+
+    fn set_state(&self, state: ConnectionState) {
+        let previous = std::mem::replace(&mut *lock(&self.state), state);
+        if previous != state {
+            lock(&self.subscribers).retain(|subscriber| subscriber.send(state).is_ok());
+        }
+    }
+}
+
+/// Sleeps for `delay`, failing early once `limits` say so.
+/// A delay too long to add to the current time sleeps until `limits` fail.
+fn sleep(delay: Duration, limits: &QueryLimits) -> EverythingResult<()> {
+    let until = Instant::now().checked_add(delay);
+    loop {
+        limits.check()?;
+        let remaining = until.map_or(Duration::MAX, |until| {
+            until.saturating_duration_since(Instant::now())
+        });
+        if remaining.is_zero() {
+            return Ok(());
+        }
+        std::thread::sleep(
+            limits
+                .poll_interval()
+                .map_or(remaining, |interval| interval.min(remaining)),
+        );
+    }
+}
+
+impl<B: SearchBackend> SearchBackend for ReconnectingBackend<B> {
+    fn get_last_error(&self) -> EverythingResult<()> {
+        self.inner.get_last_error()
+    }
+
+    fn is_db_loaded(&self) -> bool {
+        self.inner.is_db_loaded()
+    }
+
+    fn version(&self) -> (u32, u32, u32, u32) {
+        self.inner.version()
+    }
+
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        self.inner.send_command(command, param)
+    }
+
+    fn get_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.inner.get_run_count(file_name)
+    }
+
+    fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()> {
+        self.inner.set_run_count(file_name, run_count)
+    }
+
+    fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.inner.inc_run_count(file_name)
+    }
+
+    fn set_search(&self, search: &str) {
+        self.inner.set_search(search)
+    }
+
+    fn get_search(&self) -> EverythingResult<String> {
+        self.inner.get_search()
+    }
+
+    fn set_sort(&self, sort: EverythingSort) {
+        self.inner.set_sort(sort)
+    }
+
+    fn get_sort(&self) -> Option<EverythingSort> {
+        self.inner.get_sort()
+    }
+
+    fn is_fast_sort(&self, sort: EverythingSort) -> bool {
+        self.inner.is_fast_sort(sort)
+    }
+
+    fn set_match_case(&self, enable: bool) {
+        self.inner.set_match_case(enable)
+    }
+
+    fn get_match_case(&self) -> bool {
+        self.inner.get_match_case()
+    }
+
+    fn set_match_path(&self, enable: bool) {
+        self.inner.set_match_path(enable)
+    }
+
+    fn get_match_path(&self) -> bool {
+        self.inner.get_match_path()
+    }
+
+    fn set_match_whole_word(&self, enable: bool) {
+        self.inner.set_match_whole_word(enable)
+    }
+
+    fn get_match_whole_word(&self) -> bool {
+        self.inner.get_match_whole_word()
+    }
+
+    fn set_regex(&self, enable: bool) {
+        self.inner.set_regex(enable)
+    }
+
+    fn get_regex(&self) -> bool {
+        self.inner.get_regex()
+    }
+
+    fn set_max_results(&self, max_results: u32) {
+        self.inner.set_max_results(max_results)
+    }
+
+    fn get_max_results(&self) -> u32 {
+        self.inner.get_max_results()
+    }
+
+    fn set_result_offset(&self, offset: u32) {
+        self.inner.set_result_offset(offset)
+    }
+
+    fn get_result_offset(&self) -> u32 {
+        self.inner.get_result_offset()
+    }
+
+    fn set_request_flags(&self, request_flags: EverythingRequestFlags) {
+        self.inner.set_request_flags(request_flags)
+    }
+
+    fn get_request_flags(&self) -> EverythingRequestFlags {
+        self.inner.get_request_flags()
+    }
+
+    fn get_result_list_request_flags(&self) -> EverythingRequestFlags {
+        self.inner.get_result_list_request_flags()
+    }
+
+    fn query_with(&self, limits: &QueryLimits) -> EverythingResult<()> {
+        let result = match self.inner.query_with(limits) {
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc)) => {
+                self.reconnect(limits)?;
+                self.inner.query_with(limits)
+            }
+            result => result,
+        };
+        match result {
+            Ok(()) => self.set_state(ConnectionState::Connected),
+            // Everything went away again after answering the health check.
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc)) => {
+                self.set_state(ConnectionState::Disconnected)
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    fn reset(&self) {
+        self.inner.reset()
+    }
+
+    fn get_num_results(&self) -> u32 {
+        self.inner.get_num_results()
+    }
+
+    fn get_total_results(&self) -> u32 {
+        self.inner.get_total_results()
+    }
+
+    fn get_num_file_results(&self) -> u32 {
+        self.inner.get_num_file_results()
+    }
+
+    fn get_num_folder_results(&self) -> u32 {
+        self.inner.get_num_folder_results()
+    }
+
+    fn get_total_file_results(&self) -> u32 {
+        self.inner.get_total_file_results()
+    }
+
+    fn get_total_folder_results(&self) -> u32 {
+        self.inner.get_total_folder_results()
+    }
+
+    fn sort_results_by_path(&self) {
+        self.inner.sort_results_by_path()
+    }
+
+    fn is_result_file(&self, index: u32) -> bool {
+        self.inner.is_result_file(index)
+    }
+
+    fn is_result_folder(&self, index: u32) -> bool {
+        self.inner.is_result_folder(index)
+    }
+
+    fn is_result_volume(&self, index: u32) -> bool {
+        self.inner.is_result_volume(index)
+    }
+
+    fn get_result_full_path(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_full_path(index)
+    }
+
+    fn get_result_path(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_path(index)
+    }
+
+    fn get_result_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_file_name(index)
+    }
+
+    fn get_result_extension(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_extension(index)
+    }
+
+    fn get_result_size(&self, index: u32) -> EverythingResult<u64> {
+        self.inner.get_result_size(index)
+    }
+
+    fn get_result_created_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.inner.get_result_created_date(index)
+    }
+
+    fn get_result_modified_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.inner.get_result_modified_date(index)
+    }
+
+    fn get_result_accessed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.inner.get_result_accessed_date(index)
+    }
+
+    fn get_result_run_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.inner.get_result_run_date(index)
+    }
+
+    fn get_result_recently_changed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.inner.get_result_recently_changed_date(index)
+    }
+
+    fn get_result_attributes(&self, index: u32) -> EverythingResult<FileAttributes> {
+        self.inner.get_result_attributes(index)
+    }
+
+    fn get_result_run_count(&self, index: u32) -> EverythingResult<u32> {
+        self.inner.get_result_run_count(index)
+    }
+
+    fn get_result_file_list_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_file_list_file_name(index)
+    }
+
+    fn get_result_highlighted_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_highlighted_file_name(index)
+    }
+
+    fn get_result_highlighted_path(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_highlighted_path(index)
+    }
+
+    fn get_result_highlighted_full_path(&self, index: u32) -> EverythingResult<String> {
+        self.inner.get_result_highlighted_full_path(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, IpcTransport, LoopbackTransport};
+    use crate::test_util::test_entries;
+    use crate::{CancellationToken, InMemoryBackend};
+
+    /// Retries every few milliseconds so the tests do not wait long.
+    fn quick_backoff(max_attempts: Option<u32>) -> Backoff {
+        Backoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(4),
+            multiplier: 2,
+            max_attempts,
+        }
+    }
+
+    fn reconnecting(
+        max_attempts: Option<u32>,
+    ) -> ReconnectingBackend<IpcClient<LoopbackTransport>> {
+        let files = InMemoryBackend::new(test_entries(&["test1.txt"]));
+        ReconnectingBackend::new(
+            IpcClient::new(LoopbackTransport::new(files)),
+            quick_backoff(max_attempts),
+        )
+    }
+
+    #[test]
+    fn backs_off() {
+        let backoff = Backoff::default();
+        assert_eq!(backoff.delay(0), Duration::from_millis(250));
+        assert_eq!(backoff.delay(2), Duration::from_secs(1));
+        assert_eq!(backoff.delay(6), Duration::from_secs(10));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn reconnects_after_a_restart() {
+        let backend = reconnecting(None);
+        let transport = backend.inner().transport();
+        let states = backend.subscribe();
+        backend.set_search("test1.txt");
+        transport.set_running(false);
+
+        let states = std::thread::scope(|scope| {
+            let restart = scope.spawn(move || {
+                assert_eq!(states.recv(), Ok(ConnectionState::Disconnected));
+                transport.backend().set_db_loaded(false);
+                transport.set_running(true);
+                assert_eq!(states.recv(), Ok(ConnectionState::LoadingDatabase));
+                transport.backend().set_db_loaded(true);
+                states
+            });
+            backend.query().unwrap();
+            restart.join().unwrap()
+        });
+
+        assert_eq!(states.try_recv(), Ok(ConnectionState::Connected));
+        assert_eq!(backend.state(), ConnectionState::Connected);
+        assert_eq!(backend.get_num_results(), 1);
+        assert_eq!(backend.get_result_file_name(0).unwrap(), "test1.txt");
+    }
+
+    #[test]
+    fn gives_up() {
+        let backend = reconnecting(Some(3));
+        let states = backend.subscribe();
+        backend.inner().transport().set_running(false);
+        assert!(matches!(
+            backend.query(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert_eq!(backend.state(), ConnectionState::Disconnected);
+        assert_eq!(states.try_iter().count(), 1);
+
+        let backend = reconnecting(None);
+        backend.inner().transport().set_running(false);
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(matches!(
+            backend.query_with(&QueryLimits::cancel(cancel)),
+            Err(EverythingError::Cancelled)
+        ));
+        assert!(matches!(
+            backend.query_with(&QueryLimits::timeout(Duration::from_millis(20))),
+            Err(EverythingError::QueryTimeout)
+        ));
+
+        backend.inner().transport().set_running(true);
+        assert_eq!(backend.check_health(), ConnectionState::Connected);
+        assert!(backend.query().is_ok());
+    }
+
+    /// Answers every command like a running Everything with its database loaded but fails every query.
+    struct Flapping;
+
+    impl IpcTransport for Flapping {
+        fn send_command(&self, _command: u32, _param: u32) -> EverythingResult<u32> {
+            Ok(1)
+        }
+
+        fn send_copydata(&self, _copydata: u32, _data: &[u8]) -> EverythingResult<u32> {
+            Ok(1)
+        }
+
+        fn send_query(
+            &self,
+            _copydata: u32,
+            _reply_id: u32,
+            _encode: &dyn Fn(u32) -> Vec<u8>,
+            _limits: &QueryLimits,
+        ) -> EverythingResult<Vec<u8>> {
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        }
+    }
+
+    #[test]
+    fn disconnects_when_the_retry_fails() {
+        let backend = ReconnectingBackend::new(IpcClient::new(Flapping), quick_backoff(None));
+        let states = backend.subscribe();
+        assert!(matches!(
+            backend.query(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert_eq!(backend.state(), ConnectionState::Disconnected);
+        assert_eq!(
+            states.try_iter().collect::<Vec<_>>(),
+            [
+                ConnectionState::Disconnected,
+                ConnectionState::Connected,
+                ConnectionState::Disconnected
+            ]
+        );
+    }
+
+    #[test]
+    fn sleeps_until_the_limits_fail() {
+        assert!(matches!(
+            sleep(
+                Duration::MAX,
+                &QueryLimits::timeout(Duration::from_millis(10))
+            ),
+            Err(EverythingError::QueryTimeout)
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Everything, EverythingError, EverythingResult, EverythingSDKError, SearchBackend};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// How long `build` waits for the database by default, the same as `Everything::try_new`.
+const DEFAULT_DB_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Creates an `Everything` without panicking, see `Everything::builder`.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::{Everything, EverythingError, InMemoryBackend};
+/// use std::time::Duration;
+///
+/// let backend = InMemoryBackend::new(Vec::new());
+/// backend.set_db_loaded(false);
+/// let everything = Everything::builder()
+///     .backend(backend)
+///     .wait_db_loaded(Some(Duration::ZERO))
+///     .build();
+/// assert!(matches!(everything, Err(EverythingError::DatabaseTimeout)));
+/// ```
+pub struct EverythingBuilder {
+    backend: Option<Box<dyn SearchBackend>>,
+    instance: Option<String>,
+    db_timeout: Option<Duration>,
+}
+
+impl EverythingBuilder {
+    pub(crate) fn new() -> EverythingBuilder {
+        EverythingBuilder {
+            backend: None,
+            instance: None,
+            db_timeout: Some(DEFAULT_DB_TIMEOUT),
+        }
+    }
+
+    /// Forwards every call to `backend` instead of connecting to the running Everything instance over IPC.
+    pub fn backend<B: SearchBackend + 'static>(mut self, backend: B) -> EverythingBuilder {
+        self.backend = Some(Box::new(backend));
+        self
+    }
+
+    /// Connects to the Everything instance started with `-instance name` instead of the unnamed instance.
+    /// Cannot be combined with `backend`, select the instance on its transport instead.
+    pub fn instance(mut self, name: &str) -> EverythingBuilder {
+        self.instance = Some(name.to_string());
+        self
+    }
+
+    /// How long `build` waits for the database to load, `None` to not wait. Defaults to 5 seconds.
+    pub fn wait_db_loaded(mut self, timeout: Option<Duration>) -> EverythingBuilder {
+        self.db_timeout = timeout;
+        self
+    }
+
+    /// Fails with `EverythingSDKError::Ipc` if Everything is not running, or when no backend was given on other platforms than windows,
+    /// with `EverythingSDKError::InvalidParameter` if both a backend and an instance were given,
+    /// and with `EverythingError::DatabaseTimeout` if the database did not load in time.
+    pub fn build(self) -> EverythingResult<Everything> {
+        let backend = match (self.backend, self.instance) {
+            (Some(_), Some(_)) => {
+                return Err(EverythingError::SDKError(
+                    EverythingSDKError::InvalidParameter,
+                ))
+            }
+            (Some(backend), None) => backend,
+            (None, instance) => default_backend(instance.as_deref())?,
+        };
+        let everything = Everything {
+            backend,
+            capabilities: OnceLock::new(),
+        };
+        if let Some(timeout) = self.db_timeout {
+            everything.wait_db_loaded(timeout)?;
+        }
+        Ok(everything)
+    }
+}
+
+impl std::fmt::Debug for EverythingBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EverythingBuilder")
+            .field("instance", &self.instance)
+            .field("db_timeout", &self.db_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn default_backend(instance: Option<&str>) -> EverythingResult<Box<dyn SearchBackend>> {
+    let transport = match instance {
+        Some(instance) => crate::ipc::WindowsTransport::for_instance(instance),
+        None => crate::ipc::WindowsTransport::new(),
+    };
+    Ok(Box::new(crate::ipc::IpcClient::new(transport)))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn default_backend(_instance: Option<&str>) -> EverythingResult<Box<dyn SearchBackend>> {
+    Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, LoopbackTransport};
+    use crate::{InMemoryBackend, InMemoryEntry};
+
+    #[test]
+    fn reports_startup_failures() {
+        let transport = LoopbackTransport::new(InMemoryBackend::new(Vec::new()));
+        transport.set_running(false);
+        let stopped = Everything::builder()
+            .backend(IpcClient::new(transport))
+            .build();
+        assert!(matches!(
+            stopped,
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+
+        let loading = InMemoryBackend::new(Vec::new());
+        loading.set_db_loaded(false);
+        let loading = Everything::builder()
+            .backend(loading)
+            .wait_db_loaded(Some(Duration::from_millis(10)))
+            .build();
+        assert!(matches!(loading, Err(EverythingError::DatabaseTimeout)));
+
+        let not_waiting = InMemoryBackend::new(vec![InMemoryEntry::file("C:\\test", "a.txt")]);
+        not_waiting.set_db_loaded(false);
+        let not_waiting = Everything::builder()
+            .backend(not_waiting)
+            .wait_db_loaded(None)
+            .build()
+            .unwrap();
+        assert!(not_waiting.query().is_err());
+
+        let both = Everything::builder()
+            .backend(InMemoryBackend::new(Vec::new()))
+            .instance("1.5a")
+            .build();
+        assert!(matches!(
+            both,
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidParameter
+            ))
+        ));
+
+        #[cfg(not(target_os = "windows"))]
+        assert!(matches!(
+            Everything::builder().build(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+    }
+}
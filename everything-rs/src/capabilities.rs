@@ -0,0 +1,406 @@
+use crate::ipc::{
+    needs_query2, EVERYTHING_IPC_FILE_INFO_ATTRIBUTES, EVERYTHING_IPC_FILE_INFO_DATE_ACCESSED,
+    EVERYTHING_IPC_FILE_INFO_DATE_CREATED, EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED,
+    EVERYTHING_IPC_FILE_INFO_FILE_SIZE, EVERYTHING_IPC_FILE_INFO_FOLDER_SIZE,
+    EVERYTHING_IPC_GET_BUILD_NUMBER, EVERYTHING_IPC_GET_MAJOR_VERSION,
+    EVERYTHING_IPC_GET_MINOR_VERSION, EVERYTHING_IPC_GET_REVISION, EVERYTHING_IPC_IS_FAST_SORT,
+    EVERYTHING_IPC_IS_FILE_INFO_INDEXED,
+};
+use crate::sys::{EVERYTHING_SORT_DATE_RUN_DESCENDING, EVERYTHING_SORT_NAME_ASCENDING};
+use crate::{
+    Everything, EverythingError, EverythingRequestFlags, EverythingResult, EverythingSDKError,
+    EverythingSort, SearchRequest,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use ts_rs::TS;
+
+/// The version of Everything, ordered so callers can compare against the release a feature needs.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::Version;
+///
+/// let version = Version::new(1, 4, 1, 1024);
+/// assert!(version >= Version::QUERY2);
+/// assert!(version < Version::V1_5);
+/// assert_eq!(version.to_string(), "1.4.1.1024");
+/// ```
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize, TS,
+)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub revision: u32,
+    pub build: u32,
+}
+
+impl Version {
+    /// The first release answering `EVERYTHING_IPC_COPYDATA_QUERY2`.
+    /// Older releases only answer version 1 queries, which return the name and path sorted by name, see `needs_query2`.
+    pub const QUERY2: Version = Version::new(1, 4, 1, 0);
+    /// The first 1.5 release.
+    pub const V1_5: Version = Version::new(1, 5, 0, 0);
+
+    pub const fn new(major: u32, minor: u32, revision: u32, build: u32) -> Version {
+        Version {
+            major,
+            minor,
+            revision,
+            build,
+        }
+    }
+}
+
+impl From<(u32, u32, u32, u32)> for Version {
+    fn from((major, minor, revision, build): (u32, u32, u32, u32)) -> Self {
+        Version::new(major, minor, revision, build)
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.revision, self.build
+        )
+    }
+}
+
+/// File information Everything can keep in its index, see `Capabilities::is_file_info_indexed`.
+/// Unindexed information is read from disk for every result, which is slow on large result sets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub enum FileInfo {
+    FileSize,
+    FolderSize,
+    DateCreated,
+    DateModified,
+    DateAccessed,
+    Attributes,
+}
+
+impl FileInfo {
+    pub const ALL: [FileInfo; 6] = [
+        FileInfo::FileSize,
+        FileInfo::FolderSize,
+        FileInfo::DateCreated,
+        FileInfo::DateModified,
+        FileInfo::DateAccessed,
+        FileInfo::Attributes,
+    ];
+
+    /// The information a request flag reads, `None` for flags Everything always has at hand.
+    /// `Size` maps to `FileSize`, check `FolderSize` separately when the results include folders.
+    fn requested_by(flag: EverythingRequestFlags) -> Option<FileInfo> {
+        [
+            (EverythingRequestFlags::Size, FileInfo::FileSize),
+            (EverythingRequestFlags::DateCreated, FileInfo::DateCreated),
+            (EverythingRequestFlags::DateModified, FileInfo::DateModified),
+            (EverythingRequestFlags::DateAccessed, FileInfo::DateAccessed),
+            (EverythingRequestFlags::Attributes, FileInfo::Attributes),
+        ]
+        .into_iter()
+        .find_map(|(requested, info)| (requested == flag).then_some(info))
+    }
+
+    fn request_flag(self) -> Option<EverythingRequestFlags> {
+        match self {
+            FileInfo::FileSize => Some(EverythingRequestFlags::Size),
+            FileInfo::FolderSize => None,
+            FileInfo::DateCreated => Some(EverythingRequestFlags::DateCreated),
+            FileInfo::DateModified => Some(EverythingRequestFlags::DateModified),
+            FileInfo::DateAccessed => Some(EverythingRequestFlags::DateAccessed),
+            FileInfo::Attributes => Some(EverythingRequestFlags::Attributes),
+        }
+    }
+}
+
+impl From<FileInfo> for u32 {
+    fn from(info: FileInfo) -> Self {
+        match info {
+            FileInfo::FileSize => EVERYTHING_IPC_FILE_INFO_FILE_SIZE,
+            FileInfo::FolderSize => EVERYTHING_IPC_FILE_INFO_FOLDER_SIZE,
+            FileInfo::DateCreated => EVERYTHING_IPC_FILE_INFO_DATE_CREATED,
+            FileInfo::DateModified => EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED,
+            FileInfo::DateAccessed => EVERYTHING_IPC_FILE_INFO_DATE_ACCESSED,
+            FileInfo::Attributes => EVERYTHING_IPC_FILE_INFO_ATTRIBUTES,
+        }
+    }
+}
+
+impl TryFrom<u32> for FileInfo {
+    type Error = EverythingError;
+
+    fn try_from(info: u32) -> Result<Self, Self::Error> {
+        FileInfo::ALL
+            .into_iter()
+            .find(|known| u32::from(*known) == info)
+            .ok_or(EverythingError::SDKError(
+                EverythingSDKError::InvalidParameter,
+            ))
+    }
+}
+
+/// Something about a `SearchRequest` the running Everything cannot do quickly, see `Capabilities::review`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub enum CapabilityWarning {
+    /// Everything is older than 1.4.1 and answers with a version 1 query,
+    /// which ignores the sort and every request flag other than the name and path.
+    Query2Unsupported(Version),
+    /// The sort is not indexed, Everything sorts every result after searching.
+    SlowSort(EverythingSort),
+    /// The requested information is not indexed and is read from disk for every result.
+    UnindexedFileInfo(FileInfo),
+}
+
+/// What to do about a request the running Everything cannot answer quickly, see `SearchRequest::slow_queries`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize, TS)]
+pub enum SlowQueryPolicy {
+    /// Sends the request as is without asking Everything about it.
+    #[default]
+    Allow,
+    /// Sends the request as is and lists the problems in `ResultPage::warnings`.
+    Warn,
+    /// Sorts by name instead of a slow sort and drops unindexed request flags, or everything but the name and path before 1.4.1,
+    /// listing what was changed in `ResultPage::warnings`.
+    Degrade,
+}
+
+/// What the running Everything supports and has indexed, see `Everything::capabilities`.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::{
+///     CapabilityWarning, Everything, EverythingSort, FileInfo, InMemoryBackend, SearchRequest,
+/// };
+///
+/// let everything = Everything::with_backend(InMemoryBackend::new(Vec::new()));
+/// let capabilities = everything.capabilities().unwrap();
+/// assert!(capabilities.supports_query2());
+/// assert!(capabilities.is_file_info_indexed(FileInfo::DateModified));
+///
+/// let mut request = SearchRequest {
+///     sort: EverythingSort::DateCreatedDescending,
+///     ..SearchRequest::new("*.log")
+/// };
+/// assert_eq!(
+///     capabilities.degrade(&mut request),
+///     [CapabilityWarning::SlowSort(EverythingSort::DateCreatedDescending)]
+/// );
+/// assert_eq!(request.sort, EverythingSort::NameAscending);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub struct Capabilities {
+    pub version: Version,
+    /// The sorts Everything has indexed, in the order of their SDK values.
+    pub fast_sorts: Vec<EverythingSort>,
+    pub indexed_file_info: Vec<FileInfo>,
+}
+
+impl Capabilities {
+    /// Whether Everything answers `EVERYTHING_IPC_COPYDATA_QUERY2`, required for sorts other than
+    /// `EverythingSort::NameAscending` and request flags other than the name and path.
+    pub fn supports_query2(&self) -> bool {
+        self.version >= Version::QUERY2
+    }
+
+    /// Whether Everything is 1.5 or newer, which adds search flags such as `IpcSearchFlags::MatchPrefix`.
+    pub fn supports_1_5(&self) -> bool {
+        self.version >= Version::V1_5
+    }
+
+    pub fn is_fast_sort(&self, sort: EverythingSort) -> bool {
+        self.fast_sorts.contains(&sort)
+    }
+
+    pub fn is_file_info_indexed(&self, info: FileInfo) -> bool {
+        self.indexed_file_info.contains(&info)
+    }
+
+    /// Lists everything about `request` that is unavailable or slow, an empty list if it is answered from the index.
+    pub fn review(&self, request: &SearchRequest) -> Vec<CapabilityWarning> {
+        let mut warnings = Vec::new();
+        if !self.supports_query2() && needs_query2(request.request_flags, request.sort) {
+            warnings.push(CapabilityWarning::Query2Unsupported(self.version));
+        }
+        if !self.is_fast_sort(request.sort) {
+            warnings.push(CapabilityWarning::SlowSort(request.sort));
+        }
+        warnings.extend(
+            request
+                .request_flags
+                .iter()
+                .filter_map(FileInfo::requested_by)
+                .filter(|info| !self.is_file_info_indexed(*info))
+                .map(CapabilityWarning::UnindexedFileInfo),
+        );
+        warnings
+    }
+
+    /// Changes `request` so it is answered from the index where possible: a slow sort becomes
+    /// `EverythingSort::NameAscending` and unindexed request flags are removed.
+    /// Before 1.4.1 the request is reduced to what a version 1 query returns, the name and path sorted by name.
+    /// Returns the same warnings as `review`.
+    pub fn degrade(&self, request: &mut SearchRequest) -> Vec<CapabilityWarning> {
+        let warnings = self.review(request);
+        for warning in &warnings {
+            match warning {
+                CapabilityWarning::Query2Unsupported(_) => {
+                    request.sort = EverythingSort::NameAscending;
+                    request.request_flags =
+                        EverythingRequestFlags::FileName | EverythingRequestFlags::Path;
+                }
+                CapabilityWarning::SlowSort(_) => request.sort = EverythingSort::NameAscending,
+                CapabilityWarning::UnindexedFileInfo(info) => {
+                    if let Some(flag) = info.request_flag() {
+                        request.request_flags.remove(flag);
+                    }
+                }
+            }
+        }
+        warnings
+    }
+}
+
+impl Everything {
+    /// Asks Everything for its version, which sorts are fast and which file information is indexed.
+    /// This sends one IPC command per sort and per `FileInfo`, keep the result rather than calling it per query.
+    /// Fails with `EverythingSDKError::Ipc` if Everything is not running.
+    pub fn capabilities(&self) -> EverythingResult<Capabilities> {
+        let backend = &*self.backend;
+        let version = Version::new(
+            backend.send_command(EVERYTHING_IPC_GET_MAJOR_VERSION, 0)?,
+            backend.send_command(EVERYTHING_IPC_GET_MINOR_VERSION, 0)?,
+            backend.send_command(EVERYTHING_IPC_GET_REVISION, 0)?,
+            backend.send_command(EVERYTHING_IPC_GET_BUILD_NUMBER, 0)?,
+        );
+        if version.major == 0 {
+            return Err(EverythingError::SDKError(EverythingSDKError::Ipc));
+        }
+
+        let mut fast_sorts = Vec::new();
+        for sort in (EVERYTHING_SORT_NAME_ASCENDING..=EVERYTHING_SORT_DATE_RUN_DESCENDING)
+            .filter_map(|sort| EverythingSort::try_from(sort).ok())
+        {
+            if backend.send_command(EVERYTHING_IPC_IS_FAST_SORT, sort.into())? != 0 {
+                fast_sorts.push(sort);
+            }
+        }
+        let mut indexed_file_info = Vec::new();
+        for info in FileInfo::ALL {
+            if backend.send_command(EVERYTHING_IPC_IS_FILE_INFO_INDEXED, info.into())? != 0 {
+                indexed_file_info.push(info);
+            }
+        }
+        Ok(Capabilities {
+            version,
+            fast_sorts,
+            indexed_file_info,
+        })
+    }
+
+    /// The capabilities probed by the first call, so searches checking for slow queries do not probe every time.
+    pub(crate) fn cached_capabilities(&self) -> EverythingResult<&Capabilities> {
+        if let Some(capabilities) = self.capabilities.get() {
+            return Ok(capabilities);
+        }
+        let capabilities = self.capabilities()?;
+        Ok(self.capabilities.get_or_init(|| capabilities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, LoopbackTransport};
+    use crate::{InMemoryBackend, InMemoryEntry};
+
+    #[test]
+    fn orders_versions() {
+        assert!(Version::new(1, 4, 0, 999) < Version::QUERY2);
+        assert!(Version::new(1, 4, 1, 1024) < Version::new(1, 5, 0, 1384));
+        assert!(Version::new(1, 10, 0, 0) > Version::V1_5);
+        assert_eq!(Version::from((1, 5, 0, 1)).to_string(), "1.5.0.1");
+        for info in FileInfo::ALL {
+            assert_eq!(FileInfo::try_from(u32::from(info)).unwrap(), info);
+        }
+        assert!(FileInfo::try_from(7).is_err());
+    }
+
+    #[test]
+    fn probes_over_ipc() {
+        let everything = Everything::with_backend(IpcClient::new(LoopbackTransport::new(
+            InMemoryBackend::new(vec![InMemoryEntry::file("C:\\", "a.txt")]),
+        )));
+        let capabilities = everything.capabilities().unwrap();
+        assert_eq!(capabilities.version, Version::new(1, 4, 1, 1024));
+        assert!(capabilities.supports_query2());
+        assert!(!capabilities.supports_1_5());
+        assert!(capabilities.is_fast_sort(EverythingSort::SizeDescending));
+        assert!(!capabilities.is_fast_sort(EverythingSort::RunCountDescending));
+        assert_eq!(
+            capabilities.indexed_file_info,
+            [FileInfo::FileSize, FileInfo::DateModified]
+        );
+
+        let old = InMemoryBackend::new(Vec::new());
+        old.set_version(1, 4, 0, 900);
+        let capabilities = Everything::with_backend(old).capabilities().unwrap();
+        assert!(capabilities.review(&SearchRequest::default()).is_empty());
+        let mut request = SearchRequest {
+            sort: EverythingSort::SizeDescending,
+            request_flags: EverythingRequestFlags::FullPathAndFileName
+                | EverythingRequestFlags::Size,
+            ..SearchRequest::default()
+        };
+        let warnings = [CapabilityWarning::Query2Unsupported(Version::new(
+            1, 4, 0, 900,
+        ))];
+        assert_eq!(capabilities.degrade(&mut request), warnings);
+        assert_eq!(request.sort, EverythingSort::NameAscending);
+        assert_eq!(
+            request.request_flags,
+            EverythingRequestFlags::FileName | EverythingRequestFlags::Path
+        );
+        assert!(capabilities.review(&request).is_empty());
+
+        let transport = LoopbackTransport::new(InMemoryBackend::new(Vec::new()));
+        transport.set_running(false);
+        assert!(matches!(
+            Everything::with_backend(IpcClient::new(transport)).capabilities(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+    }
+
+    #[test]
+    fn degrades_slow_requests() {
+        let capabilities = Capabilities {
+            version: Version::new(1, 5, 0, 1384),
+            fast_sorts: vec![EverythingSort::NameAscending],
+            indexed_file_info: vec![FileInfo::FileSize],
+        };
+        let mut request = SearchRequest {
+            sort: EverythingSort::PathDescending,
+            request_flags: EverythingRequestFlags::FileName
+                | EverythingRequestFlags::Size
+                | EverythingRequestFlags::Attributes
+                | EverythingRequestFlags::DateCreated,
+            ..SearchRequest::default()
+        };
+        let warnings = [
+            CapabilityWarning::SlowSort(EverythingSort::PathDescending),
+            CapabilityWarning::UnindexedFileInfo(FileInfo::DateCreated),
+            CapabilityWarning::UnindexedFileInfo(FileInfo::Attributes),
+        ];
+        assert_eq!(capabilities.review(&request), warnings);
+        assert_eq!(capabilities.degrade(&mut request), warnings);
+        assert_eq!(request.sort, EverythingSort::NameAscending);
+        assert_eq!(
+            request.request_flags,
+            EverythingRequestFlags::FileName | EverythingRequestFlags::Size
+        );
+        assert!(capabilities.review(&request).is_empty());
+    }
+}
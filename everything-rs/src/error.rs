@@ -1,7 +1,5 @@
-#[cfg(target_os = "windows")]
-extern crate everything_sys_bindgen;
-
-use everything_sys_bindgen::*;
+use crate::ipc::IpcCodecError;
+use crate::sys::*;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use ts_rs::TS;
@@ -46,6 +44,8 @@ pub enum EverythingSDKError {
     InvalidRequest = EVERYTHING_ERROR_INVALIDREQUEST,
     /// bad parameter.
     InvalidParameter = EVERYTHING_ERROR_INVALIDPARAMETER,
+    /// An error code this crate does not know, for example from a newer SDK.
+    Unknown(u32),
 }
 
 impl From<EverythingSDKError> for String {
@@ -54,11 +54,27 @@ impl From<EverythingSDKError> for String {
     }
 }
 
-impl TryFrom<u32> for EverythingSDKError {
-    type Error = &'static str;
+impl EverythingSDKError {
+    /// The `EVERYTHING_ERROR_*` code of this error.
+    pub fn code(self) -> u32 {
+        match self {
+            EverythingSDKError::Ok => EVERYTHING_OK,
+            EverythingSDKError::Memory => EVERYTHING_ERROR_MEMORY,
+            EverythingSDKError::Ipc => EVERYTHING_ERROR_IPC,
+            EverythingSDKError::RegisterClassEx => EVERYTHING_ERROR_REGISTERCLASSEX,
+            EverythingSDKError::CreateWindow => EVERYTHING_ERROR_CREATEWINDOW,
+            EverythingSDKError::CreateThread => EVERYTHING_ERROR_CREATETHREAD,
+            EverythingSDKError::InvalidIndex => EVERYTHING_ERROR_INVALIDINDEX,
+            EverythingSDKError::InvalidCall => EVERYTHING_ERROR_INVALIDCALL,
+            EverythingSDKError::InvalidRequest => EVERYTHING_ERROR_INVALIDREQUEST,
+            EverythingSDKError::InvalidParameter => EVERYTHING_ERROR_INVALIDPARAMETER,
+            EverythingSDKError::Unknown(code) => code,
+        }
+    }
 
-    fn try_from(error: u32) -> Result<Self, Self::Error> {
-        let err = match error {
+    /// The error for an SDK error code, codes without a variant become `EverythingSDKError::Unknown`.
+    pub fn from_code(code: u32) -> EverythingSDKError {
+        match code {
             EVERYTHING_OK => EverythingSDKError::Ok,
             EVERYTHING_ERROR_MEMORY => EverythingSDKError::Memory,
             EVERYTHING_ERROR_IPC => EverythingSDKError::Ipc,
@@ -69,9 +85,20 @@ impl TryFrom<u32> for EverythingSDKError {
             EVERYTHING_ERROR_INVALIDCALL => EverythingSDKError::InvalidCall,
             EVERYTHING_ERROR_INVALIDREQUEST => EverythingSDKError::InvalidRequest,
             EVERYTHING_ERROR_INVALIDPARAMETER => EverythingSDKError::InvalidParameter,
-            _ => Err("Unknown error code")?,
-        };
-        Ok(err)
+            code => EverythingSDKError::Unknown(code),
+        }
+    }
+}
+
+impl TryFrom<u32> for EverythingSDKError {
+    type Error = &'static str;
+
+    /// Fails for codes without a variant, see `from_code` to keep them as `EverythingSDKError::Unknown`.
+    fn try_from(error: u32) -> Result<Self, Self::Error> {
+        match EverythingSDKError::from_code(error) {
+            EverythingSDKError::Unknown(_) => Err("Unknown error code"),
+            err => Ok(err),
+        }
     }
 }
 
@@ -89,6 +116,20 @@ pub enum EverythingError {
     DatabaseTimeout,
     #[error("Null pointer returned by Everything SDK")]
     NullPointerError,
+    #[error("Malformed IPC data: {0}")]
+    IpcCodec(#[from] IpcCodecError),
+    #[error("The query was cancelled")]
+    Cancelled,
+    #[error("The query did not finish before its deadline")]
+    QueryTimeout,
+    #[error("The number of results changed from {previous} to {current} between pages")]
+    ResultsChanged { previous: u32, current: u32 },
+    #[error("The worker running queries stopped before replying")]
+    WorkerStopped,
+    #[error("Everything did not carry out IPC command {0}")]
+    CommandRejected(u32),
+    #[error("Everything SDK3 error: {0}")]
+    Sdk3(#[from] crate::sdk3::Sdk3Error),
 }
 
 impl EverythingError {
@@ -103,6 +144,11 @@ impl EverythingError {
             EverythingError::SDKError(EverythingSDKError::Memory) => {
                 "Out of memory error. The system might be low on resources.".to_string()
             }
+            EverythingError::QueryTimeout => {
+                "The search took too long. Complex searches such as content: or regex can be slow, \
+                try narrowing the search or allowing more time."
+                    .to_string()
+            }
             EverythingError::DatabaseTimeout => {
                 "Timeout waiting for Everything database. The database might be still loading or \
                 the Everything service might be busy. Please try again later."
@@ -114,3 +160,26 @@ impl EverythingError {
 }
 
 pub type EverythingResult<T> = Result<T, EverythingError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_unknown_codes() {
+        for code in 0..=EVERYTHING_ERROR_INVALIDPARAMETER {
+            let err = EverythingSDKError::from_code(code);
+            assert_eq!(EverythingSDKError::try_from(code), Ok(err));
+            assert!(!matches!(err, EverythingSDKError::Unknown(_)));
+            assert_eq!(err.code(), code);
+        }
+        let unknown = EverythingSDKError::from_code(0xdead);
+        assert!(EverythingSDKError::try_from(0xdead).is_err());
+        assert_eq!(unknown, EverythingSDKError::Unknown(0xdead));
+        assert_eq!(unknown.code(), 0xdead);
+        assert_eq!(
+            EverythingError::SDKError(unknown).to_string(),
+            "Everything SDK error: Unknown(57005)"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use ts_rs::TS;
+
+/// 100 nanosecond intervals between 1601-01-01 and 1970-01-01.
+const UNIX_EPOCH_TICKS: u64 = 116_444_736_000_000_000;
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// A date returned by Everything, the number of 100 nanosecond intervals since 1601-01-01 UTC.
+/// Everything reports dates it does not know as `FileTime::UNKNOWN`.
+/// See <https://learn.microsoft.com/en-us/windows/win32/api/minwinbase/ns-minwinbase-filetime>
+///
+/// # Example
+/// ```rust
+/// use everything_rs::FileTime;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let date = FileTime::from_raw(116_444_736_000_000_000 + 10_000_000);
+/// assert_eq!(date.to_system_time(), Some(UNIX_EPOCH + Duration::from_secs(1)));
+/// assert_eq!(FileTime::UNKNOWN.to_system_time(), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TS)]
+pub struct FileTime(u64);
+
+impl FileTime {
+    /// The value Everything uses for a date it does not know.
+    pub const UNKNOWN: FileTime = FileTime(u64::MAX);
+
+    pub const fn from_raw(ticks: u64) -> FileTime {
+        FileTime(ticks)
+    }
+
+    /// The raw FILETIME value.
+    pub const fn raw(self) -> u64 {
+        self.0
+    }
+
+    pub const fn is_unknown(self) -> bool {
+        self.0 == u64::MAX
+    }
+
+    /// Returns `None` for `FileTime::UNKNOWN`.
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        if self.is_unknown() {
+            return None;
+        }
+        let since_1601 = Duration::from_nanos(self.0 % TICKS_PER_SECOND * 100)
+            + Duration::from_secs(self.0 / TICKS_PER_SECOND);
+        let unix_epoch = Duration::from_secs(UNIX_EPOCH_TICKS / TICKS_PER_SECOND);
+        if since_1601 >= unix_epoch {
+            UNIX_EPOCH.checked_add(since_1601 - unix_epoch)
+        } else {
+            UNIX_EPOCH.checked_sub(unix_epoch - since_1601)
+        }
+    }
+
+    /// Converts `time`, saturating to 1601-01-01 and to the last date before `FileTime::UNKNOWN`.
+    pub fn from_system_time(time: SystemTime) -> FileTime {
+        let ticks = |duration: Duration| {
+            duration.as_secs() as u128 * TICKS_PER_SECOND as u128
+                + duration.subsec_nanos() as u128 / 100
+        };
+        let ticks = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => UNIX_EPOCH_TICKS as u128 + ticks(after),
+            Err(before) => (UNIX_EPOCH_TICKS as u128).saturating_sub(ticks(before.duration())),
+        };
+        FileTime(ticks.min(u64::MAX as u128 - 1) as u64)
+    }
+
+    /// Returns `None` for `FileTime::UNKNOWN` and dates chrono can not represent.
+    #[cfg(feature = "chrono")]
+    pub fn to_chrono(self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.to_system_time().map(chrono::DateTime::from)
+    }
+
+    /// Returns `None` for `FileTime::UNKNOWN` and dates time can not represent.
+    #[cfg(feature = "time")]
+    pub fn to_offset_date_time(self) -> Option<time::OffsetDateTime> {
+        if self.is_unknown() {
+            return None;
+        }
+        let nanos = (self.0 as i128 - UNIX_EPOCH_TICKS as i128) * 100;
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+    }
+}
+
+impl From<u64> for FileTime {
+    fn from(ticks: u64) -> FileTime {
+        FileTime(ticks)
+    }
+}
+
+impl From<FileTime> for u64 {
+    fn from(time: FileTime) -> u64 {
+        time.0
+    }
+}
+
+impl From<SystemTime> for FileTime {
+    fn from(time: SystemTime) -> FileTime {
+        FileTime::from_system_time(time)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for FileTime {
+    fn from(time: chrono::DateTime<Tz>) -> FileTime {
+        FileTime::from_system_time(time.with_timezone(&chrono::Utc).into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for FileTime {
+    fn from(time: time::OffsetDateTime) -> FileTime {
+        let ticks = time.unix_timestamp_nanos() / 100 + UNIX_EPOCH_TICKS as i128;
+        FileTime(ticks.clamp(0, u64::MAX as i128 - 1) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_system_time() {
+        assert_eq!(
+            FileTime::from_raw(UNIX_EPOCH_TICKS).to_system_time(),
+            Some(UNIX_EPOCH)
+        );
+        assert_eq!(
+            FileTime::from_raw(UNIX_EPOCH_TICKS + 15).to_system_time(),
+            Some(UNIX_EPOCH + Duration::from_nanos(1500))
+        );
+        assert_eq!(FileTime::UNKNOWN.to_system_time(), None);
+        assert!(FileTime::UNKNOWN.is_unknown());
+
+        let before_unix = FileTime::from_raw(UNIX_EPOCH_TICKS - TICKS_PER_SECOND);
+        let system_time = before_unix.to_system_time();
+        assert_eq!(system_time, UNIX_EPOCH.checked_sub(Duration::from_secs(1)));
+        assert_eq!(FileTime::from(system_time.unwrap()), before_unix);
+    }
+
+    #[test]
+    fn saturates_from_system_time() {
+        let now = SystemTime::now();
+        let round_trip = FileTime::from(now).to_system_time().unwrap();
+        assert!(now.duration_since(round_trip).unwrap() < Duration::from_nanos(100));
+
+        if let Some(before_1601) = UNIX_EPOCH.checked_sub(Duration::from_secs(400 * 366 * 86400)) {
+            assert_eq!(FileTime::from(before_1601).raw(), 0);
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn converts_to_chrono() {
+        let date = FileTime::from_raw(UNIX_EPOCH_TICKS + 86400 * TICKS_PER_SECOND);
+        let chrono = date.to_chrono().unwrap();
+        assert_eq!(chrono.to_rfc3339(), "1970-01-02T00:00:00+00:00");
+        assert_eq!(FileTime::from(chrono), date);
+        assert_eq!(FileTime::UNKNOWN.to_chrono(), None);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn converts_to_time() {
+        let date = FileTime::from_raw(0);
+        let time = date.to_offset_date_time().unwrap();
+        assert_eq!(time.year(), 1601);
+        assert_eq!(FileTime::from(time), date);
+        assert_eq!(FileTime::UNKNOWN.to_offset_date_time(), None);
+    }
+}
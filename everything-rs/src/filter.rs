@@ -0,0 +1,206 @@
+use crate::ipc::{
+    EVERYTHING_IPC_FILTER_AUDIO, EVERYTHING_IPC_FILTER_COMPRESSED, EVERYTHING_IPC_FILTER_CUSTOM,
+    EVERYTHING_IPC_FILTER_DOCUMENT, EVERYTHING_IPC_FILTER_EVERYTHING,
+    EVERYTHING_IPC_FILTER_EXECUTABLE, EVERYTHING_IPC_FILTER_FOLDER, EVERYTHING_IPC_FILTER_PICTURE,
+    EVERYTHING_IPC_FILTER_VIDEO,
+};
+use crate::query::Query;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+mod filters;
+
+pub use filters::{Filter, Filters, FiltersError};
+
+const AUDIO: &[&str] = &[
+    "aac", "ac3", "aif", "aifc", "aiff", "amr", "ape", "au", "cda", "dts", "fla", "flac", "it",
+    "m1a", "m2a", "m3u", "m4a", "m4b", "m4p", "mid", "midi", "mka", "mod", "mp2", "mp3", "mpa",
+    "ogg", "opus", "pls", "ra", "rmi", "snd", "spc", "umx", "voc", "wav", "wma", "xm",
+];
+const COMPRESSED: &[&str] = &[
+    "7z", "ace", "arj", "bz2", "cab", "gz", "gzip", "jar", "r00", "r01", "r02", "r03", "r04",
+    "r05", "r06", "r07", "r08", "r09", "r10", "r11", "r12", "r13", "r14", "r15", "r16", "r17",
+    "r18", "r19", "r20", "r21", "r22", "r23", "r24", "r25", "r26", "r27", "r28", "r29", "rar",
+    "tar", "tgz", "z", "zip",
+];
+const DOCUMENT: &[&str] = &[
+    "c", "chm", "cpp", "csv", "cxx", "doc", "docm", "docx", "dot", "dotm", "dotx", "h", "hpp",
+    "htm", "html", "hxx", "ini", "java", "lua", "mht", "mhtml", "odt", "pdf", "potx", "potm",
+    "ppam", "ppsm", "ppsx", "pps", "ppt", "pptm", "pptx", "rtf", "sldm", "sldx", "thmx", "txt",
+    "vsd", "wpd", "wps", "wri", "xlam", "xls", "xlsb", "xlsm", "xlsx", "xltm", "xltx", "xml",
+];
+const EXECUTABLE: &[&str] = &["bat", "cmd", "exe", "msi", "msp", "scr"];
+const PICTURE: &[&str] = &[
+    "ani", "bmp", "gif", "ico", "jpe", "jpeg", "jpg", "pcx", "png", "psd", "tga", "tif", "tiff",
+    "webp", "wmf",
+];
+const VIDEO: &[&str] = &[
+    "3g2", "3gp", "3gp2", "3gpp", "amv", "asf", "avi", "bdmv", "bik", "d2v", "divx", "drc", "dsa",
+    "dsm", "dss", "dsv", "evo", "f4v", "flc", "fli", "flic", "flv", "hdmov", "ifo", "ivf", "m1v",
+    "m2p", "m2t", "m2ts", "m2v", "m4v", "mkv", "mp2v", "mp4", "mp4v", "mpe", "mpeg", "mpg", "mpls",
+    "mpv2", "mpv4", "mov", "mts", "ogm", "ogv", "pss", "pva", "qt", "ram", "ratdvd", "rm", "rmm",
+    "rmvb", "roq", "rpm", "smil", "smk", "swf", "tp", "tpr", "ts", "vob", "vp6", "webm", "wm",
+    "wmp", "wmv",
+];
+
+/// The filters built into Everything, selectable from the filter bar of a search window.
+/// See `SearchWindow::filter`, and `Filters` for the ones a user added.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::query::Query;
+/// use everything_rs::EverythingFilter;
+///
+/// let query = EverythingFilter::Executable.query().and(Query::text("setup"));
+/// assert_eq!(query.to_string(), "ext:bat;cmd;exe;msi;msp;scr setup");
+/// assert_eq!(EverythingFilter::Executable.macro_name(), Some("exe"));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub enum EverythingFilter {
+    Everything,
+    Audio,
+    Compressed,
+    Document,
+    Executable,
+    Folder,
+    Picture,
+    Video,
+    /// A filter added by the user.
+    Custom,
+}
+
+impl EverythingFilter {
+    /// Every built in filter in the order of the filter bar, without `Custom`.
+    pub const ALL: [EverythingFilter; 8] = [
+        EverythingFilter::Everything,
+        EverythingFilter::Audio,
+        EverythingFilter::Compressed,
+        EverythingFilter::Document,
+        EverythingFilter::Executable,
+        EverythingFilter::Folder,
+        EverythingFilter::Picture,
+        EverythingFilter::Video,
+    ];
+
+    /// The name Everything gives the filter in `Filters.csv`, `AUDIO` for `Audio`.
+    /// `Custom` is not stored in the file and is named `CUSTOM`.
+    pub fn name(self) -> &'static str {
+        match self {
+            EverythingFilter::Everything => "EVERYTHING",
+            EverythingFilter::Audio => "AUDIO",
+            EverythingFilter::Compressed => "COMPRESSED",
+            EverythingFilter::Document => "DOCUMENT",
+            EverythingFilter::Executable => "EXECUTABLE",
+            EverythingFilter::Folder => "FOLDER",
+            EverythingFilter::Picture => "PICTURE",
+            EverythingFilter::Video => "VIDEO",
+            EverythingFilter::Custom => "CUSTOM",
+        }
+    }
+
+    /// The extensions the filter matches, empty for filters that are not based on extensions.
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            EverythingFilter::Audio => AUDIO,
+            EverythingFilter::Compressed => COMPRESSED,
+            EverythingFilter::Document => DOCUMENT,
+            EverythingFilter::Executable => EXECUTABLE,
+            EverythingFilter::Picture => PICTURE,
+            EverythingFilter::Video => VIDEO,
+            EverythingFilter::Everything | EverythingFilter::Folder | EverythingFilter::Custom => {
+                &[]
+            }
+        }
+    }
+
+    /// The search macro Everything defines for the filter, `audio` for the `audio:` macro.
+    /// The macros can be edited by the user, `query` does not depend on them.
+    pub fn macro_name(self) -> Option<&'static str> {
+        match self {
+            EverythingFilter::Audio => Some("audio"),
+            EverythingFilter::Compressed => Some("zip"),
+            EverythingFilter::Document => Some("doc"),
+            EverythingFilter::Executable => Some("exe"),
+            EverythingFilter::Picture => Some("pic"),
+            EverythingFilter::Video => Some("video"),
+            EverythingFilter::Everything | EverythingFilter::Folder | EverythingFilter::Custom => {
+                None
+            }
+        }
+    }
+
+    /// The filter expanded into search syntax, for example `ext:bat;cmd;exe;msi;msp;scr`.
+    /// Empty for `Everything` and `Custom`, which match every result.
+    pub fn query(self) -> Query {
+        match self {
+            EverythingFilter::Folder => Query::raw("folder:"),
+            filter if filter.extensions().is_empty() => Query::new(),
+            filter => Query::ext(filter.extensions()),
+        }
+    }
+}
+
+impl From<EverythingFilter> for Query {
+    fn from(filter: EverythingFilter) -> Self {
+        filter.query()
+    }
+}
+
+impl From<EverythingFilter> for u32 {
+    fn from(filter: EverythingFilter) -> Self {
+        match filter {
+            EverythingFilter::Everything => EVERYTHING_IPC_FILTER_EVERYTHING,
+            EverythingFilter::Audio => EVERYTHING_IPC_FILTER_AUDIO,
+            EverythingFilter::Compressed => EVERYTHING_IPC_FILTER_COMPRESSED,
+            EverythingFilter::Document => EVERYTHING_IPC_FILTER_DOCUMENT,
+            EverythingFilter::Executable => EVERYTHING_IPC_FILTER_EXECUTABLE,
+            EverythingFilter::Folder => EVERYTHING_IPC_FILTER_FOLDER,
+            EverythingFilter::Picture => EVERYTHING_IPC_FILTER_PICTURE,
+            EverythingFilter::Video => EVERYTHING_IPC_FILTER_VIDEO,
+            EverythingFilter::Custom => EVERYTHING_IPC_FILTER_CUSTOM,
+        }
+    }
+}
+
+impl TryFrom<u32> for EverythingFilter {
+    type Error = &'static str;
+
+    fn try_from(filter: u32) -> Result<Self, Self::Error> {
+        let filter = match filter {
+            EVERYTHING_IPC_FILTER_EVERYTHING => EverythingFilter::Everything,
+            EVERYTHING_IPC_FILTER_AUDIO => EverythingFilter::Audio,
+            EVERYTHING_IPC_FILTER_COMPRESSED => EverythingFilter::Compressed,
+            EVERYTHING_IPC_FILTER_DOCUMENT => EverythingFilter::Document,
+            EVERYTHING_IPC_FILTER_EXECUTABLE => EverythingFilter::Executable,
+            EVERYTHING_IPC_FILTER_FOLDER => EverythingFilter::Folder,
+            EVERYTHING_IPC_FILTER_PICTURE => EverythingFilter::Picture,
+            EVERYTHING_IPC_FILTER_VIDEO => EverythingFilter::Video,
+            EVERYTHING_IPC_FILTER_CUSTOM => EverythingFilter::Custom,
+            _ => Err("Unknown filter code")?,
+        };
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_builtin_filters() {
+        for filter in EverythingFilter::ALL {
+            assert_eq!(EverythingFilter::try_from(u32::from(filter)), Ok(filter));
+            assert_eq!(
+                filter.macro_name().is_some(),
+                !filter.extensions().is_empty()
+            );
+        }
+        assert!(EverythingFilter::try_from(9).is_err());
+
+        assert_eq!(EverythingFilter::Everything.query().to_string(), "");
+        assert_eq!(EverythingFilter::Folder.query().to_string(), "folder:");
+        let pictures = EverythingFilter::Picture.query().to_string();
+        assert!(pictures.starts_with("ext:ani;bmp;gif;"));
+        assert!(pictures.ends_with(";webp;wmf"));
+    }
+}
@@ -0,0 +1,515 @@
+use super::EverythingFilter;
+use crate::query::Query;
+use crate::SearchRequest;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use ts_rs::TS;
+
+const NAME: &str = "Name";
+const CASE: &str = "Case";
+const WHOLE_WORD: &str = "Whole Word";
+const PATH: &str = "Path";
+const DIACRITICS: &str = "Diacritics";
+const REGEX: &str = "Regex";
+const SEARCH: &str = "Search";
+const MACRO: &str = "Macro";
+const KEY: &str = "Key";
+
+/// The columns Everything writes to `Filters.csv`, in order.
+const COLUMNS: [&str; 9] = [
+    NAME, CASE, WHOLE_WORD, PATH, DIACRITICS, REGEX, SEARCH, MACRO, KEY,
+];
+
+/// Errors returned when reading or writing a `Filters.csv`.
+#[derive(Debug, thiserror::Error)]
+pub enum FiltersError {
+    #[error("Unable to access the filters file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Filters are missing the {0} column")]
+    MissingColumn(&'static str),
+    #[error("Invalid {column} {value:?} on line {line} of the filters")]
+    InvalidField {
+        line: usize,
+        column: &'static str,
+        value: String,
+    },
+    #[error("Quote opened on line {0} of the filters is never closed")]
+    UnterminatedQuote(usize),
+}
+
+/// A filter as Everything stores it in `Filters.csv`: a search and the options it runs with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize, TS)]
+pub struct Filter {
+    pub name: String,
+    pub search: String,
+    pub match_case: bool,
+    pub match_whole_word: bool,
+    pub match_path: bool,
+    pub match_diacritics: bool,
+    pub regex: bool,
+    /// The name of the search macro that runs the filter, `src` for `src:`.
+    pub macro_name: Option<String>,
+    /// The keyboard shortcut selecting the filter as Everything encodes it, 0 for none.
+    pub key: u32,
+    /// Columns this crate does not know, kept so they are written back unchanged.
+    pub other: BTreeMap<String, String>,
+}
+
+impl Filter {
+    pub fn new(name: impl Into<String>, search: impl Into<String>) -> Filter {
+        Filter {
+            name: name.into(),
+            search: search.into(),
+            ..Filter::default()
+        }
+    }
+
+    /// The search of the filter with its options turned into modifiers, so it can be combined with other terms.
+    pub fn query(&self) -> Query {
+        let mut query = Query::raw(&self.search);
+        if self.regex {
+            query = query.modifier("regex");
+        }
+        if self.match_path {
+            query = query.modifier("path");
+        }
+        if self.match_whole_word {
+            query = query.whole_word();
+        }
+        if self.match_case {
+            query = query.match_case();
+        }
+        if self.match_diacritics {
+            query = query.diacritics();
+        }
+        query
+    }
+
+    /// Restricts `request` to the results of the filter, like selecting it in the filter bar.
+    /// The options of the filter only apply to its own search.
+    pub fn apply(&self, request: &mut SearchRequest) {
+        request.search = self.query().and(Query::raw(&request.search)).to_string();
+    }
+}
+
+/// The built in filter with the search and macro Everything ships with.
+impl From<EverythingFilter> for Filter {
+    fn from(filter: EverythingFilter) -> Self {
+        Filter {
+            macro_name: filter.macro_name().map(str::to_string),
+            ..Filter::new(filter.name(), filter.query().to_string())
+        }
+    }
+}
+
+/// The filters of an Everything install, read from and written to its `Filters.csv`.
+/// Custom filters can be shared as a file and applied from code without installing them.
+///
+/// Everything keeps the file next to `Everything.ini`, in `%APPDATA%\Everything` when `Admin::is_appdata` is true.
+/// It reads the file on startup, so save it while Everything is closed.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::{Filter, Filters, SearchRequest};
+///
+/// let mut filters = Filters::builtin();
+/// filters.insert(Filter {
+///     macro_name: Some("src".to_string()),
+///     ..Filter::new("Source", "ext:rs;toml")
+/// });
+///
+/// let filters = Filters::parse(&filters.to_csv()).unwrap();
+/// assert_eq!(filters.expand("src: main"), "<ext:rs;toml> main");
+///
+/// let mut request = SearchRequest::new("main");
+/// filters.get("source").unwrap().apply(&mut request);
+/// assert_eq!(request.search, "ext:rs;toml main");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Filters {
+    /// The header of the file, written back in the same order.
+    columns: Vec<String>,
+    filters: Vec<Filter>,
+}
+
+impl Filters {
+    /// An empty list with the columns Everything writes.
+    pub fn new() -> Filters {
+        Filters {
+            columns: COLUMNS.iter().map(|column| column.to_string()).collect(),
+            filters: Vec::new(),
+        }
+    }
+
+    /// The filters Everything starts out with, see `EverythingFilter`.
+    pub fn builtin() -> Filters {
+        let mut filters = Filters::new();
+        filters.filters = EverythingFilter::ALL
+            .into_iter()
+            .map(Filter::from)
+            .collect();
+        filters
+    }
+
+    /// Reads the contents of a `Filters.csv`. Columns are matched by their header,
+    /// `Name` and `Search` are required and unknown ones are kept in `Filter::other`.
+    pub fn parse(csv: &str) -> Result<Filters, FiltersError> {
+        let mut records = parse_records(csv.trim_start_matches('\u{feff}'))?.into_iter();
+        let Some((_, columns)) = records.next() else {
+            return Ok(Filters::new());
+        };
+        for required in [NAME, SEARCH] {
+            if !columns.iter().any(|column| column == required) {
+                return Err(FiltersError::MissingColumn(required));
+            }
+        }
+
+        let mut filters = Vec::new();
+        for (line, record) in records {
+            let mut filter = Filter::default();
+            for (column, value) in columns.iter().zip(record) {
+                let flag = |column: &'static str| match value.as_str() {
+                    "" | "0" => Ok(false),
+                    "1" => Ok(true),
+                    _ => Err(FiltersError::InvalidField {
+                        line,
+                        column,
+                        value: value.clone(),
+                    }),
+                };
+                match column.as_str() {
+                    NAME => filter.name = value,
+                    SEARCH => filter.search = value,
+                    CASE => filter.match_case = flag(CASE)?,
+                    WHOLE_WORD => filter.match_whole_word = flag(WHOLE_WORD)?,
+                    PATH => filter.match_path = flag(PATH)?,
+                    DIACRITICS => filter.match_diacritics = flag(DIACRITICS)?,
+                    REGEX => filter.regex = flag(REGEX)?,
+                    MACRO => filter.macro_name = Some(value).filter(|name| !name.is_empty()),
+                    KEY if value.is_empty() => filter.key = 0,
+                    KEY => {
+                        filter.key = value.parse().map_err(|_| FiltersError::InvalidField {
+                            line,
+                            column: KEY,
+                            value: value.clone(),
+                        })?
+                    }
+                    _ => {
+                        filter.other.insert(column.clone(), value);
+                    }
+                }
+            }
+            filters.push(filter);
+        }
+        Ok(Filters { columns, filters })
+    }
+
+    /// Reads a `Filters.csv` from disk.
+    pub fn load(path: impl AsRef<Path>) -> Result<Filters, FiltersError> {
+        Filters::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Writes the filters in the format Everything reads, with the columns they were read with.
+    pub fn to_csv(&self) -> String {
+        let mut csv = self.columns.join(",");
+        csv.push_str("\r\n");
+        for filter in &self.filters {
+            let fields: Vec<String> = self
+                .columns
+                .iter()
+                .map(|column| {
+                    let flag = |enabled: bool| (enabled as u8).to_string();
+                    match column.as_str() {
+                        NAME => quote(&filter.name),
+                        SEARCH => quote(&filter.search),
+                        CASE => flag(filter.match_case),
+                        WHOLE_WORD => flag(filter.match_whole_word),
+                        PATH => flag(filter.match_path),
+                        DIACRITICS => flag(filter.match_diacritics),
+                        REGEX => flag(filter.regex),
+                        MACRO => quote(filter.macro_name.as_deref().unwrap_or_default()),
+                        KEY => filter.key.to_string(),
+                        other => escape(filter.other.get(other).map_or("", String::as_str)),
+                    }
+                })
+                .collect();
+            csv.push_str(&fields.join(","));
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+
+    /// Writes the filters to a `Filters.csv` on disk, see `to_csv`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), FiltersError> {
+        fs::write(path, self.to_csv())?;
+        Ok(())
+    }
+
+    pub fn filters(&self) -> &[Filter] {
+        &self.filters
+    }
+
+    /// The filter called `name`, ignoring case like Everything does.
+    pub fn get(&self, name: &str) -> Option<&Filter> {
+        self.filters
+            .iter()
+            .find(|filter| filter.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The filter whose macro is `macro_name`, ignoring case.
+    pub fn get_macro(&self, macro_name: &str) -> Option<&Filter> {
+        self.filters.iter().find(|filter| {
+            filter
+                .macro_name
+                .as_deref()
+                .is_some_and(|name| name.eq_ignore_ascii_case(macro_name))
+        })
+    }
+
+    /// Adds `filter`, replacing and returning the one with the same name.
+    pub fn insert(&mut self, filter: Filter) -> Option<Filter> {
+        for column in filter.other.keys() {
+            if !self.columns.contains(column) {
+                self.columns.push(column.clone());
+            }
+        }
+        match self
+            .filters
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(&filter.name))
+        {
+            Some(existing) => Some(std::mem::replace(existing, filter)),
+            None => {
+                self.filters.push(filter);
+                None
+            }
+        }
+    }
+
+    /// Removes and returns the filter called `name`.
+    pub fn remove(&mut self, name: &str) -> Option<Filter> {
+        let index = self
+            .filters
+            .iter()
+            .position(|filter| filter.name.eq_ignore_ascii_case(name))?;
+        Some(self.filters.remove(index))
+    }
+
+    /// Replaces every macro of these filters in `search`, such as `src:`, with the search it stands for.
+    /// The result runs the same on an Everything that does not have the filters installed.
+    /// Macros inside quotes and macros with a parameter are left alone.
+    pub fn expand(&self, search: &str) -> String {
+        let mut expanded = String::with_capacity(search.len());
+        let mut quoted = false;
+        let mut term_start = true;
+        let mut rest = search;
+        while let Some(c) = rest.chars().next() {
+            if !quoted && term_start {
+                if let Some((filter, len)) = self.macro_at(rest) {
+                    let query = filter.query();
+                    if !query.is_empty() {
+                        expanded.push_str(&format!("<{}>", query));
+                    }
+                    rest = &rest[len..];
+                    term_start = false;
+                    continue;
+                }
+            }
+            if c == '"' {
+                quoted = !quoted;
+            }
+            term_start = !quoted && (c.is_whitespace() || matches!(c, '|' | '<' | '!'));
+            expanded.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        expanded
+    }
+
+    /// The filter whose macro starts `text` as a whole term, and the length of the macro.
+    fn macro_at(&self, text: &str) -> Option<(&Filter, usize)> {
+        self.filters.iter().find_map(|filter| {
+            let name = filter.macro_name.as_deref()?;
+            let len = name.len() + 1;
+            let term = text.get(..len)?;
+            let ends = text[len..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_whitespace() || c == '|' || c == '>');
+            (ends && term.strip_suffix(':')?.eq_ignore_ascii_case(name)).then_some((filter, len))
+        })
+    }
+}
+
+impl Default for Filters {
+    fn default() -> Self {
+        Filters::new()
+    }
+}
+
+/// Splits `csv` into records of unquoted fields, each with the line it starts on.
+fn parse_records(csv: &str) -> Result<Vec<(usize, Vec<String>)>, FiltersError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quote_line = None;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quote_line.is_some() && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quote_line.is_some() => quote_line = None,
+            '"' if field.is_empty() => quote_line = Some(line),
+            ',' if quote_line.is_none() => record.push(std::mem::take(&mut field)),
+            '\r' if quote_line.is_none() && chars.peek() == Some(&'\n') => {}
+            '\n' if quote_line.is_none() => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|field| !field.is_empty()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                record_line = line;
+            }
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if let Some(line) = quote_line {
+        return Err(FiltersError::UnterminatedQuote(line));
+    }
+    record.push(field);
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Quotes `text` only if it would not read back as a single field otherwise.
+fn escape(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        quote(text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILTERS_CSV: &str =
+        "\u{feff}Name,Case,Whole Word,Path,Diacritics,Regex,Search,Macro,Key,Columns\r\n\
+        \"EVERYTHING\",0,0,0,0,0,\"\",\"\",0,\r\n\
+        \"Logs, \"\"old\"\"\",1,0,1,0,0,\"ext:log\r\ndm:<lastyear\",\"logs\",589,\"Name,Path\"\r\n\
+        \"Builds\",0,1,0,0,1,\"^target$\",\"\",,\r\n";
+
+    #[test]
+    fn reads_and_writes_filters_csv() {
+        let filters = Filters::parse(FILTERS_CSV).unwrap();
+        assert_eq!(filters.filters().len(), 3);
+
+        let logs = filters.get("logs, \"OLD\"").unwrap();
+        assert!(logs.match_case && logs.match_path && !logs.regex);
+        assert_eq!(logs.search, "ext:log\r\ndm:<lastyear");
+        assert_eq!(logs.macro_name.as_deref(), Some("logs"));
+        assert_eq!(logs.key, 589);
+        assert_eq!(logs.other["Columns"], "Name,Path");
+        assert_eq!(filters.get_macro("LOGS"), Some(logs));
+
+        let builds = filters.get("builds").unwrap();
+        assert_eq!((builds.regex, builds.macro_name.as_deref()), (true, None));
+
+        let written = filters.to_csv();
+        assert!(written.starts_with(
+            "Name,Case,Whole Word,Path,Diacritics,Regex,Search,Macro,Key,Columns\r\n"
+        ));
+        assert_eq!(Filters::parse(&written).unwrap(), filters);
+
+        let path =
+            std::env::temp_dir().join(format!("everything-rs-filters-{}.csv", std::process::id()));
+        filters.save(&path).unwrap();
+        assert_eq!(Filters::load(&path).unwrap(), filters);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn matches_the_builtin_filters_of_everything() {
+        // As written by Everything 1.4.1.
+        let line = "\"EXECUTABLE\",0,0,0,0,0,\"ext:bat;cmd;exe;msi;msp;scr\",\"exe\",0";
+        let filters = Filters::parse(&format!(
+            "Name,Case,Whole Word,Path,Diacritics,Regex,Search,Macro,Key\r\n{line}\r\n"
+        ))
+        .unwrap();
+        let executable = Filter::from(EverythingFilter::Executable);
+        assert_eq!(Filters::builtin().filters()[4], executable);
+        assert_eq!(filters.filters(), [executable]);
+        assert!(filters.to_csv().contains(line));
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(matches!(
+            Filters::parse("Name,Macro\r\n\"a\",\"b\"\r\n"),
+            Err(FiltersError::MissingColumn(SEARCH))
+        ));
+        assert!(matches!(
+            Filters::parse("Name,Search,Case\r\na,b,0\r\nc,d,yes\r\n"),
+            Err(FiltersError::InvalidField {
+                line: 3,
+                column: CASE,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Filters::parse("Name,Search\r\n\"a,b\r\n"),
+            Err(FiltersError::UnterminatedQuote(2))
+        ));
+        assert!(matches!(
+            Filters::load(
+                std::env::temp_dir()
+                    .join("everything-rs-missing")
+                    .join("Filters.csv")
+            ),
+            Err(FiltersError::Io(_))
+        ));
+        assert_eq!(Filters::parse("").unwrap(), Filters::new());
+    }
+
+    #[test]
+    fn expands_macros() {
+        let mut filters = Filters::builtin();
+        filters.insert(Filter {
+            match_case: true,
+            macro_name: Some("src".to_string()),
+            ..Filter::new("Source", "ext:rs;toml | Cargo.lock")
+        });
+        assert_eq!(
+            filters.expand("SRC: !exe: \"src: \" src:main srcs: <pic:|src:>"),
+            "<case:<ext:rs;toml | Cargo.lock>> !<ext:bat;cmd;exe;msi;msp;scr> \"src: \" src:main srcs: <<ext:ani;bmp;gif;ico;jpe;jpeg;jpg;pcx;png;psd;tga;tif;tiff;webp;wmf>|<case:<ext:rs;toml | Cargo.lock>>>"
+        );
+
+        let replaced = filters.insert(Filter::new("source", "*.rs")).unwrap();
+        assert_eq!(replaced.macro_name.as_deref(), Some("src"));
+        assert_eq!(filters.expand("src:"), "src:");
+        assert!(filters.remove("SOURCE").is_some());
+        assert_eq!(filters.filters().len(), EverythingFilter::ALL.len());
+
+        let mut request = SearchRequest::new("a|b");
+        Filter::from(EverythingFilter::Folder).apply(&mut request);
+        assert_eq!(request.search, "folder: a|b");
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+use ts_rs::TS;
+
+/// Starts a highlighted run in a terminal, bold yellow.
+const ANSI_START: &str = "\x1b[1;33m";
+/// Ends a highlighted run, resetting only the intensity and the color.
+const ANSI_END: &str = "\x1b[22;39m";
+
+/// A highlighted result field with the text matched by the search, returned by `Everything::get_result_highlighted_file_name` and friends.
+///
+/// Everything marks a matched run as `*text*` and writes a literal `*` as `**`.
+/// See <https://www.voidtools.com/support/everything/sdk/everything_getresulthighlightedfilename/>
+///
+/// # Example
+/// ```rust
+/// use everything_rs::Highlighted;
+///
+/// let name = Highlighted::parse("*tes*t<1>.txt");
+/// assert_eq!(name.text, "test<1>.txt");
+/// assert_eq!(name.spans[0], 0..3);
+/// assert_eq!(name.to_html(), "<mark>tes</mark>t&lt;1&gt;.txt");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize, TS)]
+pub struct Highlighted {
+    /// The text without markup.
+    pub text: String,
+    /// The byte ranges of `text` that matched, in order and not overlapping.
+    /// Spans that are out of range, split a character or start before the previous one ends are skipped when rendering.
+    pub spans: Vec<Range<usize>>,
+}
+
+impl Highlighted {
+    /// Parses Everything's highlight markup.
+    /// A run left open at the end is highlighted up to the end of the text.
+    pub fn parse(markup: &str) -> Highlighted {
+        let mut text = String::with_capacity(markup.len());
+        let mut spans = Vec::new();
+        let mut start = None;
+        let mut chars = markup.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '*' {
+                text.push(c);
+            } else if chars.next_if_eq(&'*').is_some() {
+                text.push('*');
+            } else if let Some(start) = start.take() {
+                if start < text.len() {
+                    spans.push(start..text.len());
+                }
+            } else {
+                start = Some(text.len());
+            }
+        }
+        if let Some(start) = start.filter(|&start| start < text.len()) {
+            spans.push(start..text.len());
+        }
+        Highlighted { text, spans }
+    }
+
+    /// Writes the text back as Everything's highlight markup.
+    pub fn to_markup(&self) -> String {
+        self.render(|text| text.replace('*', "**"), "*", "*")
+    }
+
+    /// The text with the highlighted runs in bold yellow, for printing to a terminal.
+    pub fn to_ansi(&self) -> String {
+        self.render(str::to_string, ANSI_START, ANSI_END)
+    }
+
+    /// The text escaped for HTML with the highlighted runs in `<mark>` elements.
+    pub fn to_html(&self) -> String {
+        self.render(escape_html, "<mark>", "</mark>")
+    }
+
+    /// The highlighted parts of the text.
+    pub fn matches(&self) -> impl Iterator<Item = &str> {
+        self.valid_spans().map(|span| &self.text[span])
+    }
+
+    /// The spans that can be cut out of the text, see `spans`.
+    fn valid_spans(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let mut end = 0;
+        self.spans
+            .iter()
+            .filter(move |span| {
+                let valid = end <= span.start && self.text.get((*span).clone()).is_some();
+                if valid {
+                    end = span.end;
+                }
+                valid
+            })
+            .cloned()
+    }
+
+    /// Writes the text through `escape`, surrounding each highlighted run with `start` and `end`.
+    fn render(&self, escape: impl Fn(&str) -> String, start: &str, end: &str) -> String {
+        let mut rendered = String::with_capacity(self.text.len());
+        let mut written = 0;
+        for span in self.valid_spans() {
+            rendered.push_str(&escape(&self.text[written..span.start]));
+            rendered.push_str(start);
+            rendered.push_str(&escape(&self.text[span.clone()]));
+            rendered.push_str(end);
+            written = span.end;
+        }
+        rendered.push_str(&escape(&self.text[written..]));
+        rendered
+    }
+}
+
+/// Displays the text without highlighting.
+impl fmt::Display for Highlighted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_markup() {
+        let name = Highlighted::parse("run***me*.exe");
+        assert_eq!(name.text, "run*me.exe");
+        assert_eq!(name.spans.first(), Some(&(4..6)));
+        assert_eq!(name.to_markup(), "run***me*.exe");
+
+        let path = Highlighted::parse("C:\\*Ü*ber\\*ü**b*er");
+        assert_eq!(path.text, "C:\\Über\\ü*ber");
+        assert_eq!(path.matches().collect::<Vec<_>>(), ["Ü", "ü*b"]);
+        assert_eq!(Highlighted::parse(&path.to_markup()), path);
+
+        // Empty and unterminated runs.
+        assert_eq!(Highlighted::parse("a**").text, "a*");
+        assert_eq!(Highlighted::parse("**").spans, []);
+        assert_eq!(
+            Highlighted::parse("a*b").matches().collect::<Vec<_>>(),
+            ["b"]
+        );
+        assert_eq!(Highlighted::parse("a*").spans, []);
+        assert_eq!(Highlighted::parse(""), Highlighted::default());
+    }
+
+    #[test]
+    fn renders() {
+        let name = Highlighted::parse("*a*<&>*b*");
+        assert_eq!(name.to_string(), "a<&>b");
+        assert_eq!(name.to_html(), "<mark>a</mark>&lt;&amp;&gt;<mark>b</mark>");
+        assert_eq!(
+            name.to_ansi(),
+            "\x1b[1;33ma\x1b[22;39m<&>\x1b[1;33mb\x1b[22;39m"
+        );
+        assert_eq!(
+            serde_json::to_value(&name).unwrap(),
+            serde_json::json!({
+                "text": "a<&>b",
+                "spans": [{ "start": 0, "end": 1 }, { "start": 4, "end": 5 }],
+            })
+        );
+    }
+
+    #[test]
+    fn skips_invalid_spans() {
+        let name: Highlighted = serde_json::from_value(serde_json::json!({
+            "text": "aÜb.txt",
+            "spans": [
+                { "start": 0, "end": 1 },
+                { "start": 0, "end": 3 },
+                { "start": 2, "end": 3 },
+                { "start": 4, "end": 3 },
+                { "start": 5, "end": 99 },
+                { "start": 3, "end": 4 },
+            ],
+        }))
+        .unwrap();
+        assert_eq!(name.matches().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(name.to_markup(), "*a*Ü*b*.txt");
+        assert_eq!(name.to_html(), "<mark>a</mark>Ü<mark>b</mark>.txt");
+    }
+}
@@ -0,0 +1,208 @@
+//! # IPC
+//! Safe encoders and decoders for the messages the Everything service exchanges over `WM_COPYDATA`.
+//! The layouts are documented in `Everything-SDK/ipc/everything_ipc.h`.
+//! All structs are packed and little endian, text is UTF-16 (the `W` variants of the messages).
+//!
+//! The codec does not touch any windows API so it can be used and tested on any platform.
+//!
+//! `IpcClient` is a `SearchBackend` built on the codec that talks to Everything without the SDK dll.
+//! Like the SDK it sends the version 1 `Query1` when only the name and path sorted by name are requested,
+//! and `Query2` otherwise, falling back to `Query1` when Everything is too old to answer it.
+//! Messages are delivered by an `IpcTransport`: `WindowsTransport` sends real window messages,
+//! `LoopbackTransport` answers them in process from an `InMemoryBackend`.
+//! Everything 1.5 can run several named instances, each with its own window class, see `window_class`.
+//! A transport talks to one instance and lists the running ones with `IpcTransport::running_instances`.
+//!
+//! `SearchWindow` reads the options of an open search window and sends it `CommandId`s.
+//! Its messages go through a `SearchWindowTransport`, `RecordedTransport` answers them from canned replies.
+//! Only the windows transport uses unsafe code.
+
+#![deny(unsafe_code)]
+
+mod client;
+mod command_id;
+pub(crate) mod instance;
+mod query1;
+mod query2;
+mod run_history;
+mod search_window;
+mod transport;
+#[cfg(target_os = "windows")]
+mod windows;
+mod wire;
+
+pub use client::IpcClient;
+pub use command_id::*;
+pub use instance::window_class;
+pub use query1::{needs_query2, Item1, List1, Query1};
+pub use query2::{Item2, List2, Query2};
+pub use run_history::{decode_file_name, encode_file_name, RunHistory};
+pub use search_window::{
+    OnTop, RecordedTransport, SearchWindow, SearchWindowState, SearchWindowTransport,
+};
+pub use transport::{IpcTransport, LoopbackDesktop, LoopbackTransport};
+#[cfg(target_os = "windows")]
+pub use windows::WindowsTransport;
+
+use bitflags::bitflags;
+
+/// `dwData` of the `COPYDATASTRUCT` carrying a unicode `EVERYTHING_IPC_QUERY`, the version 1 query.
+pub const EVERYTHING_IPC_COPYDATAQUERYW: u32 = 2;
+/// `dwData` of the `COPYDATASTRUCT` carrying a unicode `EVERYTHING_IPC_QUERY2`.
+/// Requires Everything 1.4.1.
+pub const EVERYTHING_IPC_COPYDATA_QUERY2W: u32 = 18;
+/// `dwData` of a `COPYDATASTRUCT` carrying a file name, answered with its run count.
+pub const EVERYTHING_IPC_COPYDATA_GET_RUN_COUNTW: u32 = 20;
+/// `dwData` of a `COPYDATASTRUCT` carrying a `RunHistory`, answered with non zero on success.
+pub const EVERYTHING_IPC_COPYDATA_SET_RUN_COUNTW: u32 = 22;
+/// `dwData` of a `COPYDATASTRUCT` carrying a file name, answered with its new run count.
+pub const EVERYTHING_IPC_COPYDATA_INC_RUN_COUNTW: u32 = 24;
+
+/// Class name of the Everything taskbar notification window that receives IPC messages.
+pub const EVERYTHING_IPC_WNDCLASS: &str = "EVERYTHING_TASKBAR_NOTIFICATION";
+
+/// Class name of an Everything search window, which answers the search window state queries and `WM_COMMAND`.
+pub const EVERYTHING_IPC_SEARCH_CLIENT_WNDCLASS: &str = "EVERYTHING";
+
+/// The message sent to `EVERYTHING_IPC_WNDCLASS` with an `EVERYTHING_IPC_*` command as the wParam.
+pub const EVERYTHING_WM_IPC: u32 = 0x0400;
+
+pub const EVERYTHING_IPC_GET_MAJOR_VERSION: u32 = 0;
+pub const EVERYTHING_IPC_GET_MINOR_VERSION: u32 = 1;
+pub const EVERYTHING_IPC_GET_REVISION: u32 = 2;
+pub const EVERYTHING_IPC_GET_BUILD_NUMBER: u32 = 3;
+/// Closes Everything, returns non zero if it closes.
+pub const EVERYTHING_IPC_EXIT: u32 = 4;
+/// Returns one of the `EVERYTHING_IPC_TARGET_MACHINE_*` values, 0 if not supported.
+/// Requires Everything 1.4.1.
+pub const EVERYTHING_IPC_GET_TARGET_MACHINE: u32 = 5;
+/// Returns non zero if the NTFS drive passed as the lParam is indexed, 0 for `A:`, 1 for `B:` and so on.
+pub const EVERYTHING_IPC_IS_NTFS_DRIVE_INDEXED: u32 = 400;
+/// Returns non zero once the database has been loaded.
+pub const EVERYTHING_IPC_IS_DB_LOADED: u32 = 401;
+/// Returns non zero while the database is busy. Issuing another action cancels the current one if possible.
+pub const EVERYTHING_IPC_IS_DB_BUSY: u32 = 402;
+pub const EVERYTHING_IPC_IS_ADMIN: u32 = 403;
+/// Returns non zero if Everything stores its settings and data in `%APPDATA%`.
+pub const EVERYTHING_IPC_IS_APPDATA: u32 = 404;
+/// Rescans every index.
+pub const EVERYTHING_IPC_REBUILD_DB: u32 = 405;
+/// Rescans every folder index.
+pub const EVERYTHING_IPC_UPDATE_ALL_FOLDER_INDEXES: u32 = 406;
+/// Saves the database to disk.
+pub const EVERYTHING_IPC_SAVE_DB: u32 = 407;
+/// Saves the run history to disk.
+pub const EVERYTHING_IPC_SAVE_RUN_HISTORY: u32 = 408;
+/// Deletes the run history from memory and disk.
+pub const EVERYTHING_IPC_DELETE_RUN_HISTORY: u32 = 409;
+/// Returns non zero if the sort passed as the lParam is indexed.
+pub const EVERYTHING_IPC_IS_FAST_SORT: u32 = 410;
+/// Returns non zero if the `EVERYTHING_IPC_FILE_INFO_*` value passed as the lParam is indexed.
+pub const EVERYTHING_IPC_IS_FILE_INFO_INDEXED: u32 = 411;
+/// Rescans every index once the database is ready.
+pub const EVERYTHING_IPC_QUEUE_REBUILD_DB: u32 = 412;
+
+pub const EVERYTHING_IPC_TARGET_MACHINE_X86: u32 = 1;
+pub const EVERYTHING_IPC_TARGET_MACHINE_X64: u32 = 2;
+pub const EVERYTHING_IPC_TARGET_MACHINE_ARM: u32 = 3;
+pub const EVERYTHING_IPC_TARGET_MACHINE_ARM64: u32 = 4;
+
+// Sent to a search window, requires Everything 1.4.1.
+pub const EVERYTHING_IPC_IS_MATCH_CASE: u32 = 500;
+pub const EVERYTHING_IPC_IS_MATCH_WHOLE_WORD: u32 = 501;
+pub const EVERYTHING_IPC_IS_MATCH_PATH: u32 = 502;
+pub const EVERYTHING_IPC_IS_MATCH_DIACRITICS: u32 = 503;
+pub const EVERYTHING_IPC_IS_REGEX: u32 = 504;
+pub const EVERYTHING_IPC_IS_FILTERS: u32 = 505;
+pub const EVERYTHING_IPC_IS_PREVIEW: u32 = 506;
+pub const EVERYTHING_IPC_IS_STATUS_BAR: u32 = 507;
+pub const EVERYTHING_IPC_IS_DETAILS: u32 = 508;
+/// Returns the thumbnail size in pixels, 0 for the details view.
+pub const EVERYTHING_IPC_GET_THUMBNAIL_SIZE: u32 = 509;
+/// Returns one of the `EVERYTHING_SORT_*` values.
+pub const EVERYTHING_IPC_GET_SORT: u32 = 510;
+/// Returns 0 for never, 1 for always and 2 for while searching.
+pub const EVERYTHING_IPC_GET_ON_TOP: u32 = 511;
+/// Returns one of the `EVERYTHING_IPC_FILTER_*` values.
+pub const EVERYTHING_IPC_GET_FILTER: u32 = 512;
+/// Returns the index of the selected filter in the filters menu.
+pub const EVERYTHING_IPC_GET_FILTER_INDEX: u32 = 513;
+/// Everything 1.5
+pub const EVERYTHING_IPC_IS_MATCH_PREFIX: u32 = 514;
+/// Everything 1.5
+pub const EVERYTHING_IPC_IS_MATCH_SUFFIX: u32 = 515;
+/// Everything 1.5
+pub const EVERYTHING_IPC_IS_IGNORE_PUNCTUATION: u32 = 516;
+/// Everything 1.5
+pub const EVERYTHING_IPC_IS_IGNORE_WHITESPACE: u32 = 517;
+/// Everything 1.5
+pub const EVERYTHING_IPC_IS_SEARCH_AS_YOU_TYPE: u32 = 518;
+
+pub const EVERYTHING_IPC_FILTER_EVERYTHING: u32 = 0;
+pub const EVERYTHING_IPC_FILTER_AUDIO: u32 = 1;
+pub const EVERYTHING_IPC_FILTER_COMPRESSED: u32 = 2;
+pub const EVERYTHING_IPC_FILTER_DOCUMENT: u32 = 3;
+pub const EVERYTHING_IPC_FILTER_EXECUTABLE: u32 = 4;
+pub const EVERYTHING_IPC_FILTER_FOLDER: u32 = 5;
+pub const EVERYTHING_IPC_FILTER_PICTURE: u32 = 6;
+pub const EVERYTHING_IPC_FILTER_VIDEO: u32 = 7;
+/// A filter added by the user.
+pub const EVERYTHING_IPC_FILTER_CUSTOM: u32 = 8;
+
+pub const EVERYTHING_IPC_FILE_INFO_FILE_SIZE: u32 = 1;
+pub const EVERYTHING_IPC_FILE_INFO_FOLDER_SIZE: u32 = 2;
+pub const EVERYTHING_IPC_FILE_INFO_DATE_CREATED: u32 = 3;
+pub const EVERYTHING_IPC_FILE_INFO_DATE_MODIFIED: u32 = 4;
+pub const EVERYTHING_IPC_FILE_INFO_DATE_ACCESSED: u32 = 5;
+pub const EVERYTHING_IPC_FILE_INFO_ATTRIBUTES: u32 = 6;
+
+/// Passed as `max_results` to request every result.
+pub const EVERYTHING_IPC_ALLRESULTS: u32 = 0xFFFFFFFF;
+
+bitflags! {
+    /// The `search_flags` member of a query.
+    /// See `EVERYTHING_IPC_MATCHCASE` and friends in `everything_ipc.h`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct IpcSearchFlags: u32 {
+        const MatchCase = 0x00000001;
+        const MatchWholeWord = 0x00000002;
+        const MatchPath = 0x00000004;
+        const Regex = 0x00000008;
+        const MatchDiacritics = 0x00000010;
+        /// Everything 1.5
+        const MatchPrefix = 0x00000020;
+        /// Everything 1.5
+        const MatchSuffix = 0x00000040;
+        /// Everything 1.5
+        const IgnorePunctuation = 0x00000080;
+        /// Everything 1.5
+        const IgnoreWhitespace = 0x00000100;
+    }
+}
+
+bitflags! {
+    /// The `flags` member of a result item.
+    /// An item without `Folder` is a file, an item with both `Folder` and `Root` is a volume.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct IpcItemFlags: u32 {
+        const Folder = 0x00000001;
+        const Root = 0x00000002;
+    }
+}
+
+/// Errors returned when decoding malformed IPC data.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IpcCodecError {
+    #[error("IPC data truncated: needed {needed} bytes at offset {offset}")]
+    Truncated { offset: usize, needed: usize },
+    #[error("IPC data offset {0} is out of bounds")]
+    InvalidOffset(u32),
+    #[error("IPC string at offset {0} is not null terminated")]
+    MissingTerminator(usize),
+    #[error("Unknown sort type {0}")]
+    UnknownSort(u32),
+    #[error("Unknown request flags {0:#x}")]
+    UnknownRequestFlags(u32),
+    #[error("Unknown reply {reply} to search window state {state}")]
+    UnknownReply { state: u32, reply: u32 },
+}
@@ -0,0 +1,1144 @@
+use super::{
+    encode_file_name, needs_query2, IpcItemFlags, IpcSearchFlags, IpcTransport, Item2, List1,
+    List2, Query1, Query2, RunHistory, EVERYTHING_IPC_COPYDATAQUERYW,
+    EVERYTHING_IPC_COPYDATA_GET_RUN_COUNTW, EVERYTHING_IPC_COPYDATA_INC_RUN_COUNTW,
+    EVERYTHING_IPC_COPYDATA_QUERY2W, EVERYTHING_IPC_COPYDATA_SET_RUN_COUNTW,
+    EVERYTHING_IPC_GET_BUILD_NUMBER, EVERYTHING_IPC_GET_MAJOR_VERSION,
+    EVERYTHING_IPC_GET_MINOR_VERSION, EVERYTHING_IPC_GET_REVISION, EVERYTHING_IPC_IS_DB_LOADED,
+    EVERYTHING_IPC_IS_FAST_SORT,
+};
+use crate::backend::{check_file_name, check_search};
+use crate::sync::lock;
+use crate::{
+    EverythingError, EverythingRequestFlags, EverythingResult, EverythingSDKError, EverythingSort,
+    FileAttributes, FileTime, QueryLimits, SearchBackend,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Debug)]
+struct State {
+    search: String,
+    search_flags: IpcSearchFlags,
+    sort: EverythingSort,
+    max_results: u32,
+    offset: u32,
+    request_flags: EverythingRequestFlags,
+    last_error: EverythingSDKError,
+    /// The reply of the last successful query, a version 1 reply is converted.
+    list: Option<List2>,
+    /// The `totfolders` and `totfiles` of the last reply, only version 1 replies carry them.
+    totals: Option<(u32, u32)>,
+}
+
+impl State {
+    /// The same defaults as the SDK.
+    fn new() -> State {
+        let query = Query2::default();
+        State {
+            search: query.search,
+            search_flags: query.search_flags,
+            sort: query.sort,
+            max_results: query.max_results,
+            offset: query.offset,
+            request_flags: query.request_flags,
+            last_error: EverythingSDKError::Ok,
+            list: None,
+            totals: None,
+        }
+    }
+
+    /// Records the outcome of a call so `get_last_error` can report it.
+    /// A query given up on by its limits leaves the error of the previous call, Everything did nothing wrong.
+    fn record<R>(&mut self, result: EverythingResult<R>) -> EverythingResult<R> {
+        self.last_error = match &result {
+            Ok(_) => EverythingSDKError::Ok,
+            Err(EverythingError::SDKError(err)) => *err,
+            Err(EverythingError::Cancelled | EverythingError::QueryTimeout) => self.last_error,
+            // Everything answered but the reply could not be understood.
+            Err(_) => EverythingSDKError::Ipc,
+        };
+        result
+    }
+
+    /// Looks up a result, failing like the SDK when there is no result list or the index is out of range.
+    fn item(&mut self, index: u32) -> EverythingResult<&Item2> {
+        let item = match &self.list {
+            None => Err(EverythingSDKError::InvalidCall),
+            Some(list) => list
+                .items
+                .get(index as usize)
+                .ok_or(EverythingSDKError::InvalidIndex),
+        };
+        self.last_error = item.err().unwrap_or(EverythingSDKError::Ok);
+        item.map_err(EverythingError::SDKError)
+    }
+
+    /// Reads a field of a result, failing with `InvalidRequest` if the field was not part of the reply.
+    fn field<R>(
+        &mut self,
+        index: u32,
+        read: impl FnOnce(&Item2) -> Option<R>,
+    ) -> EverythingResult<R> {
+        let value = read(self.item(index)?);
+        self.record(value.ok_or(EverythingError::SDKError(
+            EverythingSDKError::InvalidRequest,
+        )))
+    }
+}
+
+/// A `SearchBackend` that speaks the Everything IPC protocol itself instead of going through the SDK dll.
+/// Queries are sent as `EVERYTHING_IPC_QUERY2` messages and the `EVERYTHING_IPC_LIST2` reply is kept until the next query.
+/// Like the SDK, a query for just the name and path sorted by name is sent as the version 1 `EVERYTHING_IPC_QUERY`,
+/// whose reply also counts the folders and files found, see `needs_query2`.
+/// Everything before 1.4.1 only answers version 1, the query is sent again as version 1 when it rejects `EVERYTHING_IPC_QUERY2`.
+///
+/// The messages are delivered by `T`, use `WindowsTransport` to talk to a running Everything instance.
+/// Every query gets a new reply id so a reply to an earlier query is never mistaken for the current one.
+#[derive(Debug)]
+pub struct IpcClient<T: IpcTransport> {
+    transport: T,
+    state: Mutex<State>,
+    /// The `dwData` of the last reply that was asked for.
+    reply_id: AtomicU32,
+}
+
+impl<T: IpcTransport> IpcClient<T> {
+    pub fn new(transport: T) -> IpcClient<T> {
+        IpcClient {
+            transport,
+            state: Mutex::new(State::new()),
+            reply_id: AtomicU32::new(0),
+        }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+
+    fn next_reply_id(&self) -> u32 {
+        self.reply_id.fetch_add(1, Ordering::SeqCst).wrapping_add(1)
+    }
+
+    /// Sends the search in `state` as an `EVERYTHING_IPC_QUERY2` and decodes the reply.
+    fn query2(&self, state: &State, limits: &QueryLimits) -> EverythingResult<List2> {
+        let reply_id = self.next_reply_id();
+        let query = Query2 {
+            reply_hwnd: 0,
+            reply_copydata_message: reply_id,
+            search_flags: state.search_flags,
+            offset: state.offset,
+            max_results: state.max_results,
+            request_flags: state.request_flags,
+            sort: state.sort,
+            search: state.search.clone(),
+        };
+        let encode = |reply_hwnd| {
+            Query2 {
+                reply_hwnd,
+                ..query.clone()
+            }
+            .encode()
+        };
+
+        let reply = self.transport.send_query(
+            EVERYTHING_IPC_COPYDATA_QUERY2W,
+            reply_id,
+            &encode,
+            limits,
+        )?;
+        Ok(List2::decode(&reply)?)
+    }
+
+    /// Sends the search in `state` as a version 1 `EVERYTHING_IPC_QUERY` and decodes the reply.
+    fn query1(&self, state: &State, limits: &QueryLimits) -> EverythingResult<List1> {
+        let reply_id = self.next_reply_id();
+        let query = Query1 {
+            reply_hwnd: 0,
+            reply_copydata_message: reply_id,
+            search_flags: state.search_flags,
+            offset: state.offset,
+            max_results: state.max_results,
+            search: state.search.clone(),
+        };
+        let encode = |reply_hwnd| {
+            Query1 {
+                reply_hwnd,
+                ..query.clone()
+            }
+            .encode()
+        };
+
+        let reply =
+            self.transport
+                .send_query(EVERYTHING_IPC_COPYDATAQUERYW, reply_id, &encode, limits)?;
+        Ok(List1::decode(&reply)?)
+    }
+
+    /// Counts the results of the current list that `count` selects, failing like the SDK when there is no list.
+    fn count_items(&self, count: impl Fn(&Item2) -> bool) -> u32 {
+        let mut state = self.state();
+        let counted = state
+            .list
+            .as_ref()
+            .map(|list| list.items.iter().filter(|item| count(item)).count() as u32);
+        state
+            .record(counted.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .unwrap_or(0)
+    }
+}
+
+/// Fails like the SDK when Everything answers a run history message with 0.
+fn carried_out(reply: u32) -> EverythingResult<u32> {
+    match reply {
+        0 => Err(EverythingError::SDKError(EverythingSDKError::InvalidCall)),
+        reply => Ok(reply),
+    }
+}
+
+/// The path and name of an item lower cased for sorting, from either the separate fields or the full path.
+fn sort_key(item: &Item2) -> Option<(String, String)> {
+    let (path, name) = match (&item.path, &item.name, &item.full_path) {
+        (Some(path), Some(name), _) => (path.as_str(), name.as_str()),
+        (_, _, Some(full_path)) => full_path.rsplit_once('\\').unwrap_or(("", full_path)),
+        _ => return None,
+    };
+    Some((path.to_lowercase(), name.to_lowercase()))
+}
+
+impl<T: IpcTransport> SearchBackend for IpcClient<T> {
+    fn get_last_error(&self) -> EverythingResult<()> {
+        match self.state().last_error {
+            EverythingSDKError::Ok => Ok(()),
+            err => Err(EverythingError::SDKError(err)),
+        }
+    }
+
+    fn is_db_loaded(&self) -> bool {
+        self.send_command(EVERYTHING_IPC_IS_DB_LOADED, 0)
+            .is_ok_and(|loaded| loaded != 0)
+    }
+
+    fn version(&self) -> (u32, u32, u32, u32) {
+        let part = |command| self.send_command(command, 0).unwrap_or(0);
+        (
+            part(EVERYTHING_IPC_GET_MAJOR_VERSION),
+            part(EVERYTHING_IPC_GET_MINOR_VERSION),
+            part(EVERYTHING_IPC_GET_REVISION),
+            part(EVERYTHING_IPC_GET_BUILD_NUMBER),
+        )
+    }
+
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        let result = self.transport.send_command(command, param);
+        self.state().record(result)
+    }
+
+    fn get_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        let result = check_file_name(file_name).and_then(|_| {
+            self.transport.send_copydata(
+                EVERYTHING_IPC_COPYDATA_GET_RUN_COUNTW,
+                &encode_file_name(file_name),
+            )
+        });
+        self.state().record(result)
+    }
+
+    fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()> {
+        let history = RunHistory {
+            run_count,
+            file_name: file_name.to_string(),
+        };
+        let result = check_file_name(file_name)
+            .and_then(|_| {
+                self.transport
+                    .send_copydata(EVERYTHING_IPC_COPYDATA_SET_RUN_COUNTW, &history.encode())
+            })
+            .and_then(carried_out);
+        self.state().record(result.map(|_| ()))
+    }
+
+    fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        let result = check_file_name(file_name)
+            .and_then(|_| {
+                self.transport.send_copydata(
+                    EVERYTHING_IPC_COPYDATA_INC_RUN_COUNTW,
+                    &encode_file_name(file_name),
+                )
+            })
+            .and_then(carried_out);
+        self.state().record(result)
+    }
+
+    fn set_search(&self, search: &str) {
+        self.state().search = search.to_string();
+    }
+
+    fn get_search(&self) -> EverythingResult<String> {
+        Ok(self.state().search.clone())
+    }
+
+    fn set_sort(&self, sort: EverythingSort) {
+        self.state().sort = sort;
+    }
+
+    fn get_sort(&self) -> Option<EverythingSort> {
+        Some(self.state().sort)
+    }
+
+    fn is_fast_sort(&self, sort: EverythingSort) -> bool {
+        self.send_command(EVERYTHING_IPC_IS_FAST_SORT, sort.into())
+            .is_ok_and(|fast| fast != 0)
+    }
+
+    fn set_match_case(&self, enable: bool) {
+        self.state()
+            .search_flags
+            .set(IpcSearchFlags::MatchCase, enable);
+    }
+
+    fn get_match_case(&self) -> bool {
+        self.state()
+            .search_flags
+            .contains(IpcSearchFlags::MatchCase)
+    }
+
+    fn set_match_path(&self, enable: bool) {
+        self.state()
+            .search_flags
+            .set(IpcSearchFlags::MatchPath, enable);
+    }
+
+    fn get_match_path(&self) -> bool {
+        self.state()
+            .search_flags
+            .contains(IpcSearchFlags::MatchPath)
+    }
+
+    fn set_match_whole_word(&self, enable: bool) {
+        self.state()
+            .search_flags
+            .set(IpcSearchFlags::MatchWholeWord, enable);
+    }
+
+    fn get_match_whole_word(&self) -> bool {
+        self.state()
+            .search_flags
+            .contains(IpcSearchFlags::MatchWholeWord)
+    }
+
+    fn set_regex(&self, enable: bool) {
+        self.state().search_flags.set(IpcSearchFlags::Regex, enable);
+    }
+
+    fn get_regex(&self) -> bool {
+        self.state().search_flags.contains(IpcSearchFlags::Regex)
+    }
+
+    fn set_max_results(&self, max_results: u32) {
+        self.state().max_results = max_results;
+    }
+
+    fn get_max_results(&self) -> u32 {
+        self.state().max_results
+    }
+
+    fn set_result_offset(&self, offset: u32) {
+        self.state().offset = offset;
+    }
+
+    fn get_result_offset(&self) -> u32 {
+        self.state().offset
+    }
+
+    fn set_request_flags(&self, request_flags: EverythingRequestFlags) {
+        self.state().request_flags = request_flags;
+    }
+
+    fn get_request_flags(&self) -> EverythingRequestFlags {
+        self.state().request_flags
+    }
+
+    fn get_result_list_request_flags(&self) -> EverythingRequestFlags {
+        let mut state = self.state();
+        let flags = state.list.as_ref().map(|list| list.request_flags);
+        state
+            .record(flags.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .unwrap_or(EverythingRequestFlags::empty())
+    }
+
+    fn query_with(&self, limits: &QueryLimits) -> EverythingResult<()> {
+        let mut state = self.state();
+        state.list = None;
+        state.totals = None;
+        // Nothing is sent for a query that was given up on already.
+        state.record(limits.check())?;
+        let search = check_search(&state.search);
+        state.record(search)?;
+
+        let query2 = needs_query2(state.request_flags, state.sort)
+            .then(|| self.query2(&state, limits).map(|list| (list, None)));
+        let reply = match query2 {
+            // Everything before 1.4.1 rejects QUERY2 but still answers version 1 queries.
+            None | Some(Err(EverythingError::CommandRejected(EVERYTHING_IPC_COPYDATA_QUERY2W))) => {
+                self.query1(&state, limits).map(|list| {
+                    let totals = (list.total_folders, list.total_files);
+                    (List2::from(list), Some(totals))
+                })
+            }
+            Some(reply) => reply,
+        };
+        // The SDK reports a rejected query like a missing Everything.
+        let reply = reply.map_err(|err| match err {
+            EverythingError::CommandRejected(_) => {
+                EverythingError::SDKError(EverythingSDKError::Ipc)
+            }
+            err => err,
+        });
+        let (list, totals) = state.record(reply)?;
+        state.list = Some(list);
+        state.totals = totals;
+        Ok(())
+    }
+
+    fn reset(&self) {
+        *self.state() = State::new();
+    }
+
+    fn get_num_results(&self) -> u32 {
+        let mut state = self.state();
+        let count = state.list.as_ref().map(|list| list.items.len() as u32);
+        state
+            .record(count.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .unwrap_or(0)
+    }
+
+    fn get_total_results(&self) -> u32 {
+        let mut state = self.state();
+        let total = state.list.as_ref().map(|list| list.total_items);
+        state
+            .record(total.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .unwrap_or(0)
+    }
+
+    fn get_num_file_results(&self) -> u32 {
+        self.count_items(|item| !item.flags.contains(IpcItemFlags::Folder))
+    }
+
+    fn get_num_folder_results(&self) -> u32 {
+        self.count_items(|item| item.flags.contains(IpcItemFlags::Folder))
+    }
+
+    /// Only version 1 replies carry the totals, after an `EVERYTHING_IPC_QUERY2` like the SDK this fails with `InvalidCall`.
+    fn get_total_file_results(&self) -> u32 {
+        let mut state = self.state();
+        let total = state.totals.map(|(_, files)| files);
+        state
+            .record(total.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .unwrap_or(0)
+    }
+
+    /// See `get_total_file_results`.
+    fn get_total_folder_results(&self) -> u32 {
+        let mut state = self.state();
+        let total = state.totals.map(|(folders, _)| folders);
+        state
+            .record(total.ok_or(EverythingError::SDKError(EverythingSDKError::InvalidCall)))
+            .unwrap_or(0)
+    }
+
+    /// The SDK leaves `EVERYTHING_IPC_LIST2` results unsorted, this sorts them whenever their path is known.
+    /// Results without a path keep their place relative to each other and sort before the rest.
+    fn sort_results_by_path(&self) {
+        let mut state = self.state();
+        let sorted = match &mut state.list {
+            Some(list) => {
+                list.items.sort_by_cached_key(sort_key);
+                Ok(())
+            }
+            None => Err(EverythingError::SDKError(EverythingSDKError::InvalidCall)),
+        };
+        let _ = state.record(sorted);
+    }
+
+    fn is_result_file(&self, index: u32) -> bool {
+        self.state()
+            .item(index)
+            .is_ok_and(|item| !item.flags.contains(IpcItemFlags::Folder))
+    }
+
+    fn is_result_folder(&self, index: u32) -> bool {
+        self.state()
+            .item(index)
+            .is_ok_and(|item| item.flags.contains(IpcItemFlags::Folder))
+    }
+
+    fn is_result_volume(&self, index: u32) -> bool {
+        self.state()
+            .item(index)
+            .is_ok_and(|item| item.flags.contains(IpcItemFlags::Root))
+    }
+
+    fn get_result_full_path(&self, index: u32) -> EverythingResult<String> {
+        // Like the SDK, fall back to joining the path and name when the full path was not requested.
+        self.state().field(index, |item| {
+            match (&item.full_path, &item.path, &item.name) {
+                (Some(full_path), _, _) => Some(full_path.clone()),
+                (None, Some(path), Some(name)) if path.is_empty() => Some(name.clone()),
+                (None, Some(path), Some(name)) => Some(format!("{}\\{}", path, name)),
+                _ => None,
+            }
+        })
+    }
+
+    fn get_result_path(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |item| item.path.clone())
+    }
+
+    fn get_result_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |item| item.name.clone())
+    }
+
+    fn get_result_extension(&self, index: u32) -> EverythingResult<String> {
+        self.state().field(index, |item| item.extension.clone())
+    }
+
+    fn get_result_size(&self, index: u32) -> EverythingResult<u64> {
+        self.state().field(index, |item| item.size)
+    }
+
+    fn get_result_created_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .field(index, |item| item.date_created.map(FileTime::from))
+    }
+
+    fn get_result_modified_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .field(index, |item| item.date_modified.map(FileTime::from))
+    }
+
+    fn get_result_accessed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .field(index, |item| item.date_accessed.map(FileTime::from))
+    }
+
+    fn get_result_run_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .field(index, |item| item.date_run.map(FileTime::from))
+    }
+
+    fn get_result_recently_changed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.state()
+            .field(index, |item| item.date_recently_changed.map(FileTime::from))
+    }
+
+    fn get_result_attributes(&self, index: u32) -> EverythingResult<FileAttributes> {
+        self.state().field(index, |item| {
+            item.attributes.map(FileAttributes::from_bits_retain)
+        })
+    }
+
+    fn get_result_run_count(&self, index: u32) -> EverythingResult<u32> {
+        self.state().field(index, |item| item.run_count)
+    }
+
+    fn get_result_file_list_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .field(index, |item| item.file_list_file_name.clone())
+    }
+
+    fn get_result_highlighted_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .field(index, |item| item.highlighted_name.clone())
+    }
+
+    fn get_result_highlighted_path(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .field(index, |item| item.highlighted_path.clone())
+    }
+
+    fn get_result_highlighted_full_path(&self, index: u32) -> EverythingResult<String> {
+        self.state()
+            .field(index, |item| item.highlighted_full_path.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{LoopbackDesktop, LoopbackTransport};
+    use crate::test_util::{names, test_backend, test_entries};
+    use crate::{
+        CancellationToken, Everything, Highlighted, InMemoryBackend, InMemoryEntry, Version,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn encodes_searches() {
+        let everything =
+            Everything::with_backend(IpcClient::new(LoopbackTransport::new(test_backend())));
+        assert!(everything.wait_db_loaded(Duration::from_secs(1)).is_ok());
+        assert_eq!(everything.version().to_string(), "1.4.1.1024");
+
+        everything.set_search("test");
+        everything.set_max_results(2);
+        everything.set_result_offset(1);
+        everything.set_request_flags(
+            EverythingRequestFlags::FileName
+                | EverythingRequestFlags::Path
+                | EverythingRequestFlags::DateCreated
+                | EverythingRequestFlags::Size,
+        );
+        everything.set_sort(EverythingSort::DateCreatedDescending);
+        everything.query().unwrap();
+
+        assert_eq!(everything.get_total_results(), 5);
+        assert_eq!(everything.get_result_count(), 2);
+        assert_eq!(names(&everything), ["test3.md", "test2.jpg"]);
+        assert_eq!(
+            everything.get_result_created_date(1).unwrap(),
+            FileTime::from_raw(100)
+        );
+        assert_eq!(everything.get_result_size(1).unwrap(), 30);
+        assert_eq!(
+            everything.get_result_full_path(1).unwrap(),
+            "C:\\test\\test_dir\\test2.jpg"
+        );
+    }
+
+    #[test]
+    fn splits_full_paths() {
+        let everything =
+            Everything::with_backend(IpcClient::new(LoopbackTransport::new(test_backend())));
+        everything.set_search("C:\\test\\test_dir\\");
+        everything.set_request_flags(EverythingRequestFlags::FullPathAndFileName);
+        everything.query().unwrap();
+
+        let paths: Vec<String> = everything.full_path_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            paths,
+            [
+                "C:\\test\\test_dir\\test1.txt",
+                "C:\\test\\test_dir\\test2.jpg"
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_result_kinds() {
+        let everything =
+            Everything::with_backend(IpcClient::new(LoopbackTransport::new(test_backend())));
+        everything.query().unwrap();
+
+        assert_eq!(names(&everything)[0], "C:");
+        assert!(everything.is_result_volume(0));
+        assert!(everything.is_result_folder(0));
+        assert!(everything.is_result_folder(1));
+        assert!(!everything.is_result_volume(1));
+        assert!(everything.is_result_file(3));
+        assert!(!everything.is_result_file(99));
+        assert_eq!(everything.get_result_full_path(0).unwrap(), "C:");
+    }
+
+    #[test]
+    fn reports_errors_like_the_sdk() {
+        let client = IpcClient::new(LoopbackTransport::new(test_backend()));
+        assert_eq!(client.get_num_results(), 0);
+        assert!(matches!(
+            client.get_last_error(),
+            Err(EverythingError::SDKError(EverythingSDKError::InvalidCall))
+        ));
+
+        client.set_request_flags(EverythingRequestFlags::FileName);
+        client.query().unwrap();
+        assert!(client.get_last_error().is_ok());
+        assert!(matches!(
+            client.get_result_size(0),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidRequest
+            ))
+        ));
+        assert!(matches!(
+            client.get_result_full_path(0),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidRequest
+            ))
+        ));
+        assert!(matches!(
+            client.get_result_file_name(99),
+            Err(EverythingError::SDKError(EverythingSDKError::InvalidIndex))
+        ));
+
+        client.reset();
+        assert_eq!(client.get_request_flags(), Query2::default().request_flags);
+        assert_eq!(client.get_total_results(), 0);
+    }
+
+    #[test]
+    fn fails_when_everything_is_not_running() {
+        let client = IpcClient::new(LoopbackTransport::new(test_backend()));
+        client.transport().set_running(false);
+
+        assert!(!client.is_db_loaded());
+        assert_eq!(client.version(), (0, 0, 0, 0));
+        assert!(matches!(
+            client.query(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert!(matches!(
+            client.get_last_error(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+
+        client.transport().set_running(true);
+        client.query().unwrap();
+        assert_eq!(client.get_num_results(), 6);
+    }
+
+    #[test]
+    fn asks_whether_db_is_loaded() {
+        let transport = LoopbackTransport::new(test_backend());
+        transport.backend().set_db_loaded(false);
+        let client = IpcClient::new(transport);
+        assert!(!client.is_db_loaded());
+        assert!(client.get_last_error().is_ok());
+
+        client.transport().backend().set_db_loaded(true);
+        assert!(client.is_db_loaded());
+    }
+
+    #[test]
+    fn keeps_instances_apart() {
+        let desktop = LoopbackDesktop::new();
+        let main = desktop.start(
+            None,
+            InMemoryBackend::new(vec![InMemoryEntry::file("C:\\src", "test_main.rs")]),
+        );
+        let build = InMemoryBackend::new(vec![
+            InMemoryEntry::file("D:\\out", "test_build.log"),
+            InMemoryEntry::file("D:\\out", "test_build.exe"),
+        ]);
+        build.set_version(1, 5, 0, 1383);
+        let build = desktop.start(Some("build"), build);
+
+        assert_eq!(main.instance(), None);
+        assert_eq!(build.instance(), Some("build"));
+        assert_eq!(
+            main.running_instances().unwrap(),
+            [None, Some("build".to_string())]
+        );
+
+        let main = Everything::with_backend(IpcClient::new(main));
+        let build = Everything::with_backend(IpcClient::new(build));
+        main.set_search("test");
+        build.set_search("test");
+        main.query().unwrap();
+        build.query().unwrap();
+        main.query().unwrap();
+
+        assert_eq!(names(&main), ["test_main.rs"]);
+        assert_eq!(names(&build), ["test_build.exe", "test_build.log"]);
+        assert_eq!(main.get_result_path(0).unwrap(), "C:\\src");
+        assert_eq!(build.version(), Version::new(1, 5, 0, 1383));
+        assert_ne!(main.version(), build.version());
+    }
+
+    #[test]
+    fn discovers_running_instances() {
+        let desktop = LoopbackDesktop::new();
+        let main = desktop.start(None, InMemoryBackend::new(Vec::new()));
+        let beta = desktop.start(Some("1.5a"), InMemoryBackend::new(Vec::new()));
+        let build = desktop.start(Some("build"), InMemoryBackend::new(Vec::new()));
+        assert_eq!(
+            build.running_instances().unwrap(),
+            [None, Some("1.5a".to_string()), Some("build".to_string())]
+        );
+
+        beta.set_running(false);
+        drop(build);
+        assert_eq!(main.running_instances().unwrap(), [None]);
+
+        // Transports that only see their own instance list it while it answers.
+        let client = IpcClient::new(Recorded {
+            reply: Vec::new(),
+            queries: Mutex::new(Vec::new()),
+        });
+        assert_eq!(client.transport().running_instances().unwrap(), [None]);
+        let alone = LoopbackTransport::new(InMemoryBackend::new(Vec::new()));
+        assert_eq!(alone.running_instances().unwrap(), [None]);
+    }
+
+    #[test]
+    fn decodes_remaining_fields() {
+        let mut entry = InMemoryEntry::file("C:\\test", "run*me.exe");
+        entry.date_accessed = 1;
+        entry.date_run = 2;
+        entry.date_recently_changed = 3;
+        entry.attributes = 0x20;
+        entry.run_count = 4;
+        let transport = LoopbackTransport::new(InMemoryBackend::new(vec![entry]));
+        let everything = Everything::with_backend(IpcClient::new(transport));
+
+        everything.set_search("me");
+        everything.set_request_flags(EverythingRequestFlags::all());
+        everything.query().unwrap();
+
+        assert_eq!(everything.get_result_path(0).unwrap(), "C:\\test");
+        assert_eq!(
+            everything.get_result_accessed_date(0).unwrap(),
+            FileTime::from_raw(1)
+        );
+        assert_eq!(
+            everything.get_result_run_date(0).unwrap(),
+            FileTime::from_raw(2)
+        );
+        assert_eq!(
+            everything.get_result_recently_changed_date(0).unwrap(),
+            FileTime::from_raw(3)
+        );
+        assert_eq!(
+            everything.get_result_attributes(0).unwrap(),
+            FileAttributes::Archive
+        );
+        assert_eq!(everything.get_result_run_count(0).unwrap(), 4);
+        assert_eq!(everything.get_result_file_list_file_name(0).unwrap(), "");
+        assert_eq!(
+            everything.get_result_highlighted_file_name(0).unwrap(),
+            Highlighted::parse("run***me*.exe")
+        );
+        assert_eq!(
+            everything.get_result_highlighted_path(0).unwrap().text,
+            "C:\\test"
+        );
+        let full_path = everything.get_result_highlighted_full_path(0).unwrap();
+        assert_eq!(full_path.text, "C:\\test\\run*me.exe");
+        assert_eq!(full_path.matches().collect::<Vec<_>>(), ["me"]);
+
+        everything.set_request_flags(EverythingRequestFlags::FileName);
+        everything.query().unwrap();
+        assert!(everything.get_result_run_count(0).is_err());
+        assert!(everything.get_result_highlighted_path(0).is_err());
+    }
+
+    #[test]
+    fn reports_totals_of_version_1_replies() {
+        let client = IpcClient::new(LoopbackTransport::new(test_backend()));
+        assert_eq!(client.get_num_file_results(), 0);
+        assert!(client.get_last_error().is_err());
+
+        client.set_max_results(4);
+        client.query().unwrap();
+        assert_eq!(client.get_num_file_results(), 2);
+        assert_eq!(client.get_num_folder_results(), 2);
+
+        // The name and path sorted by name is a version 1 query, whose reply carries the totals.
+        assert_eq!(client.get_total_file_results(), 3);
+        assert_eq!(client.get_total_folder_results(), 3);
+        assert!(client.get_last_error().is_ok());
+
+        // QUERY2 replies do not.
+        client.set_sort(EverythingSort::NameDescending);
+        client.query().unwrap();
+        assert_eq!(client.get_num_folder_results(), 1);
+        assert_eq!(client.get_total_file_results(), 0);
+        assert!(matches!(
+            client.get_last_error(),
+            Err(EverythingError::SDKError(EverythingSDKError::InvalidCall))
+        ));
+        assert_eq!(client.get_total_folder_results(), 0);
+    }
+
+    #[test]
+    fn falls_back_to_query1() {
+        let backend = InMemoryBackend::new(test_entries(&["test", "test1.txt"]));
+        backend.set_version(1, 4, 0, 0);
+        let everything = Everything::with_backend(IpcClient::new(LoopbackTransport::new(backend)));
+
+        everything.set_search("test");
+        everything
+            .set_request_flags(EverythingRequestFlags::FileName | EverythingRequestFlags::Size);
+        everything.query().unwrap();
+
+        // Only the name and path come back, like the SDK after it falls back.
+        assert_eq!(
+            everything.get_result_list_request_flags(),
+            EverythingRequestFlags::FileName | EverythingRequestFlags::Path
+        );
+        assert_eq!(names(&everything), ["test", "test1.txt"]);
+        assert_eq!(
+            everything.get_result_full_path(1).unwrap(),
+            "C:\\test\\test_dir\\test1.txt"
+        );
+        assert!(matches!(
+            everything.get_result_size(1),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidRequest
+            ))
+        ));
+        assert_eq!(everything.get_total_folder_results(), 1);
+        assert_eq!(everything.get_total_file_results(), 1);
+    }
+
+    /// Counts the queries it was sent, failing each like a missing Everything.
+    struct Stopped {
+        queries: AtomicUsize,
+    }
+
+    impl IpcTransport for Stopped {
+        fn send_command(&self, _command: u32, _param: u32) -> EverythingResult<u32> {
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        }
+
+        fn send_copydata(&self, _copydata: u32, _data: &[u8]) -> EverythingResult<u32> {
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        }
+
+        fn send_query(
+            &self,
+            _copydata: u32,
+            _reply_id: u32,
+            _encode: &dyn Fn(u32) -> Vec<u8>,
+            _limits: &QueryLimits,
+        ) -> EverythingResult<Vec<u8>> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        }
+    }
+
+    #[test]
+    fn does_not_fall_back_when_not_running() {
+        let client = IpcClient::new(Stopped {
+            queries: AtomicUsize::new(0),
+        });
+        client.set_sort(EverythingSort::SizeDescending);
+        assert!(matches!(
+            client.query(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert_eq!(client.transport().queries.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn sorts_replies_by_path() {
+        let client = IpcClient::new(LoopbackTransport::new(test_backend()));
+        client.sort_results_by_path();
+        assert!(client.get_last_error().is_err());
+
+        let everything = Everything::with_backend(client);
+        everything.set_search("test");
+        everything.set_sort(EverythingSort::SizeDescending);
+        everything.query().unwrap();
+        assert_eq!(names(&everything)[0], "test2.jpg");
+
+        everything.sort_results_by_path();
+        assert!(everything.get_last_error().is_ok());
+        assert_eq!(
+            names(&everything),
+            ["test", "test3.md", "test_dir", "test1.txt", "test2.jpg"]
+        );
+
+        // The full path is enough to sort by when the name and path were not requested.
+        everything.set_request_flags(EverythingRequestFlags::FullPathAndFileName);
+        everything.query().unwrap();
+        everything.sort_results_by_path();
+        let paths: Vec<String> = everything.full_path_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            paths,
+            [
+                "C:\\test",
+                "C:\\test\\test3.md",
+                "C:\\test\\test_dir",
+                "C:\\test\\test_dir\\test1.txt",
+                "C:\\test\\test_dir\\test2.jpg"
+            ]
+        );
+    }
+
+    #[test]
+    fn discards_replies_to_abandoned_queries() {
+        let client = IpcClient::new(LoopbackTransport::new(test_backend()));
+        let transport = client.transport();
+        transport.set_delay(Duration::from_millis(50));
+        client.set_search("test1");
+        assert!(matches!(
+            client.query_with(&QueryLimits::timeout(Duration::from_millis(5))),
+            Err(EverythingError::QueryTimeout)
+        ));
+        assert!(client.get_last_error().is_ok());
+        assert_eq!(client.get_num_results(), 0);
+        assert_eq!(transport.pending_replies(), 1);
+
+        // The reply to "test1" arrives while this query waits and must not be taken for its own.
+        transport.set_delay(Duration::from_millis(100));
+        client.set_search("test2");
+        client.query().unwrap();
+        assert_eq!(transport.pending_replies(), 0);
+        assert_eq!(client.get_num_results(), 1);
+        assert_eq!(client.get_result_file_name(0).unwrap(), "test2.jpg");
+    }
+
+    #[test]
+    fn cancels_a_waiting_query() {
+        let client = IpcClient::new(LoopbackTransport::new(test_backend()));
+        client.transport().set_delay(Duration::from_secs(30));
+        let cancel = CancellationToken::new();
+
+        let started = Instant::now();
+        thread::scope(|scope| {
+            let waiting = scope.spawn(|| client.query_with(&QueryLimits::cancel(cancel.clone())));
+            thread::sleep(Duration::from_millis(20));
+            cancel.cancel();
+            assert!(matches!(
+                waiting.join().unwrap(),
+                Err(EverythingError::Cancelled)
+            ));
+        });
+        assert!(started.elapsed() < Duration::from_secs(5));
+        // Cancelling is not an error of Everything's.
+        assert!(client.get_last_error().is_ok());
+        assert_eq!(client.get_num_results(), 0);
+    }
+
+    /// Replies with fixed bytes and records the `dwData` and body of the queries it was sent.
+    struct Recorded {
+        reply: Vec<u8>,
+        queries: Mutex<Vec<(u32, Vec<u8>)>>,
+    }
+
+    impl IpcTransport for Recorded {
+        fn send_command(&self, _command: u32, _param: u32) -> EverythingResult<u32> {
+            Ok(1)
+        }
+
+        fn send_copydata(&self, _copydata: u32, _data: &[u8]) -> EverythingResult<u32> {
+            Ok(1)
+        }
+
+        fn send_query(
+            &self,
+            copydata: u32,
+            _reply_id: u32,
+            encode: &dyn Fn(u32) -> Vec<u8>,
+            _limits: &QueryLimits,
+        ) -> EverythingResult<Vec<u8>> {
+            self.queries.lock().unwrap().push((copydata, encode(42)));
+            Ok(self.reply.clone())
+        }
+    }
+
+    #[test]
+    fn sends_the_search_state_per_query() {
+        let client = IpcClient::new(Recorded {
+            reply: List1 {
+                total_folders: 0,
+                total_files: 0,
+                total_items: 0,
+                offset: 0,
+                items: Vec::new(),
+            }
+            .encode(),
+            queries: Mutex::new(Vec::new()),
+        });
+        client.set_search("first");
+        client.query().unwrap();
+        client.set_search("second");
+        client.query().unwrap();
+
+        client.set_match_case(true);
+        client.set_match_whole_word(true);
+        client.set_match_path(true);
+        client.set_regex(true);
+        client.query().unwrap();
+        assert!(client.get_match_case() && client.get_match_path());
+        assert!(client.get_match_whole_word() && client.get_regex());
+
+        // Only the name and path sorted by name were asked for, so every query is version 1.
+        let queries: Vec<Query1> = client
+            .transport()
+            .queries
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(copydata, body)| {
+                assert_eq!(*copydata, EVERYTHING_IPC_COPYDATAQUERYW);
+                Query1::decode(body).unwrap()
+            })
+            .collect();
+        assert_eq!(queries[0].search, "first");
+        assert_eq!(queries[0].search_flags, IpcSearchFlags::empty());
+        assert_eq!(
+            queries[2].search_flags,
+            IpcSearchFlags::MatchCase
+                | IpcSearchFlags::MatchWholeWord
+                | IpcSearchFlags::MatchPath
+                | IpcSearchFlags::Regex
+        );
+        assert_eq!(queries[0].reply_hwnd, 42);
+        assert_eq!(queries[1].search, "second");
+        assert_ne!(
+            queries[0].reply_copydata_message,
+            queries[1].reply_copydata_message
+        );
+    }
+
+    #[test]
+    fn sends_query2_when_needed() {
+        let client = IpcClient::new(Recorded {
+            reply: List2 {
+                total_items: 0,
+                offset: 0,
+                request_flags: EverythingRequestFlags::FileName,
+                sort: EverythingSort::SizeDescending,
+                items: Vec::new(),
+            }
+            .encode(),
+            queries: Mutex::new(Vec::new()),
+        });
+        client.set_sort(EverythingSort::SizeDescending);
+        client.query().unwrap();
+
+        let queries = client.transport().queries.lock().unwrap();
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].0, EVERYTHING_IPC_COPYDATA_QUERY2W);
+        let query = Query2::decode(&queries[0].1).unwrap();
+        assert_eq!(query.sort, EverythingSort::SizeDescending);
+        assert_eq!(query.request_flags, Query2::default().request_flags);
+    }
+
+    #[test]
+    fn rejects_malformed_replies() {
+        let client = IpcClient::new(Recorded {
+            reply: vec![1, 2, 3],
+            queries: Mutex::new(Vec::new()),
+        });
+        assert!(matches!(client.query(), Err(EverythingError::IpcCodec(_))));
+        assert!(matches!(
+            client.get_last_error(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert_eq!(client.get_num_results(), 0);
+    }
+
+    #[test]
+    fn does_not_send_null_characters() {
+        let client = IpcClient::new(Recorded {
+            reply: Vec::new(),
+            queries: Mutex::new(Vec::new()),
+        });
+        client.set_search("a\0b");
+        assert!(matches!(
+            client.query(),
+            Err(EverythingError::SDKError(
+                EverythingSDKError::InvalidParameter
+            ))
+        ));
+        assert!(client.get_last_error().is_err());
+        assert!(client.transport().queries.lock().unwrap().is_empty());
+    }
+}
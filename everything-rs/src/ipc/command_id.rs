@@ -0,0 +1,1406 @@
+//! `EVERYTHING_IPC_ID_*`, the command IDs an Everything search window accepts as `WM_COMMAND`.
+//! Mirrors `Everything-SDK/ipc/everything_ipc.h`, in the order of the header.
+
+use crate::{EverythingError, EverythingSDKError};
+
+pub const EVERYTHING_IPC_ID_FILE_MENU: u32 = 10001;
+pub const EVERYTHING_IPC_ID_EDIT_MENU: u32 = 10002;
+pub const EVERYTHING_IPC_ID_SEARCH_MENU: u32 = 10003;
+pub const EVERYTHING_IPC_ID_TOOLS_MENU: u32 = 10004;
+pub const EVERYTHING_IPC_ID_HELP_MENU: u32 = 10005;
+pub const EVERYTHING_IPC_ID_TOOLBAR: u32 = 10006;
+pub const EVERYTHING_IPC_ID_SEARCH_EDIT: u32 = 10007;
+pub const EVERYTHING_IPC_ID_FILTER: u32 = 10008;
+pub const EVERYTHING_IPC_ID_RESULTS_HEADER: u32 = 10009;
+pub const EVERYTHING_IPC_ID_STATUS: u32 = 10010;
+pub const EVERYTHING_IPC_ID_VIEW_ZOOM_MENU: u32 = 10012;
+pub const EVERYTHING_IPC_ID_VIEW_MENU: u32 = 10013;
+pub const EVERYTHING_IPC_ID_VIEW_WINDOW_SIZE_MENU: u32 = 10019;
+pub const EVERYTHING_IPC_ID_RESULT_LIST: u32 = 10020;
+pub const EVERYTHING_IPC_ID_BOOKMARKS_MENU: u32 = 10021;
+pub const EVERYTHING_IPC_ID_VIEW_SORT_BY_MENU: u32 = 10022;
+pub const EVERYTHING_IPC_ID_VIEW_GOTO_MENU: u32 = 10024;
+pub const EVERYTHING_IPC_ID_VIEW_ONTOP_MENU: u32 = 10025;
+pub const EVERYTHING_IPC_ID_PREVIEW: u32 = 10026;
+pub const EVERYTHING_IPC_ID_TRAY_NEW_SEARCH_WINDOW: u32 = 40001;
+pub const EVERYTHING_IPC_ID_TRAY_CONNECT_TO_ETP_SERVER: u32 = 40004;
+pub const EVERYTHING_IPC_ID_TRAY_OPTIONS: u32 = 40005;
+pub const EVERYTHING_IPC_ID_TRAY_EXIT: u32 = 40006;
+pub const EVERYTHING_IPC_ID_TRAY_SHOW_SEARCH_WINDOW: u32 = 40007;
+pub const EVERYTHING_IPC_ID_TRAY_TOGGLE_SEARCH_WINDOW: u32 = 40008;
+pub const EVERYTHING_IPC_ID_FILE_NEW_WINDOW: u32 = 40010;
+pub const EVERYTHING_IPC_ID_FILE_CLOSE: u32 = 40011;
+pub const EVERYTHING_IPC_ID_FILE_EXPORT: u32 = 40012;
+pub const EVERYTHING_IPC_ID_FILE_EXIT: u32 = 40013;
+pub const EVERYTHING_IPC_ID_FILE_OPEN_FILELIST: u32 = 40014;
+pub const EVERYTHING_IPC_ID_FILE_CLOSE_FILELIST: u32 = 40015;
+pub const EVERYTHING_IPC_ID_EDIT_CUT: u32 = 40020;
+pub const EVERYTHING_IPC_ID_EDIT_COPY: u32 = 40021;
+pub const EVERYTHING_IPC_ID_EDIT_PASTE: u32 = 40022;
+pub const EVERYTHING_IPC_ID_EDIT_SELECT_ALL: u32 = 40023;
+pub const EVERYTHING_IPC_ID_EDIT_INVERT_SELECTION: u32 = 40029;
+pub const EVERYTHING_IPC_ID_VIEW_ZOOM_IN: u32 = 40030;
+pub const EVERYTHING_IPC_ID_VIEW_ZOOM_OUT: u32 = 40031;
+pub const EVERYTHING_IPC_ID_VIEW_ZOOM_RESET: u32 = 40032;
+pub const EVERYTHING_IPC_ID_VIEW_TOGGLE_FULLSCREEN: u32 = 40034;
+pub const EVERYTHING_IPC_ID_VIEW_AUTO_FIT: u32 = 40044;
+pub const EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_1: u32 = 40045;
+pub const EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_2: u32 = 40046;
+pub const EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_3: u32 = 40047;
+pub const EVERYTHING_IPC_ID_VIEW_REFRESH: u32 = 40036;
+pub const EVERYTHING_IPC_ID_VIEW_FILTERS: u32 = 40035;
+pub const EVERYTHING_IPC_ID_VIEW_SORT_BY_ASCENDING: u32 = 40037;
+pub const EVERYTHING_IPC_ID_VIEW_SORT_BY_DESCENDING: u32 = 40038;
+pub const EVERYTHING_IPC_ID_VIEW_STATUS_BAR: u32 = 40039;
+pub const EVERYTHING_IPC_ID_VIEW_GOTO_BACK: u32 = 40040;
+pub const EVERYTHING_IPC_ID_VIEW_GOTO_FORWARD: u32 = 40041;
+pub const EVERYTHING_IPC_ID_VIEW_ONTOP_NEVER: u32 = 40042;
+pub const EVERYTHING_IPC_ID_VIEW_ONTOP_ALWAYS: u32 = 40043;
+pub const EVERYTHING_IPC_ID_VIEW_ONTOP_WHILE_SEARCHING: u32 = 40048;
+pub const EVERYTHING_IPC_ID_VIEW_GOTO_HOME: u32 = 40049;
+pub const EVERYTHING_IPC_ID_VIEW_TOGGLE_LTR_RTL: u32 = 40050;
+pub const EVERYTHING_IPC_ID_VIEW_DETAILS: u32 = 40051;
+pub const EVERYTHING_IPC_ID_VIEW_MEDIUM_ICONS: u32 = 40052;
+pub const EVERYTHING_IPC_ID_VIEW_LARGE_ICONS: u32 = 40053;
+pub const EVERYTHING_IPC_ID_VIEW_EXTRA_LARGE_ICONS: u32 = 40054;
+pub const EVERYTHING_IPC_ID_VIEW_PREVIEW: u32 = 40055;
+pub const EVERYTHING_IPC_ID_VIEW_GOTO_SHOW_ALL_HISTORY: u32 = 40056;
+pub const EVERYTHING_IPC_ID_VIEW_INCREASE_THUMBNAIL_SIZE: u32 = 40057;
+pub const EVERYTHING_IPC_ID_VIEW_DECREASE_THUMBNAIL_SIZE: u32 = 40058;
+pub const EVERYTHING_IPC_ID_VIEW_SHOW_FILTERS: u32 = 40096;
+pub const EVERYTHING_IPC_ID_VIEW_HIDE_FILTERS: u32 = 40097;
+pub const EVERYTHING_IPC_ID_VIEW_SHOW_PREVIEW: u32 = 40098;
+pub const EVERYTHING_IPC_ID_VIEW_HIDE_PREVIEW: u32 = 40099;
+pub const EVERYTHING_IPC_ID_VIEW_SHOW_STATUS_BAR: u32 = 40100;
+pub const EVERYTHING_IPC_ID_VIEW_HIDE_STATUS_BAR: u32 = 40101;
+pub const EVERYTHING_IPC_ID_VIEW_DETAILS_NO_TOGGLE: u32 = 40102;
+pub const EVERYTHING_IPC_ID_VIEW_MEDIUM_ICONS_NO_TOGGLE: u32 = 40103;
+pub const EVERYTHING_IPC_ID_VIEW_LARGE_ICONS_NO_TOGGLE: u32 = 40104;
+pub const EVERYTHING_IPC_ID_VIEW_EXTRA_LARGE_ICONS_NO_TOGGLE: u32 = 40105;
+pub const EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_CASE: u32 = 40060;
+pub const EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_WHOLE_WORD: u32 = 40061;
+pub const EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_PATH: u32 = 40062;
+pub const EVERYTHING_IPC_ID_SEARCH_TOGGLE_REGEX: u32 = 40063;
+pub const EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_DIACRITICS: u32 = 40066;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_ADD: u32 = 40067;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_ORGANIZE: u32 = 40068;
+pub const EVERYTHING_IPC_ID_SEARCH_ADVANCED_SEARCH: u32 = 40069;
+pub const EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_CASE: u32 = 40106;
+pub const EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_WHOLE_WORD: u32 = 40107;
+pub const EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_PATH: u32 = 40108;
+pub const EVERYTHING_IPC_ID_SEARCH_ENABLE_REGEX: u32 = 40109;
+pub const EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_DIACRITICS: u32 = 40110;
+pub const EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_CASE: u32 = 40111;
+pub const EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_WHOLE_WORD: u32 = 40112;
+pub const EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_PATH: u32 = 40113;
+pub const EVERYTHING_IPC_ID_SEARCH_DISABLE_REGEX: u32 = 40114;
+pub const EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_DIACRITICS: u32 = 40115;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_EVERYTHING: u32 = 40116;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_AUDIO: u32 = 40117;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_COMPRESSED: u32 = 40118;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_DOCUMENT: u32 = 40119;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_EXECUTABLE: u32 = 40120;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_FOLDER: u32 = 40121;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_PICTURE: u32 = 40122;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_VIDEO: u32 = 40123;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_AUDIO_NO_TOGGLE: u32 = 40124;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_COMPRESSED_NO_TOGGLE: u32 = 40125;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_DOCUMENT_NO_TOGGLE: u32 = 40126;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_EXECUTABLE_NO_TOGGLE: u32 = 40127;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_FOLDER_NO_TOGGLE: u32 = 40128;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_PICTURE_NO_TOGGLE: u32 = 40129;
+pub const EVERYTHING_IPC_ID_SEARCH_FILTER_VIDEO_NO_TOGGLE: u32 = 40130;
+pub const EVERYTHING_IPC_ID_TOOLS_CONNECT_TO_ETP_SERVER: u32 = 40072;
+pub const EVERYTHING_IPC_ID_TOOLS_DISCONNECT_FROM_ETP_SERVER: u32 = 40073;
+pub const EVERYTHING_IPC_ID_TOOLS_OPTIONS: u32 = 40074;
+pub const EVERYTHING_IPC_ID_TOOLS_CONSOLE: u32 = 40075;
+pub const EVERYTHING_IPC_ID_TOOLS_EDITOR: u32 = 40076;
+pub const EVERYTHING_IPC_ID_HELP_VIEW_HELP_TOPICS: u32 = 40080;
+pub const EVERYTHING_IPC_ID_HELP_OPEN_EVERYTHING_WEBSITE: u32 = 40081;
+pub const EVERYTHING_IPC_ID_HELP_CHECK_FOR_UPDATES: u32 = 40082;
+pub const EVERYTHING_IPC_ID_HELP_ABOUT_EVERYTHING: u32 = 40083;
+pub const EVERYTHING_IPC_ID_HELP_SEARCH_SYNTAX: u32 = 40084;
+pub const EVERYTHING_IPC_ID_HELP_COMMAND_LINE_OPTIONS: u32 = 40085;
+pub const EVERYTHING_IPC_ID_HELP_REGEX_SYNTAX: u32 = 40086;
+pub const EVERYTHING_IPC_ID_HELP_DONATE: u32 = 40087;
+pub const EVERYTHING_IPC_ID_BOOKMARK_ADD: u32 = 40090;
+pub const EVERYTHING_IPC_ID_BOOKMARK_ORGANIZE: u32 = 40091;
+/// The first bookmark, bookmarks run up to `EVERYTHING_IPC_ID_BOOKMARK_END` exclusive.
+pub const EVERYTHING_IPC_ID_BOOKMARK_START: u32 = 44000;
+pub const EVERYTHING_IPC_ID_BOOKMARK_END: u32 = 45000;
+/// The first filter, filters run up to `EVERYTHING_IPC_ID_FILTER_END` exclusive.
+pub const EVERYTHING_IPC_ID_FILTER_START: u32 = 45000;
+pub const EVERYTHING_IPC_ID_FILTER_END: u32 = 46000;
+/// The first history entry of the go to menu, entries run up to `EVERYTHING_IPC_ID_VIEW_GOTO_END` exclusive.
+pub const EVERYTHING_IPC_ID_VIEW_GOTO_START: u32 = 46000;
+pub const EVERYTHING_IPC_ID_VIEW_GOTO_END: u32 = 47000;
+pub const EVERYTHING_IPC_ID_FILE_OPEN: u32 = 41000;
+pub const EVERYTHING_IPC_ID_FILE_OPEN_NEW: u32 = 41048;
+pub const EVERYTHING_IPC_ID_FILE_OPEN_WITH: u32 = 41049;
+pub const EVERYTHING_IPC_ID_FILE_EDIT: u32 = 41050;
+pub const EVERYTHING_IPC_ID_FILE_PLAY: u32 = 41051;
+pub const EVERYTHING_IPC_ID_FILE_PRINT: u32 = 41052;
+pub const EVERYTHING_IPC_ID_FILE_PREVIEW: u32 = 41053;
+pub const EVERYTHING_IPC_ID_FILE_PRINT_TO: u32 = 41054;
+pub const EVERYTHING_IPC_ID_FILE_RUN_AS: u32 = 41055;
+pub const EVERYTHING_IPC_ID_FILE_OPEN_WITH_DEFAULT_VERB: u32 = 41056;
+pub const EVERYTHING_IPC_ID_FILE_OPEN_AND_CLOSE: u32 = 41057;
+pub const EVERYTHING_IPC_ID_FILE_EXPLORE_PATH: u32 = 41002;
+pub const EVERYTHING_IPC_ID_FILE_OPEN_PATH: u32 = 41003;
+pub const EVERYTHING_IPC_ID_FILE_DELETE: u32 = 41004;
+pub const EVERYTHING_IPC_ID_FILE_PERMANENTLY_DELETE: u32 = 41005;
+pub const EVERYTHING_IPC_ID_FILE_RENAME: u32 = 41006;
+pub const EVERYTHING_IPC_ID_FILE_COPY_FULL_PATH_AND_NAME: u32 = 41007;
+pub const EVERYTHING_IPC_ID_FILE_COPY_PATH: u32 = 41008;
+pub const EVERYTHING_IPC_ID_FILE_PROPERTIES: u32 = 41009;
+pub const EVERYTHING_IPC_ID_FILE_READ_EXTENDED_INFORMATION: u32 = 41064;
+pub const EVERYTHING_IPC_ID_FILE_CREATE_SHORTCUT: u32 = 41065;
+pub const EVERYTHING_IPC_ID_FILE_SET_RUN_COUNT: u32 = 41068;
+pub const EVERYTHING_IPC_ID_FILE_COPY_NAME: u32 = 41011;
+pub const EVERYTHING_IPC_ID_FILE_OPEN_AND_DO_NOT_CLOSE: u32 = 41076;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_EXPLORE: u32 = 41001;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS: u32 = 41010;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_AUTOFIT_COLUMNS: u32 = 41012;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_DOWN: u32 = 41018;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_UP: u32 = 41019;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_PAGE_UP: u32 = 41020;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_PAGE_DOWN: u32 = 41021;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_START: u32 = 41022;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_END: u32 = 41023;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_DOWN_EXTEND: u32 = 41024;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_UP_EXTEND: u32 = 41025;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_PAGE_UP_EXTEND: u32 = 41026;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_PAGE_DOWN_EXTEND: u32 = 41027;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_START_EXTEND: u32 = 41028;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_END_EXTEND: u32 = 41029;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_DOWN: u32 = 41030;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_UP: u32 = 41031;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_UP: u32 = 41032;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_DOWN: u32 = 41033;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_START: u32 = 41034;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_END: u32 = 41035;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_LEFT: u32 = 41036;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_RIGHT: u32 = 41037;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_LEFT: u32 = 41038;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_RIGHT: u32 = 41039;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SELECT_FOCUS: u32 = 41040;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_FOCUS_SELECTION: u32 = 41041;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_CONTEXT_MENU: u32 = 41046;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_DOWN_EXTEND: u32 = 41058;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_UP_EXTEND: u32 = 41059;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_UP_EXTEND: u32 = 41060;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_DOWN_EXTEND: u32 = 41061;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_START_EXTEND: u32 = 41062;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_END_EXTEND: u32 = 41063;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_AUTOFIT: u32 = 41066;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_COPY_CSV: u32 = 41067;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_LEFT_EXTEND: u32 = 41070;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_RIGHT_EXTEND: u32 = 41071;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LEFT_EXTEND: u32 = 41072;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_RIGHT_EXTEND: u32 = 41073;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_MOST_RUN: u32 = 41074;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LAST_RUN: u32 = 41075;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_LEFT: u32 = 41079;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_RIGHT: u32 = 41080;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LEFT: u32 = 41081;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_RIGHT: u32 = 41082;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_LEFT_SCROLL_ONLY: u32 = 41083;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_RIGHT_SCROLL_ONLY: u32 = 41084;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_LEFT_SCROLL_ONLY: u32 = 41085;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_RIGHT_SCROLL_ONLY: u32 = 41086;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_NAME: u32 = 41300;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_PATH: u32 = 41301;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_SIZE: u32 = 41302;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_EXTENSION: u32 = 41303;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_TYPE: u32 = 41304;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_MODIFIED: u32 = 41305;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_CREATED: u32 = 41306;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_ATTRIBUTES: u32 = 41307;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_FILE_LIST_FILENAME: u32 = 41308;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_RUN_COUNT: u32 = 41309;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_RECENTLY_CHANGED: u32 = 41310;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_ACCESSED: u32 = 41311;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_RUN: u32 = 41312;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_NAME_COLUMN: u32 = 41400;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_PATH_COLUMN: u32 = 41401;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_SIZE_COLUMN: u32 = 41402;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_EXTENSION_COLUMN: u32 = 41403;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_TYPE_COLUMN: u32 = 41404;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_MODIFIED_COLUMN: u32 = 41405;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_CREATED_COLUMN: u32 = 41406;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_ATTRIBUTES_COLUMN: u32 = 41407;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_FILE_LIST_FILENAME_COLUMN: u32 = 41408;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_RUN_COUNT_COLUMN: u32 = 41409;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_RECENTLY_CHANGED_COLUMN: u32 = 41410;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_ACCESSED_COLUMN: u32 = 41411;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_RUN_COLUMN: u32 = 41412;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_NAME_COLUMN_TO_FIT: u32 = 41600;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_PATH_COLUMN_TO_FIT: u32 = 41601;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_SIZE_COLUMN_TO_FIT: u32 = 41602;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_EXTENSION_COLUMN_TO_FIT: u32 = 41603;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_TYPE_COLUMN_TO_FIT: u32 = 41604;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_MODIFIED_COLUMN_TO_FIT: u32 = 41605;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_CREATED_COLUMN_TO_FIT: u32 = 41606;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_ATTRIBUTES_COLUMN_TO_FIT: u32 = 41607;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_FILE_LIST_FILENAME_COLUMN_TO_FIT: u32 = 41608;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_RUN_COUNT_COLUMN_TO_FIT: u32 = 41609;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_RECENTLY_CHANGED_COLUMN_TO_FIT: u32 = 41610;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_ACCESSED_COLUMN_TO_FIT: u32 = 41611;
+pub const EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_RUN_COLUMN_TO_FIT: u32 = 41612;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB01: u32 = 41500;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB02: u32 = 41501;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB03: u32 = 41502;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB04: u32 = 41503;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB05: u32 = 41504;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB06: u32 = 41505;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB07: u32 = 41506;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB08: u32 = 41507;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB09: u32 = 41508;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB10: u32 = 41509;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB11: u32 = 41510;
+pub const EVERYTHING_IPC_ID_FILE_CUSTOM_VERB12: u32 = 41511;
+pub const EVERYTHING_IPC_ID_SEARCH_EDIT_FOCUS: u32 = 42000;
+pub const EVERYTHING_IPC_ID_SEARCH_EDIT_WORD_DELETE_TO_START: u32 = 42019;
+pub const EVERYTHING_IPC_ID_SEARCH_EDIT_AUTO_COMPLETE: u32 = 42020;
+pub const EVERYTHING_IPC_ID_SEARCH_EDIT_SHOW_SEARCH_HISTORY: u32 = 42021;
+pub const EVERYTHING_IPC_ID_SEARCH_EDIT_SHOW_ALL_SEARCH_HISTORY: u32 = 42022;
+pub const EVERYTHING_IPC_ID_TRAY_EDITOR: u32 = 41700;
+pub const EVERYTHING_IPC_ID_TRAY_OPEN_FILELIST: u32 = 41701;
+pub const EVERYTHING_IPC_ID_INDEX_UPDATE_ALL_FOLDERS_NOW: u32 = 41800;
+pub const EVERYTHING_IPC_ID_INDEX_FORCE_REBUILD: u32 = 41801;
+
+/// A command an Everything search window carries out when sent as `WM_COMMAND`, see `SearchWindow::command`.
+/// Most do what the menu item or shortcut of the same name does.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CommandId {
+    FileMenu,
+    EditMenu,
+    SearchMenu,
+    ToolsMenu,
+    HelpMenu,
+    Toolbar,
+    SearchEdit,
+    Filter,
+    ResultsHeader,
+    Status,
+    ViewZoomMenu,
+    ViewMenu,
+    ViewWindowSizeMenu,
+    ResultList,
+    BookmarksMenu,
+    ViewSortByMenu,
+    ViewGotoMenu,
+    ViewOntopMenu,
+    Preview,
+    TrayNewSearchWindow,
+    TrayConnectToEtpServer,
+    TrayOptions,
+    TrayExit,
+    TrayShowSearchWindow,
+    TrayToggleSearchWindow,
+    FileNewWindow,
+    FileClose,
+    FileExport,
+    FileExit,
+    FileOpenFilelist,
+    FileCloseFilelist,
+    EditCut,
+    EditCopy,
+    EditPaste,
+    EditSelectAll,
+    EditInvertSelection,
+    ViewZoomIn,
+    ViewZoomOut,
+    ViewZoomReset,
+    ViewToggleFullscreen,
+    ViewAutoFit,
+    ViewAutoSize1,
+    ViewAutoSize2,
+    ViewAutoSize3,
+    ViewRefresh,
+    ViewFilters,
+    ViewSortByAscending,
+    ViewSortByDescending,
+    ViewStatusBar,
+    ViewGotoBack,
+    ViewGotoForward,
+    ViewOntopNever,
+    ViewOntopAlways,
+    ViewOntopWhileSearching,
+    ViewGotoHome,
+    ViewToggleLtrRtl,
+    ViewDetails,
+    ViewMediumIcons,
+    ViewLargeIcons,
+    ViewExtraLargeIcons,
+    ViewPreview,
+    ViewGotoShowAllHistory,
+    ViewIncreaseThumbnailSize,
+    ViewDecreaseThumbnailSize,
+    /// Requires Everything 1.4.1.
+    ViewShowFilters,
+    /// Requires Everything 1.4.1.
+    ViewHideFilters,
+    /// Requires Everything 1.4.1.
+    ViewShowPreview,
+    /// Requires Everything 1.4.1.
+    ViewHidePreview,
+    /// Requires Everything 1.4.1.
+    ViewShowStatusBar,
+    /// Requires Everything 1.4.1.
+    ViewHideStatusBar,
+    /// Requires Everything 1.4.1.
+    ViewDetailsNoToggle,
+    /// Requires Everything 1.4.1.
+    ViewMediumIconsNoToggle,
+    /// Requires Everything 1.4.1.
+    ViewLargeIconsNoToggle,
+    /// Requires Everything 1.4.1.
+    ViewExtraLargeIconsNoToggle,
+    SearchToggleMatchCase,
+    SearchToggleMatchWholeWord,
+    SearchToggleMatchPath,
+    SearchToggleRegex,
+    SearchToggleMatchDiacritics,
+    SearchFilterAdd,
+    SearchFilterOrganize,
+    SearchAdvancedSearch,
+    /// Requires Everything 1.4.1.
+    SearchEnableMatchCase,
+    /// Requires Everything 1.4.1.
+    SearchEnableMatchWholeWord,
+    /// Requires Everything 1.4.1.
+    SearchEnableMatchPath,
+    /// Requires Everything 1.4.1.
+    SearchEnableRegex,
+    /// Requires Everything 1.4.1.
+    SearchEnableMatchDiacritics,
+    /// Requires Everything 1.4.1.
+    SearchDisableMatchCase,
+    /// Requires Everything 1.4.1.
+    SearchDisableMatchWholeWord,
+    /// Requires Everything 1.4.1.
+    SearchDisableMatchPath,
+    /// Requires Everything 1.4.1.
+    SearchDisableRegex,
+    /// Requires Everything 1.4.1.
+    SearchDisableMatchDiacritics,
+    /// Requires Everything 1.4.1.
+    SearchFilterEverything,
+    /// Requires Everything 1.4.1.
+    SearchFilterAudio,
+    /// Requires Everything 1.4.1.
+    SearchFilterCompressed,
+    /// Requires Everything 1.4.1.
+    SearchFilterDocument,
+    /// Requires Everything 1.4.1.
+    SearchFilterExecutable,
+    /// Requires Everything 1.4.1.
+    SearchFilterFolder,
+    /// Requires Everything 1.4.1.
+    SearchFilterPicture,
+    /// Requires Everything 1.4.1.
+    SearchFilterVideo,
+    /// Requires Everything 1.4.1.
+    SearchFilterAudioNoToggle,
+    /// Requires Everything 1.4.1.
+    SearchFilterCompressedNoToggle,
+    /// Requires Everything 1.4.1.
+    SearchFilterDocumentNoToggle,
+    /// Requires Everything 1.4.1.
+    SearchFilterExecutableNoToggle,
+    /// Requires Everything 1.4.1.
+    SearchFilterFolderNoToggle,
+    /// Requires Everything 1.4.1.
+    SearchFilterPictureNoToggle,
+    /// Requires Everything 1.4.1.
+    SearchFilterVideoNoToggle,
+    ToolsConnectToEtpServer,
+    ToolsDisconnectFromEtpServer,
+    ToolsOptions,
+    ToolsConsole,
+    ToolsEditor,
+    HelpViewHelpTopics,
+    HelpOpenEverythingWebsite,
+    HelpCheckForUpdates,
+    HelpAboutEverything,
+    HelpSearchSyntax,
+    HelpCommandLineOptions,
+    HelpRegexSyntax,
+    HelpDonate,
+    BookmarkAdd,
+    BookmarkOrganize,
+    FileOpen,
+    FileOpenNew,
+    FileOpenWith,
+    FileEdit,
+    FilePlay,
+    FilePrint,
+    FilePreview,
+    FilePrintTo,
+    FileRunAs,
+    FileOpenWithDefaultVerb,
+    FileOpenAndClose,
+    FileExplorePath,
+    FileOpenPath,
+    FileDelete,
+    FilePermanentlyDelete,
+    FileRename,
+    FileCopyFullPathAndName,
+    FileCopyPath,
+    FileProperties,
+    FileReadExtendedInformation,
+    FileCreateShortcut,
+    FileSetRunCount,
+    FileCopyName,
+    FileOpenAndDoNotClose,
+    ResultListExplore,
+    ResultListFocus,
+    ResultListAutofitColumns,
+    ResultListDown,
+    ResultListUp,
+    ResultListPageUp,
+    ResultListPageDown,
+    ResultListStart,
+    ResultListEnd,
+    ResultListDownExtend,
+    ResultListUpExtend,
+    ResultListPageUpExtend,
+    ResultListPageDownExtend,
+    ResultListStartExtend,
+    ResultListEndExtend,
+    ResultListFocusDown,
+    ResultListFocusUp,
+    ResultListFocusPageUp,
+    ResultListFocusPageDown,
+    ResultListFocusStart,
+    ResultListFocusEnd,
+    ResultListScrollLeft,
+    ResultListScrollRight,
+    ResultListScrollPageLeft,
+    ResultListScrollPageRight,
+    ResultListSelectFocus,
+    ResultListToggleFocusSelection,
+    ResultListContextMenu,
+    ResultListFocusDownExtend,
+    ResultListFocusUpExtend,
+    ResultListFocusPageUpExtend,
+    ResultListFocusPageDownExtend,
+    ResultListFocusStartExtend,
+    ResultListFocusEndExtend,
+    ResultListAutofit,
+    ResultListCopyCsv,
+    ResultListLeftExtend,
+    ResultListRightExtend,
+    ResultListFocusLeftExtend,
+    ResultListFocusRightExtend,
+    ResultListFocusMostRun,
+    ResultListFocusLastRun,
+    /// Requires Everything 1.4.1.
+    ResultListLeft,
+    /// Requires Everything 1.4.1.
+    ResultListRight,
+    /// Requires Everything 1.4.1.
+    ResultListFocusLeft,
+    /// Requires Everything 1.4.1.
+    ResultListFocusRight,
+    /// Requires Everything 1.4.1.
+    ResultListScrollLeftScrollOnly,
+    /// Requires Everything 1.4.1.
+    ResultListScrollRightScrollOnly,
+    /// Requires Everything 1.4.1.
+    ResultListScrollPageLeftScrollOnly,
+    /// Requires Everything 1.4.1.
+    ResultListScrollPageRightScrollOnly,
+    ResultListSortByName,
+    ResultListSortByPath,
+    ResultListSortBySize,
+    ResultListSortByExtension,
+    ResultListSortByType,
+    ResultListSortByDateModified,
+    ResultListSortByDateCreated,
+    ResultListSortByAttributes,
+    ResultListSortByFileListFilename,
+    ResultListSortByRunCount,
+    ResultListSortByDateRecentlyChanged,
+    ResultListSortByDateAccessed,
+    ResultListSortByDateRun,
+    ResultListToggleNameColumn,
+    ResultListTogglePathColumn,
+    ResultListToggleSizeColumn,
+    ResultListToggleExtensionColumn,
+    ResultListToggleTypeColumn,
+    ResultListToggleDateModifiedColumn,
+    ResultListToggleDateCreatedColumn,
+    ResultListToggleAttributesColumn,
+    ResultListToggleFileListFilenameColumn,
+    ResultListToggleRunCountColumn,
+    ResultListToggleDateRecentlyChangedColumn,
+    ResultListToggleDateAccessedColumn,
+    ResultListToggleDateRunColumn,
+    ResultListSizeNameColumnToFit,
+    ResultListSizePathColumnToFit,
+    ResultListSizeSizeColumnToFit,
+    ResultListSizeExtensionColumnToFit,
+    ResultListSizeTypeColumnToFit,
+    ResultListSizeDateModifiedColumnToFit,
+    ResultListSizeDateCreatedColumnToFit,
+    ResultListSizeAttributesColumnToFit,
+    ResultListSizeFileListFilenameColumnToFit,
+    ResultListSizeRunCountColumnToFit,
+    ResultListSizeDateRecentlyChangedColumnToFit,
+    ResultListSizeDateAccessedColumnToFit,
+    ResultListSizeDateRunColumnToFit,
+    FileCustomVerb01,
+    FileCustomVerb02,
+    FileCustomVerb03,
+    FileCustomVerb04,
+    FileCustomVerb05,
+    FileCustomVerb06,
+    FileCustomVerb07,
+    FileCustomVerb08,
+    FileCustomVerb09,
+    FileCustomVerb10,
+    FileCustomVerb11,
+    FileCustomVerb12,
+    SearchEditFocus,
+    SearchEditWordDeleteToStart,
+    SearchEditAutoComplete,
+    SearchEditShowSearchHistory,
+    SearchEditShowAllSearchHistory,
+    TrayEditor,
+    TrayOpenFilelist,
+    IndexUpdateAllFoldersNow,
+    IndexForceRebuild,
+    /// Opens the bookmark at this index of the bookmarks menu, below 1000.
+    BookmarkItem(u32),
+    /// Selects the filter at this index of the filters menu, below 1000.
+    FilterItem(u32),
+    /// Opens the entry at this index of the go to history, below 1000.
+    ViewGotoItem(u32),
+}
+
+impl CommandId {
+    /// The `WM_COMMAND` ID, `None` if the index of an item is outside its range.
+    pub fn id(self) -> Option<u32> {
+        let item =
+            |start: u32, end: u32, index: u32| start.checked_add(index).filter(|id| *id < end);
+        Some(match self {
+            CommandId::FileMenu => EVERYTHING_IPC_ID_FILE_MENU,
+            CommandId::EditMenu => EVERYTHING_IPC_ID_EDIT_MENU,
+            CommandId::SearchMenu => EVERYTHING_IPC_ID_SEARCH_MENU,
+            CommandId::ToolsMenu => EVERYTHING_IPC_ID_TOOLS_MENU,
+            CommandId::HelpMenu => EVERYTHING_IPC_ID_HELP_MENU,
+            CommandId::Toolbar => EVERYTHING_IPC_ID_TOOLBAR,
+            CommandId::SearchEdit => EVERYTHING_IPC_ID_SEARCH_EDIT,
+            CommandId::Filter => EVERYTHING_IPC_ID_FILTER,
+            CommandId::ResultsHeader => EVERYTHING_IPC_ID_RESULTS_HEADER,
+            CommandId::Status => EVERYTHING_IPC_ID_STATUS,
+            CommandId::ViewZoomMenu => EVERYTHING_IPC_ID_VIEW_ZOOM_MENU,
+            CommandId::ViewMenu => EVERYTHING_IPC_ID_VIEW_MENU,
+            CommandId::ViewWindowSizeMenu => EVERYTHING_IPC_ID_VIEW_WINDOW_SIZE_MENU,
+            CommandId::ResultList => EVERYTHING_IPC_ID_RESULT_LIST,
+            CommandId::BookmarksMenu => EVERYTHING_IPC_ID_BOOKMARKS_MENU,
+            CommandId::ViewSortByMenu => EVERYTHING_IPC_ID_VIEW_SORT_BY_MENU,
+            CommandId::ViewGotoMenu => EVERYTHING_IPC_ID_VIEW_GOTO_MENU,
+            CommandId::ViewOntopMenu => EVERYTHING_IPC_ID_VIEW_ONTOP_MENU,
+            CommandId::Preview => EVERYTHING_IPC_ID_PREVIEW,
+            CommandId::TrayNewSearchWindow => EVERYTHING_IPC_ID_TRAY_NEW_SEARCH_WINDOW,
+            CommandId::TrayConnectToEtpServer => EVERYTHING_IPC_ID_TRAY_CONNECT_TO_ETP_SERVER,
+            CommandId::TrayOptions => EVERYTHING_IPC_ID_TRAY_OPTIONS,
+            CommandId::TrayExit => EVERYTHING_IPC_ID_TRAY_EXIT,
+            CommandId::TrayShowSearchWindow => EVERYTHING_IPC_ID_TRAY_SHOW_SEARCH_WINDOW,
+            CommandId::TrayToggleSearchWindow => EVERYTHING_IPC_ID_TRAY_TOGGLE_SEARCH_WINDOW,
+            CommandId::FileNewWindow => EVERYTHING_IPC_ID_FILE_NEW_WINDOW,
+            CommandId::FileClose => EVERYTHING_IPC_ID_FILE_CLOSE,
+            CommandId::FileExport => EVERYTHING_IPC_ID_FILE_EXPORT,
+            CommandId::FileExit => EVERYTHING_IPC_ID_FILE_EXIT,
+            CommandId::FileOpenFilelist => EVERYTHING_IPC_ID_FILE_OPEN_FILELIST,
+            CommandId::FileCloseFilelist => EVERYTHING_IPC_ID_FILE_CLOSE_FILELIST,
+            CommandId::EditCut => EVERYTHING_IPC_ID_EDIT_CUT,
+            CommandId::EditCopy => EVERYTHING_IPC_ID_EDIT_COPY,
+            CommandId::EditPaste => EVERYTHING_IPC_ID_EDIT_PASTE,
+            CommandId::EditSelectAll => EVERYTHING_IPC_ID_EDIT_SELECT_ALL,
+            CommandId::EditInvertSelection => EVERYTHING_IPC_ID_EDIT_INVERT_SELECTION,
+            CommandId::ViewZoomIn => EVERYTHING_IPC_ID_VIEW_ZOOM_IN,
+            CommandId::ViewZoomOut => EVERYTHING_IPC_ID_VIEW_ZOOM_OUT,
+            CommandId::ViewZoomReset => EVERYTHING_IPC_ID_VIEW_ZOOM_RESET,
+            CommandId::ViewToggleFullscreen => EVERYTHING_IPC_ID_VIEW_TOGGLE_FULLSCREEN,
+            CommandId::ViewAutoFit => EVERYTHING_IPC_ID_VIEW_AUTO_FIT,
+            CommandId::ViewAutoSize1 => EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_1,
+            CommandId::ViewAutoSize2 => EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_2,
+            CommandId::ViewAutoSize3 => EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_3,
+            CommandId::ViewRefresh => EVERYTHING_IPC_ID_VIEW_REFRESH,
+            CommandId::ViewFilters => EVERYTHING_IPC_ID_VIEW_FILTERS,
+            CommandId::ViewSortByAscending => EVERYTHING_IPC_ID_VIEW_SORT_BY_ASCENDING,
+            CommandId::ViewSortByDescending => EVERYTHING_IPC_ID_VIEW_SORT_BY_DESCENDING,
+            CommandId::ViewStatusBar => EVERYTHING_IPC_ID_VIEW_STATUS_BAR,
+            CommandId::ViewGotoBack => EVERYTHING_IPC_ID_VIEW_GOTO_BACK,
+            CommandId::ViewGotoForward => EVERYTHING_IPC_ID_VIEW_GOTO_FORWARD,
+            CommandId::ViewOntopNever => EVERYTHING_IPC_ID_VIEW_ONTOP_NEVER,
+            CommandId::ViewOntopAlways => EVERYTHING_IPC_ID_VIEW_ONTOP_ALWAYS,
+            CommandId::ViewOntopWhileSearching => EVERYTHING_IPC_ID_VIEW_ONTOP_WHILE_SEARCHING,
+            CommandId::ViewGotoHome => EVERYTHING_IPC_ID_VIEW_GOTO_HOME,
+            CommandId::ViewToggleLtrRtl => EVERYTHING_IPC_ID_VIEW_TOGGLE_LTR_RTL,
+            CommandId::ViewDetails => EVERYTHING_IPC_ID_VIEW_DETAILS,
+            CommandId::ViewMediumIcons => EVERYTHING_IPC_ID_VIEW_MEDIUM_ICONS,
+            CommandId::ViewLargeIcons => EVERYTHING_IPC_ID_VIEW_LARGE_ICONS,
+            CommandId::ViewExtraLargeIcons => EVERYTHING_IPC_ID_VIEW_EXTRA_LARGE_ICONS,
+            CommandId::ViewPreview => EVERYTHING_IPC_ID_VIEW_PREVIEW,
+            CommandId::ViewGotoShowAllHistory => EVERYTHING_IPC_ID_VIEW_GOTO_SHOW_ALL_HISTORY,
+            CommandId::ViewIncreaseThumbnailSize => EVERYTHING_IPC_ID_VIEW_INCREASE_THUMBNAIL_SIZE,
+            CommandId::ViewDecreaseThumbnailSize => EVERYTHING_IPC_ID_VIEW_DECREASE_THUMBNAIL_SIZE,
+            CommandId::ViewShowFilters => EVERYTHING_IPC_ID_VIEW_SHOW_FILTERS,
+            CommandId::ViewHideFilters => EVERYTHING_IPC_ID_VIEW_HIDE_FILTERS,
+            CommandId::ViewShowPreview => EVERYTHING_IPC_ID_VIEW_SHOW_PREVIEW,
+            CommandId::ViewHidePreview => EVERYTHING_IPC_ID_VIEW_HIDE_PREVIEW,
+            CommandId::ViewShowStatusBar => EVERYTHING_IPC_ID_VIEW_SHOW_STATUS_BAR,
+            CommandId::ViewHideStatusBar => EVERYTHING_IPC_ID_VIEW_HIDE_STATUS_BAR,
+            CommandId::ViewDetailsNoToggle => EVERYTHING_IPC_ID_VIEW_DETAILS_NO_TOGGLE,
+            CommandId::ViewMediumIconsNoToggle => EVERYTHING_IPC_ID_VIEW_MEDIUM_ICONS_NO_TOGGLE,
+            CommandId::ViewLargeIconsNoToggle => EVERYTHING_IPC_ID_VIEW_LARGE_ICONS_NO_TOGGLE,
+            CommandId::ViewExtraLargeIconsNoToggle => {
+                EVERYTHING_IPC_ID_VIEW_EXTRA_LARGE_ICONS_NO_TOGGLE
+            }
+            CommandId::SearchToggleMatchCase => EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_CASE,
+            CommandId::SearchToggleMatchWholeWord => {
+                EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_WHOLE_WORD
+            }
+            CommandId::SearchToggleMatchPath => EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_PATH,
+            CommandId::SearchToggleRegex => EVERYTHING_IPC_ID_SEARCH_TOGGLE_REGEX,
+            CommandId::SearchToggleMatchDiacritics => {
+                EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_DIACRITICS
+            }
+            CommandId::SearchFilterAdd => EVERYTHING_IPC_ID_SEARCH_FILTER_ADD,
+            CommandId::SearchFilterOrganize => EVERYTHING_IPC_ID_SEARCH_FILTER_ORGANIZE,
+            CommandId::SearchAdvancedSearch => EVERYTHING_IPC_ID_SEARCH_ADVANCED_SEARCH,
+            CommandId::SearchEnableMatchCase => EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_CASE,
+            CommandId::SearchEnableMatchWholeWord => {
+                EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_WHOLE_WORD
+            }
+            CommandId::SearchEnableMatchPath => EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_PATH,
+            CommandId::SearchEnableRegex => EVERYTHING_IPC_ID_SEARCH_ENABLE_REGEX,
+            CommandId::SearchEnableMatchDiacritics => {
+                EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_DIACRITICS
+            }
+            CommandId::SearchDisableMatchCase => EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_CASE,
+            CommandId::SearchDisableMatchWholeWord => {
+                EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_WHOLE_WORD
+            }
+            CommandId::SearchDisableMatchPath => EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_PATH,
+            CommandId::SearchDisableRegex => EVERYTHING_IPC_ID_SEARCH_DISABLE_REGEX,
+            CommandId::SearchDisableMatchDiacritics => {
+                EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_DIACRITICS
+            }
+            CommandId::SearchFilterEverything => EVERYTHING_IPC_ID_SEARCH_FILTER_EVERYTHING,
+            CommandId::SearchFilterAudio => EVERYTHING_IPC_ID_SEARCH_FILTER_AUDIO,
+            CommandId::SearchFilterCompressed => EVERYTHING_IPC_ID_SEARCH_FILTER_COMPRESSED,
+            CommandId::SearchFilterDocument => EVERYTHING_IPC_ID_SEARCH_FILTER_DOCUMENT,
+            CommandId::SearchFilterExecutable => EVERYTHING_IPC_ID_SEARCH_FILTER_EXECUTABLE,
+            CommandId::SearchFilterFolder => EVERYTHING_IPC_ID_SEARCH_FILTER_FOLDER,
+            CommandId::SearchFilterPicture => EVERYTHING_IPC_ID_SEARCH_FILTER_PICTURE,
+            CommandId::SearchFilterVideo => EVERYTHING_IPC_ID_SEARCH_FILTER_VIDEO,
+            CommandId::SearchFilterAudioNoToggle => EVERYTHING_IPC_ID_SEARCH_FILTER_AUDIO_NO_TOGGLE,
+            CommandId::SearchFilterCompressedNoToggle => {
+                EVERYTHING_IPC_ID_SEARCH_FILTER_COMPRESSED_NO_TOGGLE
+            }
+            CommandId::SearchFilterDocumentNoToggle => {
+                EVERYTHING_IPC_ID_SEARCH_FILTER_DOCUMENT_NO_TOGGLE
+            }
+            CommandId::SearchFilterExecutableNoToggle => {
+                EVERYTHING_IPC_ID_SEARCH_FILTER_EXECUTABLE_NO_TOGGLE
+            }
+            CommandId::SearchFilterFolderNoToggle => {
+                EVERYTHING_IPC_ID_SEARCH_FILTER_FOLDER_NO_TOGGLE
+            }
+            CommandId::SearchFilterPictureNoToggle => {
+                EVERYTHING_IPC_ID_SEARCH_FILTER_PICTURE_NO_TOGGLE
+            }
+            CommandId::SearchFilterVideoNoToggle => EVERYTHING_IPC_ID_SEARCH_FILTER_VIDEO_NO_TOGGLE,
+            CommandId::ToolsConnectToEtpServer => EVERYTHING_IPC_ID_TOOLS_CONNECT_TO_ETP_SERVER,
+            CommandId::ToolsDisconnectFromEtpServer => {
+                EVERYTHING_IPC_ID_TOOLS_DISCONNECT_FROM_ETP_SERVER
+            }
+            CommandId::ToolsOptions => EVERYTHING_IPC_ID_TOOLS_OPTIONS,
+            CommandId::ToolsConsole => EVERYTHING_IPC_ID_TOOLS_CONSOLE,
+            CommandId::ToolsEditor => EVERYTHING_IPC_ID_TOOLS_EDITOR,
+            CommandId::HelpViewHelpTopics => EVERYTHING_IPC_ID_HELP_VIEW_HELP_TOPICS,
+            CommandId::HelpOpenEverythingWebsite => EVERYTHING_IPC_ID_HELP_OPEN_EVERYTHING_WEBSITE,
+            CommandId::HelpCheckForUpdates => EVERYTHING_IPC_ID_HELP_CHECK_FOR_UPDATES,
+            CommandId::HelpAboutEverything => EVERYTHING_IPC_ID_HELP_ABOUT_EVERYTHING,
+            CommandId::HelpSearchSyntax => EVERYTHING_IPC_ID_HELP_SEARCH_SYNTAX,
+            CommandId::HelpCommandLineOptions => EVERYTHING_IPC_ID_HELP_COMMAND_LINE_OPTIONS,
+            CommandId::HelpRegexSyntax => EVERYTHING_IPC_ID_HELP_REGEX_SYNTAX,
+            CommandId::HelpDonate => EVERYTHING_IPC_ID_HELP_DONATE,
+            CommandId::BookmarkAdd => EVERYTHING_IPC_ID_BOOKMARK_ADD,
+            CommandId::BookmarkOrganize => EVERYTHING_IPC_ID_BOOKMARK_ORGANIZE,
+            CommandId::FileOpen => EVERYTHING_IPC_ID_FILE_OPEN,
+            CommandId::FileOpenNew => EVERYTHING_IPC_ID_FILE_OPEN_NEW,
+            CommandId::FileOpenWith => EVERYTHING_IPC_ID_FILE_OPEN_WITH,
+            CommandId::FileEdit => EVERYTHING_IPC_ID_FILE_EDIT,
+            CommandId::FilePlay => EVERYTHING_IPC_ID_FILE_PLAY,
+            CommandId::FilePrint => EVERYTHING_IPC_ID_FILE_PRINT,
+            CommandId::FilePreview => EVERYTHING_IPC_ID_FILE_PREVIEW,
+            CommandId::FilePrintTo => EVERYTHING_IPC_ID_FILE_PRINT_TO,
+            CommandId::FileRunAs => EVERYTHING_IPC_ID_FILE_RUN_AS,
+            CommandId::FileOpenWithDefaultVerb => EVERYTHING_IPC_ID_FILE_OPEN_WITH_DEFAULT_VERB,
+            CommandId::FileOpenAndClose => EVERYTHING_IPC_ID_FILE_OPEN_AND_CLOSE,
+            CommandId::FileExplorePath => EVERYTHING_IPC_ID_FILE_EXPLORE_PATH,
+            CommandId::FileOpenPath => EVERYTHING_IPC_ID_FILE_OPEN_PATH,
+            CommandId::FileDelete => EVERYTHING_IPC_ID_FILE_DELETE,
+            CommandId::FilePermanentlyDelete => EVERYTHING_IPC_ID_FILE_PERMANENTLY_DELETE,
+            CommandId::FileRename => EVERYTHING_IPC_ID_FILE_RENAME,
+            CommandId::FileCopyFullPathAndName => EVERYTHING_IPC_ID_FILE_COPY_FULL_PATH_AND_NAME,
+            CommandId::FileCopyPath => EVERYTHING_IPC_ID_FILE_COPY_PATH,
+            CommandId::FileProperties => EVERYTHING_IPC_ID_FILE_PROPERTIES,
+            CommandId::FileReadExtendedInformation => {
+                EVERYTHING_IPC_ID_FILE_READ_EXTENDED_INFORMATION
+            }
+            CommandId::FileCreateShortcut => EVERYTHING_IPC_ID_FILE_CREATE_SHORTCUT,
+            CommandId::FileSetRunCount => EVERYTHING_IPC_ID_FILE_SET_RUN_COUNT,
+            CommandId::FileCopyName => EVERYTHING_IPC_ID_FILE_COPY_NAME,
+            CommandId::FileOpenAndDoNotClose => EVERYTHING_IPC_ID_FILE_OPEN_AND_DO_NOT_CLOSE,
+            CommandId::ResultListExplore => EVERYTHING_IPC_ID_RESULT_LIST_EXPLORE,
+            CommandId::ResultListFocus => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS,
+            CommandId::ResultListAutofitColumns => EVERYTHING_IPC_ID_RESULT_LIST_AUTOFIT_COLUMNS,
+            CommandId::ResultListDown => EVERYTHING_IPC_ID_RESULT_LIST_DOWN,
+            CommandId::ResultListUp => EVERYTHING_IPC_ID_RESULT_LIST_UP,
+            CommandId::ResultListPageUp => EVERYTHING_IPC_ID_RESULT_LIST_PAGE_UP,
+            CommandId::ResultListPageDown => EVERYTHING_IPC_ID_RESULT_LIST_PAGE_DOWN,
+            CommandId::ResultListStart => EVERYTHING_IPC_ID_RESULT_LIST_START,
+            CommandId::ResultListEnd => EVERYTHING_IPC_ID_RESULT_LIST_END,
+            CommandId::ResultListDownExtend => EVERYTHING_IPC_ID_RESULT_LIST_DOWN_EXTEND,
+            CommandId::ResultListUpExtend => EVERYTHING_IPC_ID_RESULT_LIST_UP_EXTEND,
+            CommandId::ResultListPageUpExtend => EVERYTHING_IPC_ID_RESULT_LIST_PAGE_UP_EXTEND,
+            CommandId::ResultListPageDownExtend => EVERYTHING_IPC_ID_RESULT_LIST_PAGE_DOWN_EXTEND,
+            CommandId::ResultListStartExtend => EVERYTHING_IPC_ID_RESULT_LIST_START_EXTEND,
+            CommandId::ResultListEndExtend => EVERYTHING_IPC_ID_RESULT_LIST_END_EXTEND,
+            CommandId::ResultListFocusDown => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_DOWN,
+            CommandId::ResultListFocusUp => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_UP,
+            CommandId::ResultListFocusPageUp => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_UP,
+            CommandId::ResultListFocusPageDown => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_DOWN,
+            CommandId::ResultListFocusStart => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_START,
+            CommandId::ResultListFocusEnd => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_END,
+            CommandId::ResultListScrollLeft => EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_LEFT,
+            CommandId::ResultListScrollRight => EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_RIGHT,
+            CommandId::ResultListScrollPageLeft => EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_LEFT,
+            CommandId::ResultListScrollPageRight => EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_RIGHT,
+            CommandId::ResultListSelectFocus => EVERYTHING_IPC_ID_RESULT_LIST_SELECT_FOCUS,
+            CommandId::ResultListToggleFocusSelection => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_FOCUS_SELECTION
+            }
+            CommandId::ResultListContextMenu => EVERYTHING_IPC_ID_RESULT_LIST_CONTEXT_MENU,
+            CommandId::ResultListFocusDownExtend => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_DOWN_EXTEND,
+            CommandId::ResultListFocusUpExtend => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_UP_EXTEND,
+            CommandId::ResultListFocusPageUpExtend => {
+                EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_UP_EXTEND
+            }
+            CommandId::ResultListFocusPageDownExtend => {
+                EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_DOWN_EXTEND
+            }
+            CommandId::ResultListFocusStartExtend => {
+                EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_START_EXTEND
+            }
+            CommandId::ResultListFocusEndExtend => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_END_EXTEND,
+            CommandId::ResultListAutofit => EVERYTHING_IPC_ID_RESULT_LIST_AUTOFIT,
+            CommandId::ResultListCopyCsv => EVERYTHING_IPC_ID_RESULT_LIST_COPY_CSV,
+            CommandId::ResultListLeftExtend => EVERYTHING_IPC_ID_RESULT_LIST_LEFT_EXTEND,
+            CommandId::ResultListRightExtend => EVERYTHING_IPC_ID_RESULT_LIST_RIGHT_EXTEND,
+            CommandId::ResultListFocusLeftExtend => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LEFT_EXTEND,
+            CommandId::ResultListFocusRightExtend => {
+                EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_RIGHT_EXTEND
+            }
+            CommandId::ResultListFocusMostRun => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_MOST_RUN,
+            CommandId::ResultListFocusLastRun => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LAST_RUN,
+            CommandId::ResultListLeft => EVERYTHING_IPC_ID_RESULT_LIST_LEFT,
+            CommandId::ResultListRight => EVERYTHING_IPC_ID_RESULT_LIST_RIGHT,
+            CommandId::ResultListFocusLeft => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LEFT,
+            CommandId::ResultListFocusRight => EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_RIGHT,
+            CommandId::ResultListScrollLeftScrollOnly => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_LEFT_SCROLL_ONLY
+            }
+            CommandId::ResultListScrollRightScrollOnly => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_RIGHT_SCROLL_ONLY
+            }
+            CommandId::ResultListScrollPageLeftScrollOnly => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_LEFT_SCROLL_ONLY
+            }
+            CommandId::ResultListScrollPageRightScrollOnly => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_RIGHT_SCROLL_ONLY
+            }
+            CommandId::ResultListSortByName => EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_NAME,
+            CommandId::ResultListSortByPath => EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_PATH,
+            CommandId::ResultListSortBySize => EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_SIZE,
+            CommandId::ResultListSortByExtension => EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_EXTENSION,
+            CommandId::ResultListSortByType => EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_TYPE,
+            CommandId::ResultListSortByDateModified => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_MODIFIED
+            }
+            CommandId::ResultListSortByDateCreated => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_CREATED
+            }
+            CommandId::ResultListSortByAttributes => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_ATTRIBUTES
+            }
+            CommandId::ResultListSortByFileListFilename => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_FILE_LIST_FILENAME
+            }
+            CommandId::ResultListSortByRunCount => EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_RUN_COUNT,
+            CommandId::ResultListSortByDateRecentlyChanged => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_RECENTLY_CHANGED
+            }
+            CommandId::ResultListSortByDateAccessed => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_ACCESSED
+            }
+            CommandId::ResultListSortByDateRun => EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_RUN,
+            CommandId::ResultListToggleNameColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_NAME_COLUMN
+            }
+            CommandId::ResultListTogglePathColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_PATH_COLUMN
+            }
+            CommandId::ResultListToggleSizeColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_SIZE_COLUMN
+            }
+            CommandId::ResultListToggleExtensionColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_EXTENSION_COLUMN
+            }
+            CommandId::ResultListToggleTypeColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_TYPE_COLUMN
+            }
+            CommandId::ResultListToggleDateModifiedColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_MODIFIED_COLUMN
+            }
+            CommandId::ResultListToggleDateCreatedColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_CREATED_COLUMN
+            }
+            CommandId::ResultListToggleAttributesColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_ATTRIBUTES_COLUMN
+            }
+            CommandId::ResultListToggleFileListFilenameColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_FILE_LIST_FILENAME_COLUMN
+            }
+            CommandId::ResultListToggleRunCountColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_RUN_COUNT_COLUMN
+            }
+            CommandId::ResultListToggleDateRecentlyChangedColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_RECENTLY_CHANGED_COLUMN
+            }
+            CommandId::ResultListToggleDateAccessedColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_ACCESSED_COLUMN
+            }
+            CommandId::ResultListToggleDateRunColumn => {
+                EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_RUN_COLUMN
+            }
+            CommandId::ResultListSizeNameColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_NAME_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizePathColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_PATH_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeSizeColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_SIZE_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeExtensionColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_EXTENSION_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeTypeColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_TYPE_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeDateModifiedColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_MODIFIED_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeDateCreatedColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_CREATED_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeAttributesColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_ATTRIBUTES_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeFileListFilenameColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_FILE_LIST_FILENAME_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeRunCountColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_RUN_COUNT_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeDateRecentlyChangedColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_RECENTLY_CHANGED_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeDateAccessedColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_ACCESSED_COLUMN_TO_FIT
+            }
+            CommandId::ResultListSizeDateRunColumnToFit => {
+                EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_RUN_COLUMN_TO_FIT
+            }
+            CommandId::FileCustomVerb01 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB01,
+            CommandId::FileCustomVerb02 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB02,
+            CommandId::FileCustomVerb03 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB03,
+            CommandId::FileCustomVerb04 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB04,
+            CommandId::FileCustomVerb05 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB05,
+            CommandId::FileCustomVerb06 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB06,
+            CommandId::FileCustomVerb07 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB07,
+            CommandId::FileCustomVerb08 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB08,
+            CommandId::FileCustomVerb09 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB09,
+            CommandId::FileCustomVerb10 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB10,
+            CommandId::FileCustomVerb11 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB11,
+            CommandId::FileCustomVerb12 => EVERYTHING_IPC_ID_FILE_CUSTOM_VERB12,
+            CommandId::SearchEditFocus => EVERYTHING_IPC_ID_SEARCH_EDIT_FOCUS,
+            CommandId::SearchEditWordDeleteToStart => {
+                EVERYTHING_IPC_ID_SEARCH_EDIT_WORD_DELETE_TO_START
+            }
+            CommandId::SearchEditAutoComplete => EVERYTHING_IPC_ID_SEARCH_EDIT_AUTO_COMPLETE,
+            CommandId::SearchEditShowSearchHistory => {
+                EVERYTHING_IPC_ID_SEARCH_EDIT_SHOW_SEARCH_HISTORY
+            }
+            CommandId::SearchEditShowAllSearchHistory => {
+                EVERYTHING_IPC_ID_SEARCH_EDIT_SHOW_ALL_SEARCH_HISTORY
+            }
+            CommandId::TrayEditor => EVERYTHING_IPC_ID_TRAY_EDITOR,
+            CommandId::TrayOpenFilelist => EVERYTHING_IPC_ID_TRAY_OPEN_FILELIST,
+            CommandId::IndexUpdateAllFoldersNow => EVERYTHING_IPC_ID_INDEX_UPDATE_ALL_FOLDERS_NOW,
+            CommandId::IndexForceRebuild => EVERYTHING_IPC_ID_INDEX_FORCE_REBUILD,
+            CommandId::BookmarkItem(index) => {
+                return item(
+                    EVERYTHING_IPC_ID_BOOKMARK_START,
+                    EVERYTHING_IPC_ID_BOOKMARK_END,
+                    index,
+                )
+            }
+            CommandId::FilterItem(index) => {
+                return item(
+                    EVERYTHING_IPC_ID_FILTER_START,
+                    EVERYTHING_IPC_ID_FILTER_END,
+                    index,
+                )
+            }
+            CommandId::ViewGotoItem(index) => {
+                return item(
+                    EVERYTHING_IPC_ID_VIEW_GOTO_START,
+                    EVERYTHING_IPC_ID_VIEW_GOTO_END,
+                    index,
+                )
+            }
+        })
+    }
+}
+
+/// Fails with `EverythingSDKError::InvalidParameter` for an ID the header does not define.
+impl TryFrom<u32> for CommandId {
+    type Error = EverythingError;
+
+    fn try_from(id: u32) -> Result<Self, Self::Error> {
+        Ok(match id {
+            EVERYTHING_IPC_ID_FILE_MENU => CommandId::FileMenu,
+            EVERYTHING_IPC_ID_EDIT_MENU => CommandId::EditMenu,
+            EVERYTHING_IPC_ID_SEARCH_MENU => CommandId::SearchMenu,
+            EVERYTHING_IPC_ID_TOOLS_MENU => CommandId::ToolsMenu,
+            EVERYTHING_IPC_ID_HELP_MENU => CommandId::HelpMenu,
+            EVERYTHING_IPC_ID_TOOLBAR => CommandId::Toolbar,
+            EVERYTHING_IPC_ID_SEARCH_EDIT => CommandId::SearchEdit,
+            EVERYTHING_IPC_ID_FILTER => CommandId::Filter,
+            EVERYTHING_IPC_ID_RESULTS_HEADER => CommandId::ResultsHeader,
+            EVERYTHING_IPC_ID_STATUS => CommandId::Status,
+            EVERYTHING_IPC_ID_VIEW_ZOOM_MENU => CommandId::ViewZoomMenu,
+            EVERYTHING_IPC_ID_VIEW_MENU => CommandId::ViewMenu,
+            EVERYTHING_IPC_ID_VIEW_WINDOW_SIZE_MENU => CommandId::ViewWindowSizeMenu,
+            EVERYTHING_IPC_ID_RESULT_LIST => CommandId::ResultList,
+            EVERYTHING_IPC_ID_BOOKMARKS_MENU => CommandId::BookmarksMenu,
+            EVERYTHING_IPC_ID_VIEW_SORT_BY_MENU => CommandId::ViewSortByMenu,
+            EVERYTHING_IPC_ID_VIEW_GOTO_MENU => CommandId::ViewGotoMenu,
+            EVERYTHING_IPC_ID_VIEW_ONTOP_MENU => CommandId::ViewOntopMenu,
+            EVERYTHING_IPC_ID_PREVIEW => CommandId::Preview,
+            EVERYTHING_IPC_ID_TRAY_NEW_SEARCH_WINDOW => CommandId::TrayNewSearchWindow,
+            EVERYTHING_IPC_ID_TRAY_CONNECT_TO_ETP_SERVER => CommandId::TrayConnectToEtpServer,
+            EVERYTHING_IPC_ID_TRAY_OPTIONS => CommandId::TrayOptions,
+            EVERYTHING_IPC_ID_TRAY_EXIT => CommandId::TrayExit,
+            EVERYTHING_IPC_ID_TRAY_SHOW_SEARCH_WINDOW => CommandId::TrayShowSearchWindow,
+            EVERYTHING_IPC_ID_TRAY_TOGGLE_SEARCH_WINDOW => CommandId::TrayToggleSearchWindow,
+            EVERYTHING_IPC_ID_FILE_NEW_WINDOW => CommandId::FileNewWindow,
+            EVERYTHING_IPC_ID_FILE_CLOSE => CommandId::FileClose,
+            EVERYTHING_IPC_ID_FILE_EXPORT => CommandId::FileExport,
+            EVERYTHING_IPC_ID_FILE_EXIT => CommandId::FileExit,
+            EVERYTHING_IPC_ID_FILE_OPEN_FILELIST => CommandId::FileOpenFilelist,
+            EVERYTHING_IPC_ID_FILE_CLOSE_FILELIST => CommandId::FileCloseFilelist,
+            EVERYTHING_IPC_ID_EDIT_CUT => CommandId::EditCut,
+            EVERYTHING_IPC_ID_EDIT_COPY => CommandId::EditCopy,
+            EVERYTHING_IPC_ID_EDIT_PASTE => CommandId::EditPaste,
+            EVERYTHING_IPC_ID_EDIT_SELECT_ALL => CommandId::EditSelectAll,
+            EVERYTHING_IPC_ID_EDIT_INVERT_SELECTION => CommandId::EditInvertSelection,
+            EVERYTHING_IPC_ID_VIEW_ZOOM_IN => CommandId::ViewZoomIn,
+            EVERYTHING_IPC_ID_VIEW_ZOOM_OUT => CommandId::ViewZoomOut,
+            EVERYTHING_IPC_ID_VIEW_ZOOM_RESET => CommandId::ViewZoomReset,
+            EVERYTHING_IPC_ID_VIEW_TOGGLE_FULLSCREEN => CommandId::ViewToggleFullscreen,
+            EVERYTHING_IPC_ID_VIEW_AUTO_FIT => CommandId::ViewAutoFit,
+            EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_1 => CommandId::ViewAutoSize1,
+            EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_2 => CommandId::ViewAutoSize2,
+            EVERYTHING_IPC_ID_VIEW_AUTO_SIZE_3 => CommandId::ViewAutoSize3,
+            EVERYTHING_IPC_ID_VIEW_REFRESH => CommandId::ViewRefresh,
+            EVERYTHING_IPC_ID_VIEW_FILTERS => CommandId::ViewFilters,
+            EVERYTHING_IPC_ID_VIEW_SORT_BY_ASCENDING => CommandId::ViewSortByAscending,
+            EVERYTHING_IPC_ID_VIEW_SORT_BY_DESCENDING => CommandId::ViewSortByDescending,
+            EVERYTHING_IPC_ID_VIEW_STATUS_BAR => CommandId::ViewStatusBar,
+            EVERYTHING_IPC_ID_VIEW_GOTO_BACK => CommandId::ViewGotoBack,
+            EVERYTHING_IPC_ID_VIEW_GOTO_FORWARD => CommandId::ViewGotoForward,
+            EVERYTHING_IPC_ID_VIEW_ONTOP_NEVER => CommandId::ViewOntopNever,
+            EVERYTHING_IPC_ID_VIEW_ONTOP_ALWAYS => CommandId::ViewOntopAlways,
+            EVERYTHING_IPC_ID_VIEW_ONTOP_WHILE_SEARCHING => CommandId::ViewOntopWhileSearching,
+            EVERYTHING_IPC_ID_VIEW_GOTO_HOME => CommandId::ViewGotoHome,
+            EVERYTHING_IPC_ID_VIEW_TOGGLE_LTR_RTL => CommandId::ViewToggleLtrRtl,
+            EVERYTHING_IPC_ID_VIEW_DETAILS => CommandId::ViewDetails,
+            EVERYTHING_IPC_ID_VIEW_MEDIUM_ICONS => CommandId::ViewMediumIcons,
+            EVERYTHING_IPC_ID_VIEW_LARGE_ICONS => CommandId::ViewLargeIcons,
+            EVERYTHING_IPC_ID_VIEW_EXTRA_LARGE_ICONS => CommandId::ViewExtraLargeIcons,
+            EVERYTHING_IPC_ID_VIEW_PREVIEW => CommandId::ViewPreview,
+            EVERYTHING_IPC_ID_VIEW_GOTO_SHOW_ALL_HISTORY => CommandId::ViewGotoShowAllHistory,
+            EVERYTHING_IPC_ID_VIEW_INCREASE_THUMBNAIL_SIZE => CommandId::ViewIncreaseThumbnailSize,
+            EVERYTHING_IPC_ID_VIEW_DECREASE_THUMBNAIL_SIZE => CommandId::ViewDecreaseThumbnailSize,
+            EVERYTHING_IPC_ID_VIEW_SHOW_FILTERS => CommandId::ViewShowFilters,
+            EVERYTHING_IPC_ID_VIEW_HIDE_FILTERS => CommandId::ViewHideFilters,
+            EVERYTHING_IPC_ID_VIEW_SHOW_PREVIEW => CommandId::ViewShowPreview,
+            EVERYTHING_IPC_ID_VIEW_HIDE_PREVIEW => CommandId::ViewHidePreview,
+            EVERYTHING_IPC_ID_VIEW_SHOW_STATUS_BAR => CommandId::ViewShowStatusBar,
+            EVERYTHING_IPC_ID_VIEW_HIDE_STATUS_BAR => CommandId::ViewHideStatusBar,
+            EVERYTHING_IPC_ID_VIEW_DETAILS_NO_TOGGLE => CommandId::ViewDetailsNoToggle,
+            EVERYTHING_IPC_ID_VIEW_MEDIUM_ICONS_NO_TOGGLE => CommandId::ViewMediumIconsNoToggle,
+            EVERYTHING_IPC_ID_VIEW_LARGE_ICONS_NO_TOGGLE => CommandId::ViewLargeIconsNoToggle,
+            EVERYTHING_IPC_ID_VIEW_EXTRA_LARGE_ICONS_NO_TOGGLE => {
+                CommandId::ViewExtraLargeIconsNoToggle
+            }
+            EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_CASE => CommandId::SearchToggleMatchCase,
+            EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_WHOLE_WORD => {
+                CommandId::SearchToggleMatchWholeWord
+            }
+            EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_PATH => CommandId::SearchToggleMatchPath,
+            EVERYTHING_IPC_ID_SEARCH_TOGGLE_REGEX => CommandId::SearchToggleRegex,
+            EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_DIACRITICS => {
+                CommandId::SearchToggleMatchDiacritics
+            }
+            EVERYTHING_IPC_ID_SEARCH_FILTER_ADD => CommandId::SearchFilterAdd,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_ORGANIZE => CommandId::SearchFilterOrganize,
+            EVERYTHING_IPC_ID_SEARCH_ADVANCED_SEARCH => CommandId::SearchAdvancedSearch,
+            EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_CASE => CommandId::SearchEnableMatchCase,
+            EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_WHOLE_WORD => {
+                CommandId::SearchEnableMatchWholeWord
+            }
+            EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_PATH => CommandId::SearchEnableMatchPath,
+            EVERYTHING_IPC_ID_SEARCH_ENABLE_REGEX => CommandId::SearchEnableRegex,
+            EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_DIACRITICS => {
+                CommandId::SearchEnableMatchDiacritics
+            }
+            EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_CASE => CommandId::SearchDisableMatchCase,
+            EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_WHOLE_WORD => {
+                CommandId::SearchDisableMatchWholeWord
+            }
+            EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_PATH => CommandId::SearchDisableMatchPath,
+            EVERYTHING_IPC_ID_SEARCH_DISABLE_REGEX => CommandId::SearchDisableRegex,
+            EVERYTHING_IPC_ID_SEARCH_DISABLE_MATCH_DIACRITICS => {
+                CommandId::SearchDisableMatchDiacritics
+            }
+            EVERYTHING_IPC_ID_SEARCH_FILTER_EVERYTHING => CommandId::SearchFilterEverything,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_AUDIO => CommandId::SearchFilterAudio,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_COMPRESSED => CommandId::SearchFilterCompressed,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_DOCUMENT => CommandId::SearchFilterDocument,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_EXECUTABLE => CommandId::SearchFilterExecutable,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_FOLDER => CommandId::SearchFilterFolder,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_PICTURE => CommandId::SearchFilterPicture,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_VIDEO => CommandId::SearchFilterVideo,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_AUDIO_NO_TOGGLE => CommandId::SearchFilterAudioNoToggle,
+            EVERYTHING_IPC_ID_SEARCH_FILTER_COMPRESSED_NO_TOGGLE => {
+                CommandId::SearchFilterCompressedNoToggle
+            }
+            EVERYTHING_IPC_ID_SEARCH_FILTER_DOCUMENT_NO_TOGGLE => {
+                CommandId::SearchFilterDocumentNoToggle
+            }
+            EVERYTHING_IPC_ID_SEARCH_FILTER_EXECUTABLE_NO_TOGGLE => {
+                CommandId::SearchFilterExecutableNoToggle
+            }
+            EVERYTHING_IPC_ID_SEARCH_FILTER_FOLDER_NO_TOGGLE => {
+                CommandId::SearchFilterFolderNoToggle
+            }
+            EVERYTHING_IPC_ID_SEARCH_FILTER_PICTURE_NO_TOGGLE => {
+                CommandId::SearchFilterPictureNoToggle
+            }
+            EVERYTHING_IPC_ID_SEARCH_FILTER_VIDEO_NO_TOGGLE => CommandId::SearchFilterVideoNoToggle,
+            EVERYTHING_IPC_ID_TOOLS_CONNECT_TO_ETP_SERVER => CommandId::ToolsConnectToEtpServer,
+            EVERYTHING_IPC_ID_TOOLS_DISCONNECT_FROM_ETP_SERVER => {
+                CommandId::ToolsDisconnectFromEtpServer
+            }
+            EVERYTHING_IPC_ID_TOOLS_OPTIONS => CommandId::ToolsOptions,
+            EVERYTHING_IPC_ID_TOOLS_CONSOLE => CommandId::ToolsConsole,
+            EVERYTHING_IPC_ID_TOOLS_EDITOR => CommandId::ToolsEditor,
+            EVERYTHING_IPC_ID_HELP_VIEW_HELP_TOPICS => CommandId::HelpViewHelpTopics,
+            EVERYTHING_IPC_ID_HELP_OPEN_EVERYTHING_WEBSITE => CommandId::HelpOpenEverythingWebsite,
+            EVERYTHING_IPC_ID_HELP_CHECK_FOR_UPDATES => CommandId::HelpCheckForUpdates,
+            EVERYTHING_IPC_ID_HELP_ABOUT_EVERYTHING => CommandId::HelpAboutEverything,
+            EVERYTHING_IPC_ID_HELP_SEARCH_SYNTAX => CommandId::HelpSearchSyntax,
+            EVERYTHING_IPC_ID_HELP_COMMAND_LINE_OPTIONS => CommandId::HelpCommandLineOptions,
+            EVERYTHING_IPC_ID_HELP_REGEX_SYNTAX => CommandId::HelpRegexSyntax,
+            EVERYTHING_IPC_ID_HELP_DONATE => CommandId::HelpDonate,
+            EVERYTHING_IPC_ID_BOOKMARK_ADD => CommandId::BookmarkAdd,
+            EVERYTHING_IPC_ID_BOOKMARK_ORGANIZE => CommandId::BookmarkOrganize,
+            EVERYTHING_IPC_ID_FILE_OPEN => CommandId::FileOpen,
+            EVERYTHING_IPC_ID_FILE_OPEN_NEW => CommandId::FileOpenNew,
+            EVERYTHING_IPC_ID_FILE_OPEN_WITH => CommandId::FileOpenWith,
+            EVERYTHING_IPC_ID_FILE_EDIT => CommandId::FileEdit,
+            EVERYTHING_IPC_ID_FILE_PLAY => CommandId::FilePlay,
+            EVERYTHING_IPC_ID_FILE_PRINT => CommandId::FilePrint,
+            EVERYTHING_IPC_ID_FILE_PREVIEW => CommandId::FilePreview,
+            EVERYTHING_IPC_ID_FILE_PRINT_TO => CommandId::FilePrintTo,
+            EVERYTHING_IPC_ID_FILE_RUN_AS => CommandId::FileRunAs,
+            EVERYTHING_IPC_ID_FILE_OPEN_WITH_DEFAULT_VERB => CommandId::FileOpenWithDefaultVerb,
+            EVERYTHING_IPC_ID_FILE_OPEN_AND_CLOSE => CommandId::FileOpenAndClose,
+            EVERYTHING_IPC_ID_FILE_EXPLORE_PATH => CommandId::FileExplorePath,
+            EVERYTHING_IPC_ID_FILE_OPEN_PATH => CommandId::FileOpenPath,
+            EVERYTHING_IPC_ID_FILE_DELETE => CommandId::FileDelete,
+            EVERYTHING_IPC_ID_FILE_PERMANENTLY_DELETE => CommandId::FilePermanentlyDelete,
+            EVERYTHING_IPC_ID_FILE_RENAME => CommandId::FileRename,
+            EVERYTHING_IPC_ID_FILE_COPY_FULL_PATH_AND_NAME => CommandId::FileCopyFullPathAndName,
+            EVERYTHING_IPC_ID_FILE_COPY_PATH => CommandId::FileCopyPath,
+            EVERYTHING_IPC_ID_FILE_PROPERTIES => CommandId::FileProperties,
+            EVERYTHING_IPC_ID_FILE_READ_EXTENDED_INFORMATION => {
+                CommandId::FileReadExtendedInformation
+            }
+            EVERYTHING_IPC_ID_FILE_CREATE_SHORTCUT => CommandId::FileCreateShortcut,
+            EVERYTHING_IPC_ID_FILE_SET_RUN_COUNT => CommandId::FileSetRunCount,
+            EVERYTHING_IPC_ID_FILE_COPY_NAME => CommandId::FileCopyName,
+            EVERYTHING_IPC_ID_FILE_OPEN_AND_DO_NOT_CLOSE => CommandId::FileOpenAndDoNotClose,
+            EVERYTHING_IPC_ID_RESULT_LIST_EXPLORE => CommandId::ResultListExplore,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS => CommandId::ResultListFocus,
+            EVERYTHING_IPC_ID_RESULT_LIST_AUTOFIT_COLUMNS => CommandId::ResultListAutofitColumns,
+            EVERYTHING_IPC_ID_RESULT_LIST_DOWN => CommandId::ResultListDown,
+            EVERYTHING_IPC_ID_RESULT_LIST_UP => CommandId::ResultListUp,
+            EVERYTHING_IPC_ID_RESULT_LIST_PAGE_UP => CommandId::ResultListPageUp,
+            EVERYTHING_IPC_ID_RESULT_LIST_PAGE_DOWN => CommandId::ResultListPageDown,
+            EVERYTHING_IPC_ID_RESULT_LIST_START => CommandId::ResultListStart,
+            EVERYTHING_IPC_ID_RESULT_LIST_END => CommandId::ResultListEnd,
+            EVERYTHING_IPC_ID_RESULT_LIST_DOWN_EXTEND => CommandId::ResultListDownExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_UP_EXTEND => CommandId::ResultListUpExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_PAGE_UP_EXTEND => CommandId::ResultListPageUpExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_PAGE_DOWN_EXTEND => CommandId::ResultListPageDownExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_START_EXTEND => CommandId::ResultListStartExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_END_EXTEND => CommandId::ResultListEndExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_DOWN => CommandId::ResultListFocusDown,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_UP => CommandId::ResultListFocusUp,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_UP => CommandId::ResultListFocusPageUp,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_DOWN => CommandId::ResultListFocusPageDown,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_START => CommandId::ResultListFocusStart,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_END => CommandId::ResultListFocusEnd,
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_LEFT => CommandId::ResultListScrollLeft,
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_RIGHT => CommandId::ResultListScrollRight,
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_LEFT => CommandId::ResultListScrollPageLeft,
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_RIGHT => CommandId::ResultListScrollPageRight,
+            EVERYTHING_IPC_ID_RESULT_LIST_SELECT_FOCUS => CommandId::ResultListSelectFocus,
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_FOCUS_SELECTION => {
+                CommandId::ResultListToggleFocusSelection
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_CONTEXT_MENU => CommandId::ResultListContextMenu,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_DOWN_EXTEND => CommandId::ResultListFocusDownExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_UP_EXTEND => CommandId::ResultListFocusUpExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_UP_EXTEND => {
+                CommandId::ResultListFocusPageUpExtend
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_PAGE_DOWN_EXTEND => {
+                CommandId::ResultListFocusPageDownExtend
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_START_EXTEND => {
+                CommandId::ResultListFocusStartExtend
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_END_EXTEND => CommandId::ResultListFocusEndExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_AUTOFIT => CommandId::ResultListAutofit,
+            EVERYTHING_IPC_ID_RESULT_LIST_COPY_CSV => CommandId::ResultListCopyCsv,
+            EVERYTHING_IPC_ID_RESULT_LIST_LEFT_EXTEND => CommandId::ResultListLeftExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_RIGHT_EXTEND => CommandId::ResultListRightExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LEFT_EXTEND => CommandId::ResultListFocusLeftExtend,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_RIGHT_EXTEND => {
+                CommandId::ResultListFocusRightExtend
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_MOST_RUN => CommandId::ResultListFocusMostRun,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LAST_RUN => CommandId::ResultListFocusLastRun,
+            EVERYTHING_IPC_ID_RESULT_LIST_LEFT => CommandId::ResultListLeft,
+            EVERYTHING_IPC_ID_RESULT_LIST_RIGHT => CommandId::ResultListRight,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_LEFT => CommandId::ResultListFocusLeft,
+            EVERYTHING_IPC_ID_RESULT_LIST_FOCUS_RIGHT => CommandId::ResultListFocusRight,
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_LEFT_SCROLL_ONLY => {
+                CommandId::ResultListScrollLeftScrollOnly
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_RIGHT_SCROLL_ONLY => {
+                CommandId::ResultListScrollRightScrollOnly
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_LEFT_SCROLL_ONLY => {
+                CommandId::ResultListScrollPageLeftScrollOnly
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SCROLL_PAGE_RIGHT_SCROLL_ONLY => {
+                CommandId::ResultListScrollPageRightScrollOnly
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_NAME => CommandId::ResultListSortByName,
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_PATH => CommandId::ResultListSortByPath,
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_SIZE => CommandId::ResultListSortBySize,
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_EXTENSION => CommandId::ResultListSortByExtension,
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_TYPE => CommandId::ResultListSortByType,
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_MODIFIED => {
+                CommandId::ResultListSortByDateModified
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_CREATED => {
+                CommandId::ResultListSortByDateCreated
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_ATTRIBUTES => {
+                CommandId::ResultListSortByAttributes
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_FILE_LIST_FILENAME => {
+                CommandId::ResultListSortByFileListFilename
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_RUN_COUNT => CommandId::ResultListSortByRunCount,
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_RECENTLY_CHANGED => {
+                CommandId::ResultListSortByDateRecentlyChanged
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_ACCESSED => {
+                CommandId::ResultListSortByDateAccessed
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SORT_BY_DATE_RUN => CommandId::ResultListSortByDateRun,
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_NAME_COLUMN => {
+                CommandId::ResultListToggleNameColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_PATH_COLUMN => {
+                CommandId::ResultListTogglePathColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_SIZE_COLUMN => {
+                CommandId::ResultListToggleSizeColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_EXTENSION_COLUMN => {
+                CommandId::ResultListToggleExtensionColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_TYPE_COLUMN => {
+                CommandId::ResultListToggleTypeColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_MODIFIED_COLUMN => {
+                CommandId::ResultListToggleDateModifiedColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_CREATED_COLUMN => {
+                CommandId::ResultListToggleDateCreatedColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_ATTRIBUTES_COLUMN => {
+                CommandId::ResultListToggleAttributesColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_FILE_LIST_FILENAME_COLUMN => {
+                CommandId::ResultListToggleFileListFilenameColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_RUN_COUNT_COLUMN => {
+                CommandId::ResultListToggleRunCountColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_RECENTLY_CHANGED_COLUMN => {
+                CommandId::ResultListToggleDateRecentlyChangedColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_ACCESSED_COLUMN => {
+                CommandId::ResultListToggleDateAccessedColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_TOGGLE_DATE_RUN_COLUMN => {
+                CommandId::ResultListToggleDateRunColumn
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_NAME_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeNameColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_PATH_COLUMN_TO_FIT => {
+                CommandId::ResultListSizePathColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_SIZE_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeSizeColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_EXTENSION_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeExtensionColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_TYPE_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeTypeColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_MODIFIED_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeDateModifiedColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_CREATED_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeDateCreatedColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_ATTRIBUTES_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeAttributesColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_FILE_LIST_FILENAME_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeFileListFilenameColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_RUN_COUNT_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeRunCountColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_RECENTLY_CHANGED_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeDateRecentlyChangedColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_ACCESSED_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeDateAccessedColumnToFit
+            }
+            EVERYTHING_IPC_ID_RESULT_LIST_SIZE_DATE_RUN_COLUMN_TO_FIT => {
+                CommandId::ResultListSizeDateRunColumnToFit
+            }
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB01 => CommandId::FileCustomVerb01,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB02 => CommandId::FileCustomVerb02,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB03 => CommandId::FileCustomVerb03,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB04 => CommandId::FileCustomVerb04,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB05 => CommandId::FileCustomVerb05,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB06 => CommandId::FileCustomVerb06,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB07 => CommandId::FileCustomVerb07,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB08 => CommandId::FileCustomVerb08,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB09 => CommandId::FileCustomVerb09,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB10 => CommandId::FileCustomVerb10,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB11 => CommandId::FileCustomVerb11,
+            EVERYTHING_IPC_ID_FILE_CUSTOM_VERB12 => CommandId::FileCustomVerb12,
+            EVERYTHING_IPC_ID_SEARCH_EDIT_FOCUS => CommandId::SearchEditFocus,
+            EVERYTHING_IPC_ID_SEARCH_EDIT_WORD_DELETE_TO_START => {
+                CommandId::SearchEditWordDeleteToStart
+            }
+            EVERYTHING_IPC_ID_SEARCH_EDIT_AUTO_COMPLETE => CommandId::SearchEditAutoComplete,
+            EVERYTHING_IPC_ID_SEARCH_EDIT_SHOW_SEARCH_HISTORY => {
+                CommandId::SearchEditShowSearchHistory
+            }
+            EVERYTHING_IPC_ID_SEARCH_EDIT_SHOW_ALL_SEARCH_HISTORY => {
+                CommandId::SearchEditShowAllSearchHistory
+            }
+            EVERYTHING_IPC_ID_TRAY_EDITOR => CommandId::TrayEditor,
+            EVERYTHING_IPC_ID_TRAY_OPEN_FILELIST => CommandId::TrayOpenFilelist,
+            EVERYTHING_IPC_ID_INDEX_UPDATE_ALL_FOLDERS_NOW => CommandId::IndexUpdateAllFoldersNow,
+            EVERYTHING_IPC_ID_INDEX_FORCE_REBUILD => CommandId::IndexForceRebuild,
+            EVERYTHING_IPC_ID_BOOKMARK_START..EVERYTHING_IPC_ID_BOOKMARK_END => {
+                CommandId::BookmarkItem(id - EVERYTHING_IPC_ID_BOOKMARK_START)
+            }
+            EVERYTHING_IPC_ID_FILTER_START..EVERYTHING_IPC_ID_FILTER_END => {
+                CommandId::FilterItem(id - EVERYTHING_IPC_ID_FILTER_START)
+            }
+            EVERYTHING_IPC_ID_VIEW_GOTO_START..EVERYTHING_IPC_ID_VIEW_GOTO_END => {
+                CommandId::ViewGotoItem(id - EVERYTHING_IPC_ID_VIEW_GOTO_START)
+            }
+            _ => {
+                return Err(EverythingError::SDKError(
+                    EverythingSDKError::InvalidParameter,
+                ))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_ids() {
+        let mut known = 0;
+        for id in 10000..47000 {
+            if let Ok(command) = CommandId::try_from(id) {
+                assert_eq!(command.id(), Some(id));
+                known += 1;
+            }
+        }
+        assert_eq!(known, 256 + 3 * 1000);
+
+        assert_eq!(
+            CommandId::try_from(EVERYTHING_IPC_ID_SEARCH_TOGGLE_MATCH_CASE).unwrap(),
+            CommandId::SearchToggleMatchCase
+        );
+        assert_eq!(CommandId::FilterItem(2).id(), Some(45002));
+        assert_eq!(CommandId::BookmarkItem(1000).id(), None);
+        assert_eq!(CommandId::ViewGotoItem(u32::MAX).id(), None);
+        assert!(CommandId::try_from(47000).is_err());
+    }
+}
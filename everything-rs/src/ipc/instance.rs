@@ -0,0 +1,59 @@
+use super::EVERYTHING_IPC_WNDCLASS;
+
+/// Returns the class of the window an Everything instance receives IPC messages on,
+/// `instance` is the name passed to `-instance`. The unnamed instance uses `EVERYTHING_IPC_WNDCLASS`.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::ipc::window_class;
+///
+/// assert_eq!(window_class(None), "EVERYTHING_TASKBAR_NOTIFICATION");
+/// assert_eq!(window_class(Some("1.5a")), "EVERYTHING_TASKBAR_NOTIFICATION_(1.5a)");
+/// ```
+pub fn window_class(instance: Option<&str>) -> String {
+    instance_class(EVERYTHING_IPC_WNDCLASS, instance)
+}
+
+/// Appends the instance suffix Everything gives its window classes to `class`.
+pub(crate) fn instance_class(class: &str, instance: Option<&str>) -> String {
+    match instance {
+        Some(instance) => format!("{}_({})", class, instance),
+        None => class.to_string(),
+    }
+}
+
+/// The inverse of `window_class`, returns the instance a window of `class` belongs to
+/// or `None` if it is not an Everything IPC window. `Some(None)` is the unnamed instance.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn parse_window_class(class: &str) -> Option<Option<&str>> {
+    let suffix = class.strip_prefix(EVERYTHING_IPC_WNDCLASS)?;
+    if suffix.is_empty() {
+        return Some(None);
+    }
+    let instance = suffix.strip_prefix("_(")?.strip_suffix(')')?;
+    Some(Some(instance))
+}
+
+/// Sorts and deduplicates discovered instances, the unnamed instance first.
+pub(crate) fn sort_instances(instances: &mut Vec<Option<String>>) {
+    instances.sort();
+    instances.dedup();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_window_classes() {
+        for instance in [None, Some("1.5a"), Some("build (x64)"), Some("")] {
+            assert_eq!(parse_window_class(&window_class(instance)), Some(instance));
+        }
+        assert_eq!(parse_window_class("EVERYTHING"), None);
+        assert_eq!(parse_window_class("EVERYTHING_TASKBAR_NOTIFICATIONS"), None);
+        assert_eq!(
+            parse_window_class("EVERYTHING_TASKBAR_NOTIFICATION_(1.5a"),
+            None
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use super::wire::{Reader, Writer};
+use super::{IpcCodecError, IpcItemFlags, IpcSearchFlags, Item2, List2, EVERYTHING_IPC_ALLRESULTS};
+use crate::{EverythingRequestFlags, EverythingSort};
+
+/// Size of the fixed part of `EVERYTHING_IPC_QUERYW`.
+const QUERY1_HEADER_SIZE: usize = 5 * 4;
+/// Size of the fixed part of `EVERYTHING_IPC_LISTW`.
+const LIST1_HEADER_SIZE: usize = 7 * 4;
+/// Size of `EVERYTHING_IPC_ITEMW`.
+const ITEM1_SIZE: usize = 3 * 4;
+
+/// Whether a query with `request_flags` and `sort` needs `EVERYTHING_IPC_QUERY2`.
+/// The version 1 reply only carries the name and path of each result in the order Everything sorts by name,
+/// so like the SDK's `_Everything_ShouldUseVersion2` any other request flags or sort need version 2.
+pub fn needs_query2(request_flags: EverythingRequestFlags, sort: EverythingSort) -> bool {
+    request_flags != EverythingRequestFlags::FileName | EverythingRequestFlags::Path
+        || sort != EverythingSort::NameAscending
+}
+
+/// `EVERYTHING_IPC_QUERYW`, the version 1 search request understood by every Everything version.
+/// The search string follows the struct as null terminated UTF-16.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query1 {
+    /// The window that will receive the results. Only the low 32 bits of a `HWND` are sent.
+    pub reply_hwnd: u32,
+    /// Echoed back as the `dwData` of the reply `COPYDATASTRUCT`.
+    pub reply_copydata_message: u32,
+    pub search_flags: IpcSearchFlags,
+    pub offset: u32,
+    /// `EVERYTHING_IPC_ALLRESULTS` to return every result.
+    pub max_results: u32,
+    pub search: String,
+}
+
+impl Default for Query1 {
+    /// The same defaults as the SDK: every result.
+    fn default() -> Self {
+        Query1 {
+            reply_hwnd: 0,
+            reply_copydata_message: 0,
+            search_flags: IpcSearchFlags::empty(),
+            offset: 0,
+            max_results: EVERYTHING_IPC_ALLRESULTS,
+            search: String::new(),
+        }
+    }
+}
+
+impl Query1 {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.u32(self.reply_hwnd);
+        writer.u32(self.reply_copydata_message);
+        writer.u32(self.search_flags.bits());
+        writer.u32(self.offset);
+        writer.u32(self.max_results);
+        debug_assert_eq!(writer.len(), QUERY1_HEADER_SIZE);
+        writer.terminated_utf16(&self.search);
+        writer.into_bytes()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Query1, IpcCodecError> {
+        let mut reader = Reader::new(bytes);
+        Ok(Query1 {
+            reply_hwnd: reader.u32()?,
+            reply_copydata_message: reader.u32()?,
+            search_flags: IpcSearchFlags::from_bits_retain(reader.u32()?),
+            offset: reader.u32()?,
+            max_results: reader.u32()?,
+            search: reader.terminated_utf16()?,
+        })
+    }
+}
+
+/// One `EVERYTHING_IPC_ITEMW` and the strings its offsets point to.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Item1 {
+    pub flags: IpcItemFlags,
+    pub name: String,
+    /// Empty for a volume.
+    pub path: String,
+}
+
+/// `EVERYTHING_IPC_LISTW`, the reply Everything sends to the `reply_hwnd` of a `Query1`.
+/// Unlike `List2` it counts the folders and files found separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct List1 {
+    /// Number of folders found, including volumes.
+    pub total_folders: u32,
+    pub total_files: u32,
+    /// Number of results found, `items` only holds the requested window.
+    pub total_items: u32,
+    /// Index of the first item in the full result list.
+    pub offset: u32,
+    pub items: Vec<Item1>,
+}
+
+impl List1 {
+    /// Number of folders in `items`, sent as `numfolders`.
+    pub fn num_folders(&self) -> u32 {
+        self.items
+            .iter()
+            .filter(|item| item.flags.contains(IpcItemFlags::Folder))
+            .count() as u32
+    }
+
+    /// Number of files in `items`, sent as `numfiles`.
+    pub fn num_files(&self) -> u32 {
+        self.items.len() as u32 - self.num_folders()
+    }
+
+    /// Encodes the list the way the service lays it out: the header, every `ITEMW`
+    /// and then the name and path of each item in item order.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.u32(self.total_folders);
+        writer.u32(self.total_files);
+        writer.u32(self.total_items);
+        writer.u32(self.num_folders());
+        writer.u32(self.num_files());
+        writer.u32(self.items.len() as u32);
+        writer.u32(self.offset);
+
+        let items_start = writer.len();
+        for item in &self.items {
+            writer.u32(item.flags.bits());
+            // filename_offset and path_offset are patched below once the strings are placed.
+            writer.u32(0);
+            writer.u32(0);
+        }
+
+        for (index, item) in self.items.iter().enumerate() {
+            let item_start = items_start + index * ITEM1_SIZE;
+            let name_offset = writer.len() as u32;
+            writer.patch_u32(item_start + 4, name_offset);
+            writer.terminated_utf16(&item.name);
+            let path_offset = writer.len() as u32;
+            writer.patch_u32(item_start + 8, path_offset);
+            writer.terminated_utf16(&item.path);
+        }
+
+        writer.into_bytes()
+    }
+
+    /// Decodes a list, `numfolders` and `numfiles` are not checked against the items.
+    pub fn decode(bytes: &[u8]) -> Result<List1, IpcCodecError> {
+        let mut reader = Reader::new(bytes);
+        let total_folders = reader.u32()?;
+        let total_files = reader.u32()?;
+        let total_items = reader.u32()?;
+        let _num_folders = reader.u32()?;
+        let _num_files = reader.u32()?;
+        let num_items = reader.u32()?;
+        let offset = reader.u32()?;
+        debug_assert_eq!(reader.position(), LIST1_HEADER_SIZE);
+
+        // Every item needs at least its ITEMW so a bogus count fails before allocating.
+        let items_size = (num_items as usize).checked_mul(ITEM1_SIZE);
+        if items_size.is_none_or(|size| size > bytes.len() - LIST1_HEADER_SIZE) {
+            return Err(IpcCodecError::Truncated {
+                offset: LIST1_HEADER_SIZE,
+                needed: items_size.unwrap_or(usize::MAX),
+            });
+        }
+
+        let mut items = Vec::with_capacity(num_items as usize);
+        for _ in 0..num_items {
+            let flags = IpcItemFlags::from_bits_retain(reader.u32()?);
+            let name_offset = reader.u32()?;
+            let path_offset = reader.u32()?;
+            items.push(Item1 {
+                flags,
+                name: Reader::at(bytes, name_offset)?.terminated_utf16()?,
+                path: Reader::at(bytes, path_offset)?.terminated_utf16()?,
+            });
+        }
+
+        Ok(List1 {
+            total_folders,
+            total_files,
+            total_items,
+            offset,
+            items,
+        })
+    }
+}
+
+impl From<List1> for List2 {
+    /// The reply as if it came from a `Query2` for the name and path sorted by name.
+    /// The folder and file totals are dropped.
+    fn from(list: List1) -> Self {
+        List2 {
+            total_items: list.total_items,
+            offset: list.offset,
+            request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::Path,
+            sort: EverythingSort::NameAscending,
+            items: list
+                .items
+                .into_iter()
+                .map(|item| Item2 {
+                    flags: item.flags,
+                    name: Some(item.name),
+                    path: Some(item.path),
+                    ..Item2::default()
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn encodes_query1() {
+        let query = Query1 {
+            reply_hwnd: 0x1234,
+            reply_copydata_message: 7,
+            search_flags: IpcSearchFlags::MatchPath,
+            offset: 10,
+            max_results: EVERYTHING_IPC_ALLRESULTS,
+            search: "ab".to_string(),
+        };
+
+        let mut expected = Vec::new();
+        for dword in [0x1234u32, 7, 0x4, 10, 0xFFFFFFFF] {
+            expected.extend_from_slice(&dword.to_le_bytes());
+        }
+        expected.extend(utf16("ab"));
+        expected.extend([0, 0]);
+
+        let bytes = query.encode();
+        assert_eq!(bytes.len(), QUERY1_HEADER_SIZE + 6);
+        assert_eq!(bytes, expected);
+        assert_eq!(Query1::decode(&bytes).unwrap(), query);
+    }
+
+    #[test]
+    fn decodes_list1() {
+        let mut bytes = Vec::new();
+        // totfolders, totfiles, totitems, numfolders, numfiles, numitems, offset
+        for dword in [3u32, 4, 7, 1, 1, 2, 5] {
+            bytes.extend_from_slice(&dword.to_le_bytes());
+        }
+        // items: a volume with its strings at 52 and 58, a file at 60 and 68
+        for dword in [3u32, 52, 58, 0, 60, 68] {
+            bytes.extend_from_slice(&dword.to_le_bytes());
+        }
+        bytes.extend(utf16("C:"));
+        bytes.extend([0, 0]);
+        bytes.extend([0, 0]);
+        bytes.extend(utf16("a.b"));
+        bytes.extend([0, 0]);
+        bytes.extend(utf16("C:"));
+        bytes.extend([0, 0]);
+
+        let list = List1::decode(&bytes).unwrap();
+        assert_eq!(
+            list,
+            List1 {
+                total_folders: 3,
+                total_files: 4,
+                total_items: 7,
+                offset: 5,
+                items: vec![
+                    Item1 {
+                        flags: IpcItemFlags::Folder | IpcItemFlags::Root,
+                        name: "C:".to_string(),
+                        path: String::new(),
+                    },
+                    Item1 {
+                        flags: IpcItemFlags::empty(),
+                        name: "a.b".to_string(),
+                        path: "C:".to_string(),
+                    },
+                ],
+            }
+        );
+        assert_eq!((list.num_folders(), list.num_files()), (1, 1));
+        assert_eq!(list.encode(), bytes);
+
+        let list = List2::from(list);
+        assert_eq!(list.total_items, 7);
+        assert_eq!(list.items[1].name.as_deref(), Some("a.b"));
+        assert_eq!(list.items[1].size, None);
+    }
+
+    #[test]
+    fn rejects_malformed_lists() {
+        let list = List1 {
+            total_folders: 0,
+            total_files: 2,
+            total_items: 2,
+            offset: 0,
+            items: vec![
+                Item1 {
+                    name: "テスト.txt".to_string(),
+                    path: "C:\\Users".to_string(),
+                    ..Item1::default()
+                };
+                2
+            ],
+        };
+        let bytes = list.encode();
+        assert_eq!(List1::decode(&bytes).unwrap(), list);
+
+        // Every truncation must fail cleanly rather than panic.
+        for len in 0..bytes.len() {
+            assert!(List1::decode(&bytes[..len]).is_err(), "length {}", len);
+        }
+
+        let mut bad_count = bytes.clone();
+        bad_count[20..24].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            List1::decode(&bad_count),
+            Err(IpcCodecError::Truncated { .. })
+        ));
+
+        let mut bad_offset = bytes;
+        bad_offset[36..40].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            List1::decode(&bad_offset),
+            Err(IpcCodecError::InvalidOffset(u32::MAX))
+        );
+    }
+
+    #[test]
+    fn falls_back_like_the_sdk() {
+        let default = EverythingRequestFlags::FileName | EverythingRequestFlags::Path;
+        assert!(!needs_query2(default, EverythingSort::NameAscending));
+        assert!(needs_query2(default, EverythingSort::NameDescending));
+        assert!(needs_query2(
+            EverythingRequestFlags::FileName,
+            EverythingSort::NameAscending
+        ));
+        assert!(needs_query2(
+            default | EverythingRequestFlags::Size,
+            EverythingSort::NameAscending
+        ));
+    }
+}
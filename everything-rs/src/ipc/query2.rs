@@ -0,0 +1,495 @@
+use super::wire::{Reader, Writer};
+use super::{IpcCodecError, IpcItemFlags, IpcSearchFlags, EVERYTHING_IPC_ALLRESULTS};
+use crate::{EverythingRequestFlags, EverythingSort};
+
+/// Size of the fixed part of `EVERYTHING_IPC_QUERY2`.
+const QUERY2_HEADER_SIZE: usize = 7 * 4;
+/// Size of the fixed part of `EVERYTHING_IPC_LIST2`.
+const LIST2_HEADER_SIZE: usize = 5 * 4;
+/// Size of `EVERYTHING_IPC_ITEM2`.
+const ITEM2_SIZE: usize = 2 * 4;
+
+/// `EVERYTHING_IPC_QUERY2`, the search request sent to the Everything window.
+/// The search string follows the struct as null terminated UTF-16.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query2 {
+    /// The window that will receive the results. Only the low 32 bits of a `HWND` are sent.
+    pub reply_hwnd: u32,
+    /// Echoed back as the `dwData` of the reply `COPYDATASTRUCT`.
+    pub reply_copydata_message: u32,
+    pub search_flags: IpcSearchFlags,
+    pub offset: u32,
+    /// `EVERYTHING_IPC_ALLRESULTS` to return every result.
+    pub max_results: u32,
+    pub request_flags: EverythingRequestFlags,
+    pub sort: EverythingSort,
+    pub search: String,
+}
+
+impl Default for Query2 {
+    /// The same defaults as the SDK: every result, name and path, sorted by name.
+    fn default() -> Self {
+        Query2 {
+            reply_hwnd: 0,
+            reply_copydata_message: 0,
+            search_flags: IpcSearchFlags::empty(),
+            offset: 0,
+            max_results: EVERYTHING_IPC_ALLRESULTS,
+            request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::Path,
+            sort: EverythingSort::NameAscending,
+            search: String::new(),
+        }
+    }
+}
+
+impl Query2 {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.u32(self.reply_hwnd);
+        writer.u32(self.reply_copydata_message);
+        writer.u32(self.search_flags.bits());
+        writer.u32(self.offset);
+        writer.u32(self.max_results);
+        writer.u32(self.request_flags.bits());
+        writer.u32(self.sort.into());
+        debug_assert_eq!(writer.len(), QUERY2_HEADER_SIZE);
+        writer.terminated_utf16(&self.search);
+        writer.into_bytes()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Query2, IpcCodecError> {
+        let mut reader = Reader::new(bytes);
+        Ok(Query2 {
+            reply_hwnd: reader.u32()?,
+            reply_copydata_message: reader.u32()?,
+            search_flags: IpcSearchFlags::from_bits_retain(reader.u32()?),
+            offset: reader.u32()?,
+            max_results: reader.u32()?,
+            request_flags: decode_request_flags(reader.u32()?)?,
+            sort: decode_sort(reader.u32()?)?,
+            search: reader.terminated_utf16()?,
+        })
+    }
+}
+
+/// One `EVERYTHING_IPC_ITEM2` and its data block.
+/// A field is `Some` only when the matching flag is set in the list's `request_flags`.
+/// Dates are raw FILETIME values.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Item2 {
+    pub flags: IpcItemFlags,
+    pub name: Option<String>,
+    pub path: Option<String>,
+    pub full_path: Option<String>,
+    pub extension: Option<String>,
+    pub size: Option<u64>,
+    pub date_created: Option<u64>,
+    pub date_modified: Option<u64>,
+    pub date_accessed: Option<u64>,
+    pub attributes: Option<u32>,
+    pub file_list_file_name: Option<String>,
+    pub run_count: Option<u32>,
+    pub date_run: Option<u64>,
+    pub date_recently_changed: Option<u64>,
+    pub highlighted_name: Option<String>,
+    pub highlighted_path: Option<String>,
+    pub highlighted_full_path: Option<String>,
+}
+
+/// `EVERYTHING_IPC_LIST2`, the reply Everything sends to the `reply_hwnd` of a `Query2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct List2 {
+    /// Number of results found, `items` only holds the requested window.
+    pub total_items: u32,
+    /// Index of the first item in the full result list.
+    pub offset: u32,
+    /// The request flags the service honored, which may differ from the ones requested.
+    pub request_flags: EverythingRequestFlags,
+    /// The sort the service used, which may differ from the one requested.
+    pub sort: EverythingSort,
+    pub items: Vec<Item2>,
+}
+
+impl List2 {
+    /// Encodes the list the way the service lays it out: the header, every `ITEM2` and then the data blocks in item order.
+    /// Data is written for every flag in `request_flags`, missing fields are written as zero or an empty string.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.u32(self.total_items);
+        writer.u32(self.items.len() as u32);
+        writer.u32(self.offset);
+        writer.u32(self.request_flags.bits());
+        writer.u32(self.sort.into());
+
+        let items_start = writer.len();
+        for item in &self.items {
+            writer.u32(item.flags.bits());
+            // data_offset is patched below once the data block position is known.
+            writer.u32(0);
+        }
+
+        for (index, item) in self.items.iter().enumerate() {
+            let data_offset = writer.len() as u32;
+            writer.patch_u32(items_start + index * ITEM2_SIZE + 4, data_offset);
+            encode_item_data(&mut writer, item, self.request_flags);
+        }
+
+        writer.into_bytes()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<List2, IpcCodecError> {
+        let mut reader = Reader::new(bytes);
+        let total_items = reader.u32()?;
+        let num_items = reader.u32()?;
+        let offset = reader.u32()?;
+        let request_flags = decode_request_flags(reader.u32()?)?;
+        let sort = decode_sort(reader.u32()?)?;
+        debug_assert_eq!(reader.position(), LIST2_HEADER_SIZE);
+
+        // Every item needs at least its ITEM2 header so a bogus count fails before allocating.
+        let items_size = (num_items as usize).checked_mul(ITEM2_SIZE);
+        if items_size.is_none_or(|size| size > bytes.len() - LIST2_HEADER_SIZE) {
+            return Err(IpcCodecError::Truncated {
+                offset: LIST2_HEADER_SIZE,
+                needed: items_size.unwrap_or(usize::MAX),
+            });
+        }
+
+        let mut items = Vec::with_capacity(num_items as usize);
+        for _ in 0..num_items {
+            let flags = IpcItemFlags::from_bits_retain(reader.u32()?);
+            let data_offset = reader.u32()?;
+            let mut data = Reader::at(bytes, data_offset)?;
+            items.push(decode_item_data(&mut data, flags, request_flags)?);
+        }
+
+        Ok(List2 {
+            total_items,
+            offset,
+            request_flags,
+            sort,
+            items,
+        })
+    }
+}
+
+fn decode_request_flags(bits: u32) -> Result<EverythingRequestFlags, IpcCodecError> {
+    EverythingRequestFlags::from_bits(bits).ok_or(IpcCodecError::UnknownRequestFlags(bits))
+}
+
+fn decode_sort(sort: u32) -> Result<EverythingSort, IpcCodecError> {
+    EverythingSort::try_from(sort).map_err(|_| IpcCodecError::UnknownSort(sort))
+}
+
+fn encode_item_data(writer: &mut Writer, item: &Item2, flags: EverythingRequestFlags) {
+    let text = |value: &Option<String>| value.clone().unwrap_or_default();
+
+    if flags.contains(EverythingRequestFlags::FileName) {
+        writer.counted_utf16(&text(&item.name));
+    }
+    if flags.contains(EverythingRequestFlags::Path) {
+        writer.counted_utf16(&text(&item.path));
+    }
+    if flags.contains(EverythingRequestFlags::FullPathAndFileName) {
+        writer.counted_utf16(&text(&item.full_path));
+    }
+    if flags.contains(EverythingRequestFlags::Extension) {
+        writer.counted_utf16(&text(&item.extension));
+    }
+    if flags.contains(EverythingRequestFlags::Size) {
+        writer.u64(item.size.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::DateCreated) {
+        writer.u64(item.date_created.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::DateModified) {
+        writer.u64(item.date_modified.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::DateAccessed) {
+        writer.u64(item.date_accessed.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::Attributes) {
+        writer.u32(item.attributes.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::FileListFileName) {
+        writer.counted_utf16(&text(&item.file_list_file_name));
+    }
+    if flags.contains(EverythingRequestFlags::RunCount) {
+        writer.u32(item.run_count.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::DateRun) {
+        writer.u64(item.date_run.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::DateRecentlyChanged) {
+        writer.u64(item.date_recently_changed.unwrap_or_default());
+    }
+    if flags.contains(EverythingRequestFlags::HighlightedFileName) {
+        writer.counted_utf16(&text(&item.highlighted_name));
+    }
+    if flags.contains(EverythingRequestFlags::HighlightedPath) {
+        writer.counted_utf16(&text(&item.highlighted_path));
+    }
+    if flags.contains(EverythingRequestFlags::HighlightedFullPathAndFileName) {
+        writer.counted_utf16(&text(&item.highlighted_full_path));
+    }
+}
+
+/// Reads the data block in the order the SDK's `_Everything_GetRequestData` walks it, which is request flag bit order.
+fn decode_item_data(
+    reader: &mut Reader,
+    item_flags: IpcItemFlags,
+    flags: EverythingRequestFlags,
+) -> Result<Item2, IpcCodecError> {
+    let mut item = Item2 {
+        flags: item_flags,
+        ..Item2::default()
+    };
+
+    if flags.contains(EverythingRequestFlags::FileName) {
+        item.name = Some(reader.counted_utf16()?);
+    }
+    if flags.contains(EverythingRequestFlags::Path) {
+        item.path = Some(reader.counted_utf16()?);
+    }
+    if flags.contains(EverythingRequestFlags::FullPathAndFileName) {
+        item.full_path = Some(reader.counted_utf16()?);
+    }
+    if flags.contains(EverythingRequestFlags::Extension) {
+        item.extension = Some(reader.counted_utf16()?);
+    }
+    if flags.contains(EverythingRequestFlags::Size) {
+        item.size = Some(reader.u64()?);
+    }
+    if flags.contains(EverythingRequestFlags::DateCreated) {
+        item.date_created = Some(reader.u64()?);
+    }
+    if flags.contains(EverythingRequestFlags::DateModified) {
+        item.date_modified = Some(reader.u64()?);
+    }
+    if flags.contains(EverythingRequestFlags::DateAccessed) {
+        item.date_accessed = Some(reader.u64()?);
+    }
+    if flags.contains(EverythingRequestFlags::Attributes) {
+        item.attributes = Some(reader.u32()?);
+    }
+    if flags.contains(EverythingRequestFlags::FileListFileName) {
+        item.file_list_file_name = Some(reader.counted_utf16()?);
+    }
+    if flags.contains(EverythingRequestFlags::RunCount) {
+        item.run_count = Some(reader.u32()?);
+    }
+    if flags.contains(EverythingRequestFlags::DateRun) {
+        item.date_run = Some(reader.u64()?);
+    }
+    if flags.contains(EverythingRequestFlags::DateRecentlyChanged) {
+        item.date_recently_changed = Some(reader.u64()?);
+    }
+    if flags.contains(EverythingRequestFlags::HighlightedFileName) {
+        item.highlighted_name = Some(reader.counted_utf16()?);
+    }
+    if flags.contains(EverythingRequestFlags::HighlightedPath) {
+        item.highlighted_path = Some(reader.counted_utf16()?);
+    }
+    if flags.contains(EverythingRequestFlags::HighlightedFullPathAndFileName) {
+        item.highlighted_full_path = Some(reader.counted_utf16()?);
+    }
+
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xorshift;
+
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn encodes_query2() {
+        let query = Query2 {
+            reply_hwnd: 0x1234,
+            reply_copydata_message: 7,
+            search_flags: IpcSearchFlags::MatchCase | IpcSearchFlags::Regex,
+            offset: 10,
+            max_results: 20,
+            request_flags: EverythingRequestFlags::FullPathAndFileName
+                | EverythingRequestFlags::Size,
+            sort: EverythingSort::SizeDescending,
+            search: "ab".to_string(),
+        };
+
+        let mut expected = Vec::new();
+        for dword in [0x1234u32, 7, 0x9, 10, 20, 0x14, 6] {
+            expected.extend_from_slice(&dword.to_le_bytes());
+        }
+        expected.extend(utf16("ab"));
+        expected.extend([0, 0]);
+
+        let bytes = query.encode();
+        assert_eq!(bytes.len(), QUERY2_HEADER_SIZE + 6);
+        assert_eq!(bytes, expected);
+        assert_eq!(Query2::decode(&bytes).unwrap(), query);
+    }
+
+    #[test]
+    fn decodes_list2() {
+        let mut bytes = Vec::new();
+        // totitems, numitems, offset, request_flags (name | size), sort
+        for dword in [5u32, 1, 2, 0x11, 1] {
+            bytes.extend_from_slice(&dword.to_le_bytes());
+        }
+        // item: folder, data at 28
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&28u32.to_le_bytes());
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend(utf16("abc"));
+        bytes.extend([0, 0]);
+        bytes.extend_from_slice(&0x1_0000_0002u64.to_le_bytes());
+
+        let list = List2::decode(&bytes).unwrap();
+        assert_eq!(
+            list,
+            List2 {
+                total_items: 5,
+                offset: 2,
+                request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::Size,
+                sort: EverythingSort::NameAscending,
+                items: vec![Item2 {
+                    flags: IpcItemFlags::Folder,
+                    name: Some("abc".to_string()),
+                    size: Some(0x1_0000_0002),
+                    ..Item2::default()
+                }],
+            }
+        );
+        assert_eq!(list.encode(), bytes);
+    }
+
+    fn full_item(index: u64) -> Item2 {
+        Item2 {
+            flags: IpcItemFlags::empty(),
+            name: Some(format!("file{}.txt", index)),
+            path: Some("C:\\Users\\テスト".to_string()),
+            full_path: Some(format!("C:\\Users\\テスト\\file{}.txt", index)),
+            extension: Some("txt".to_string()),
+            size: Some(index * 1024),
+            date_created: Some(index + 1),
+            date_modified: Some(index + 2),
+            date_accessed: Some(index + 3),
+            attributes: Some(0x20),
+            file_list_file_name: Some(String::new()),
+            run_count: Some(index as u32),
+            date_run: Some(index + 4),
+            date_recently_changed: Some(index + 5),
+            highlighted_name: Some(format!("*file*{}.txt", index)),
+            highlighted_path: Some("C:\\Users\\テスト".to_string()),
+            highlighted_full_path: Some(format!("C:\\Users\\テスト\\*file*{}.txt", index)),
+        }
+    }
+
+    #[test]
+    fn round_trips_every_field() {
+        let list = List2 {
+            total_items: 100,
+            offset: 0,
+            request_flags: EverythingRequestFlags::all(),
+            sort: EverythingSort::DateRunDescending,
+            items: (0..3).map(full_item).collect(),
+        };
+
+        assert_eq!(List2::decode(&list.encode()).unwrap(), list);
+    }
+
+    #[test]
+    fn only_decodes_requested_fields() {
+        let flags = EverythingRequestFlags::Path | EverythingRequestFlags::Attributes;
+        let list = List2 {
+            total_items: 1,
+            offset: 0,
+            request_flags: flags,
+            sort: EverythingSort::PathAscending,
+            items: vec![full_item(1)],
+        };
+
+        let decoded = List2::decode(&list.encode()).unwrap();
+        assert_eq!(
+            decoded.items[0],
+            Item2 {
+                path: Some("C:\\Users\\テスト".to_string()),
+                attributes: Some(0x20),
+                ..Item2::default()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lists() {
+        let list = List2 {
+            total_items: 2,
+            offset: 0,
+            request_flags: EverythingRequestFlags::all(),
+            sort: EverythingSort::NameAscending,
+            items: (0..2).map(full_item).collect(),
+        };
+        let bytes = list.encode();
+
+        // Every truncation must fail cleanly rather than panic.
+        for len in 0..bytes.len() {
+            assert!(List2::decode(&bytes[..len]).is_err(), "length {}", len);
+        }
+
+        let mut bad_count = bytes.clone();
+        bad_count[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            List2::decode(&bad_count),
+            Err(IpcCodecError::Truncated { .. })
+        ));
+
+        let mut bad_offset = bytes.clone();
+        bad_offset[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            List2::decode(&bad_offset),
+            Err(IpcCodecError::InvalidOffset(u32::MAX))
+        );
+
+        let mut bad_sort = bytes.clone();
+        bad_sort[16..20].copy_from_slice(&99u32.to_le_bytes());
+        assert_eq!(
+            List2::decode(&bad_sort),
+            Err(IpcCodecError::UnknownSort(99))
+        );
+
+        let mut bad_flags = bytes;
+        bad_flags[12..16].copy_from_slice(&0x10000u32.to_le_bytes());
+        assert_eq!(
+            List2::decode(&bad_flags),
+            Err(IpcCodecError::UnknownRequestFlags(0x10000))
+        );
+    }
+
+    #[test]
+    fn survives_random_input() {
+        let mut next = xorshift();
+
+        let valid = List2 {
+            total_items: 1,
+            offset: 0,
+            request_flags: EverythingRequestFlags::all(),
+            sort: EverythingSort::NameAscending,
+            items: vec![full_item(7)],
+        }
+        .encode();
+
+        for _ in 0..2000 {
+            let mut bytes = valid.clone();
+            for _ in 0..(next() % 8) {
+                let index = (next() as usize) % bytes.len();
+                bytes[index] = next() as u8;
+            }
+            let _ = List2::decode(&bytes);
+            let _ = Query2::decode(&bytes);
+        }
+    }
+}
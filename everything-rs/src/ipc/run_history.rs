@@ -0,0 +1,74 @@
+use super::wire::{Reader, Writer};
+use super::IpcCodecError;
+
+/// `EVERYTHING_IPC_RUN_HISTORY`, sent with `EVERYTHING_IPC_COPYDATA_SET_RUN_COUNTW`.
+/// The file name follows the struct as null terminated UTF-16.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RunHistory {
+    pub run_count: u32,
+    pub file_name: String,
+}
+
+impl RunHistory {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.u32(self.run_count);
+        writer.terminated_utf16(&self.file_name);
+        writer.into_bytes()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<RunHistory, IpcCodecError> {
+        let mut reader = Reader::new(bytes);
+        Ok(RunHistory {
+            run_count: reader.u32()?,
+            file_name: reader.terminated_utf16()?,
+        })
+    }
+}
+
+/// Encodes the null terminated UTF-16 file name sent with `EVERYTHING_IPC_COPYDATA_GET_RUN_COUNTW`
+/// and `EVERYTHING_IPC_COPYDATA_INC_RUN_COUNTW`.
+pub fn encode_file_name(file_name: &str) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.terminated_utf16(file_name);
+    writer.into_bytes()
+}
+
+pub fn decode_file_name(bytes: &[u8]) -> Result<String, IpcCodecError> {
+    Reader::new(bytes).terminated_utf16()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_run_history() {
+        let history = RunHistory {
+            run_count: 3,
+            file_name: "C:\\ä.exe".to_string(),
+        };
+        let bytes = history.encode();
+        assert_eq!(
+            bytes,
+            [
+                3, 0, 0, 0, b'C', 0, b':', 0, b'\\', 0, 0xe4, 0, b'.', 0, b'e', 0, b'x', 0, b'e',
+                0, 0, 0
+            ]
+        );
+        assert_eq!(RunHistory::decode(&bytes).unwrap(), history);
+        assert_eq!(
+            decode_file_name(&encode_file_name("C:\\ä.exe")).unwrap(),
+            "C:\\ä.exe"
+        );
+
+        assert!(matches!(
+            RunHistory::decode(&[1, 0]),
+            Err(IpcCodecError::Truncated { .. })
+        ));
+        assert_eq!(
+            decode_file_name(&[b'a', 0]),
+            Err(IpcCodecError::MissingTerminator(0))
+        );
+    }
+}
@@ -0,0 +1,521 @@
+use super::*;
+use crate::sync::lock;
+use crate::{
+    EverythingError, EverythingFilter, EverythingResult, EverythingSDKError, EverythingSort,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// The window message carrying a `CommandId` as the low word of its wParam.
+const WM_COMMAND: u32 = 0x0111;
+
+/// An option of a search window Everything reports through `EVERYTHING_WM_IPC`, see `SearchWindow::state`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SearchWindowState {
+    MatchCase,
+    MatchWholeWord,
+    MatchPath,
+    MatchDiacritics,
+    Regex,
+    /// Whether the filter bar is shown.
+    Filters,
+    /// Whether the preview pane is shown.
+    Preview,
+    StatusBar,
+    /// Whether results are shown as details rather than thumbnails.
+    Details,
+    ThumbnailSize,
+    Sort,
+    OnTop,
+    Filter,
+    FilterIndex,
+    /// Everything 1.5
+    MatchPrefix,
+    /// Everything 1.5
+    MatchSuffix,
+    /// Everything 1.5
+    IgnorePunctuation,
+    /// Everything 1.5
+    IgnoreWhitespace,
+    /// Everything 1.5
+    SearchAsYouType,
+}
+
+impl From<SearchWindowState> for u32 {
+    fn from(state: SearchWindowState) -> Self {
+        match state {
+            SearchWindowState::MatchCase => EVERYTHING_IPC_IS_MATCH_CASE,
+            SearchWindowState::MatchWholeWord => EVERYTHING_IPC_IS_MATCH_WHOLE_WORD,
+            SearchWindowState::MatchPath => EVERYTHING_IPC_IS_MATCH_PATH,
+            SearchWindowState::MatchDiacritics => EVERYTHING_IPC_IS_MATCH_DIACRITICS,
+            SearchWindowState::Regex => EVERYTHING_IPC_IS_REGEX,
+            SearchWindowState::Filters => EVERYTHING_IPC_IS_FILTERS,
+            SearchWindowState::Preview => EVERYTHING_IPC_IS_PREVIEW,
+            SearchWindowState::StatusBar => EVERYTHING_IPC_IS_STATUS_BAR,
+            SearchWindowState::Details => EVERYTHING_IPC_IS_DETAILS,
+            SearchWindowState::ThumbnailSize => EVERYTHING_IPC_GET_THUMBNAIL_SIZE,
+            SearchWindowState::Sort => EVERYTHING_IPC_GET_SORT,
+            SearchWindowState::OnTop => EVERYTHING_IPC_GET_ON_TOP,
+            SearchWindowState::Filter => EVERYTHING_IPC_GET_FILTER,
+            SearchWindowState::FilterIndex => EVERYTHING_IPC_GET_FILTER_INDEX,
+            SearchWindowState::MatchPrefix => EVERYTHING_IPC_IS_MATCH_PREFIX,
+            SearchWindowState::MatchSuffix => EVERYTHING_IPC_IS_MATCH_SUFFIX,
+            SearchWindowState::IgnorePunctuation => EVERYTHING_IPC_IS_IGNORE_PUNCTUATION,
+            SearchWindowState::IgnoreWhitespace => EVERYTHING_IPC_IS_IGNORE_WHITESPACE,
+            SearchWindowState::SearchAsYouType => EVERYTHING_IPC_IS_SEARCH_AS_YOU_TYPE,
+        }
+    }
+}
+
+/// When a search window stays on top of other windows, see `SearchWindow::on_top`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OnTop {
+    Never,
+    Always,
+    WhileSearching,
+}
+
+impl TryFrom<u32> for OnTop {
+    type Error = &'static str;
+
+    fn try_from(on_top: u32) -> Result<Self, Self::Error> {
+        match on_top {
+            0 => Ok(OnTop::Never),
+            1 => Ok(OnTop::Always),
+            2 => Ok(OnTop::WhileSearching),
+            _ => Err("Unknown on top code"),
+        }
+    }
+}
+
+/// Delivers window messages to an Everything search window and returns its replies.
+/// `SearchWindow` only deals with message numbers so the transport can be swapped out, see `RecordedTransport`.
+pub trait SearchWindowTransport: Send {
+    /// Sends `message` with `wparam` and `lparam` to a search window and returns the result.
+    /// Fails with `EverythingSDKError::Ipc` if no search window is open.
+    fn send_message(&self, message: u32, wparam: u32, lparam: u32) -> EverythingResult<u32>;
+}
+
+/// A `SearchWindowTransport` that records every message and answers state queries from canned replies.
+/// State queries without a reply and commands are answered with 0, like Everything does.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::ipc::{CommandId, RecordedTransport, SearchWindow, SearchWindowState};
+///
+/// let transport = RecordedTransport::new();
+/// transport.set_reply(SearchWindowState::MatchCase, 1);
+/// let window = SearchWindow::new(transport);
+///
+/// assert!(window.is_match_case().unwrap());
+/// window.command(CommandId::ViewZoomIn).unwrap();
+/// assert_eq!(window.transport().commands(), [CommandId::ViewZoomIn]);
+/// ```
+#[derive(Debug)]
+pub struct RecordedTransport {
+    open: AtomicBool,
+    replies: Mutex<HashMap<u32, u32>>,
+    messages: Mutex<Vec<(u32, u32, u32)>>,
+}
+
+impl RecordedTransport {
+    pub fn new() -> RecordedTransport {
+        RecordedTransport {
+            open: AtomicBool::new(true),
+            replies: Mutex::new(HashMap::new()),
+            messages: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Answers `state` with `reply` from now on.
+    pub fn set_reply(&self, state: SearchWindowState, reply: u32) {
+        lock(&self.replies).insert(state.into(), reply);
+    }
+
+    /// Simulates the search window opening or closing. While closed every message fails with `EverythingSDKError::Ipc`.
+    pub fn set_open(&self, open: bool) {
+        self.open.store(open, Ordering::SeqCst);
+    }
+
+    /// Every message sent so far as `(message, wparam, lparam)`, including failed ones.
+    pub fn messages(&self) -> Vec<(u32, u32, u32)> {
+        lock(&self.messages).clone()
+    }
+
+    /// The commands sent so far, in order.
+    pub fn commands(&self) -> Vec<CommandId> {
+        lock(&self.messages)
+            .iter()
+            .filter(|(message, _, _)| *message == WM_COMMAND)
+            .filter_map(|(_, wparam, _)| CommandId::try_from(wparam & 0xffff).ok())
+            .collect()
+    }
+}
+
+impl Default for RecordedTransport {
+    fn default() -> Self {
+        RecordedTransport::new()
+    }
+}
+
+impl SearchWindowTransport for RecordedTransport {
+    fn send_message(&self, message: u32, wparam: u32, lparam: u32) -> EverythingResult<u32> {
+        lock(&self.messages).push((message, wparam, lparam));
+        if !self.open.load(Ordering::SeqCst) {
+            return Err(EverythingError::SDKError(EverythingSDKError::Ipc));
+        }
+        Ok(match message {
+            EVERYTHING_WM_IPC => lock(&self.replies).get(&wparam).copied().unwrap_or(0),
+            _ => 0,
+        })
+    }
+}
+
+/// Reads the options of an Everything search window and remote controls it with `CommandId`s.
+/// Requires Everything 1.4.1, the `Everything 1.5` states read as off on older versions.
+///
+/// # Example
+/// ```rust,no_run
+/// # #[cfg(target_os = "windows")]
+/// # {
+/// use everything_rs::ipc::{CommandId, SearchWindow, WindowsTransport};
+/// use everything_rs::EverythingSort;
+///
+/// let window = SearchWindow::new(WindowsTransport::new());
+/// if window.sort().unwrap() != EverythingSort::NameAscending {
+///     window.command(CommandId::ResultListSortByName).unwrap();
+/// }
+/// window.set_match_case(true).unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SearchWindow<T: SearchWindowTransport> {
+    transport: T,
+}
+
+impl<T: SearchWindowTransport> SearchWindow<T> {
+    pub fn new(transport: T) -> SearchWindow<T> {
+        SearchWindow { transport }
+    }
+
+    /// The transport the messages go through.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Returns the raw reply to `state`.
+    pub fn state(&self, state: SearchWindowState) -> EverythingResult<u32> {
+        self.transport
+            .send_message(EVERYTHING_WM_IPC, state.into(), 0)
+    }
+
+    fn is(&self, state: SearchWindowState) -> EverythingResult<bool> {
+        Ok(self.state(state)? != 0)
+    }
+
+    pub fn is_match_case(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::MatchCase)
+    }
+
+    pub fn is_match_whole_word(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::MatchWholeWord)
+    }
+
+    pub fn is_match_path(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::MatchPath)
+    }
+
+    pub fn is_match_diacritics(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::MatchDiacritics)
+    }
+
+    pub fn is_regex(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::Regex)
+    }
+
+    /// Whether the filter bar is shown.
+    pub fn is_filters(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::Filters)
+    }
+
+    /// Whether the preview pane is shown.
+    pub fn is_preview(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::Preview)
+    }
+
+    pub fn is_status_bar(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::StatusBar)
+    }
+
+    /// Whether results are shown as details rather than thumbnails.
+    pub fn is_details(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::Details)
+    }
+
+    /// The thumbnail size in pixels, 0 in the details view.
+    pub fn thumbnail_size(&self) -> EverythingResult<u32> {
+        self.state(SearchWindowState::ThumbnailSize)
+    }
+
+    /// Fails with `IpcCodecError::UnknownSort` if Everything reports a sort this crate does not know.
+    pub fn sort(&self) -> EverythingResult<EverythingSort> {
+        let sort = self.state(SearchWindowState::Sort)?;
+        Ok(EverythingSort::try_from(sort).map_err(|_| IpcCodecError::UnknownSort(sort))?)
+    }
+
+    /// Fails with `IpcCodecError::UnknownReply` for a value this crate does not know.
+    pub fn on_top(&self) -> EverythingResult<OnTop> {
+        self.decode(SearchWindowState::OnTop, OnTop::try_from)
+    }
+
+    /// The selected filter, `EverythingFilter::Custom` for one added by the user, see `filter_index`.
+    /// Fails with `IpcCodecError::UnknownReply` for a value this crate does not know.
+    pub fn filter(&self) -> EverythingResult<EverythingFilter> {
+        self.decode(SearchWindowState::Filter, EverythingFilter::try_from)
+    }
+
+    /// The index of the selected filter in the filters menu.
+    pub fn filter_index(&self) -> EverythingResult<u32> {
+        self.state(SearchWindowState::FilterIndex)
+    }
+
+    /// Everything 1.5
+    pub fn is_match_prefix(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::MatchPrefix)
+    }
+
+    /// Everything 1.5
+    pub fn is_match_suffix(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::MatchSuffix)
+    }
+
+    /// Everything 1.5
+    pub fn is_ignore_punctuation(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::IgnorePunctuation)
+    }
+
+    /// Everything 1.5
+    pub fn is_ignore_whitespace(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::IgnoreWhitespace)
+    }
+
+    /// Everything 1.5
+    pub fn is_search_as_you_type(&self) -> EverythingResult<bool> {
+        self.is(SearchWindowState::SearchAsYouType)
+    }
+
+    /// The search options of the window, as they would be sent with a query.
+    pub fn search_flags(&self) -> EverythingResult<IpcSearchFlags> {
+        let states = [
+            (SearchWindowState::MatchCase, IpcSearchFlags::MatchCase),
+            (
+                SearchWindowState::MatchWholeWord,
+                IpcSearchFlags::MatchWholeWord,
+            ),
+            (SearchWindowState::MatchPath, IpcSearchFlags::MatchPath),
+            (SearchWindowState::Regex, IpcSearchFlags::Regex),
+            (
+                SearchWindowState::MatchDiacritics,
+                IpcSearchFlags::MatchDiacritics,
+            ),
+            (SearchWindowState::MatchPrefix, IpcSearchFlags::MatchPrefix),
+            (SearchWindowState::MatchSuffix, IpcSearchFlags::MatchSuffix),
+            (
+                SearchWindowState::IgnorePunctuation,
+                IpcSearchFlags::IgnorePunctuation,
+            ),
+            (
+                SearchWindowState::IgnoreWhitespace,
+                IpcSearchFlags::IgnoreWhitespace,
+            ),
+        ];
+        let mut flags = IpcSearchFlags::empty();
+        for (state, flag) in states {
+            flags.set(flag, self.is(state)?);
+        }
+        Ok(flags)
+    }
+
+    /// Sends `command` as `WM_COMMAND`, the window carries it out like the menu item of the same name.
+    /// Fails with `EverythingSDKError::InvalidParameter` if the index of an item is outside its range.
+    pub fn command(&self, command: CommandId) -> EverythingResult<()> {
+        let id = command.id().ok_or(EverythingError::SDKError(
+            EverythingSDKError::InvalidParameter,
+        ))?;
+        self.transport.send_message(WM_COMMAND, id, 0)?;
+        Ok(())
+    }
+
+    pub fn set_match_case(&self, enable: bool) -> EverythingResult<()> {
+        self.toggle(
+            enable,
+            CommandId::SearchEnableMatchCase,
+            CommandId::SearchDisableMatchCase,
+        )
+    }
+
+    pub fn set_match_whole_word(&self, enable: bool) -> EverythingResult<()> {
+        self.toggle(
+            enable,
+            CommandId::SearchEnableMatchWholeWord,
+            CommandId::SearchDisableMatchWholeWord,
+        )
+    }
+
+    pub fn set_match_path(&self, enable: bool) -> EverythingResult<()> {
+        self.toggle(
+            enable,
+            CommandId::SearchEnableMatchPath,
+            CommandId::SearchDisableMatchPath,
+        )
+    }
+
+    pub fn set_match_diacritics(&self, enable: bool) -> EverythingResult<()> {
+        self.toggle(
+            enable,
+            CommandId::SearchEnableMatchDiacritics,
+            CommandId::SearchDisableMatchDiacritics,
+        )
+    }
+
+    pub fn set_regex(&self, enable: bool) -> EverythingResult<()> {
+        self.toggle(
+            enable,
+            CommandId::SearchEnableRegex,
+            CommandId::SearchDisableRegex,
+        )
+    }
+
+    /// Selects a built in filter, selecting it again does not switch back to `EverythingFilter::Everything`.
+    /// Fails with `EverythingSDKError::InvalidParameter` for `EverythingFilter::Custom`, use `CommandId::FilterItem` instead.
+    pub fn set_filter(&self, filter: EverythingFilter) -> EverythingResult<()> {
+        let command = match filter {
+            EverythingFilter::Everything => CommandId::SearchFilterEverything,
+            EverythingFilter::Audio => CommandId::SearchFilterAudioNoToggle,
+            EverythingFilter::Compressed => CommandId::SearchFilterCompressedNoToggle,
+            EverythingFilter::Document => CommandId::SearchFilterDocumentNoToggle,
+            EverythingFilter::Executable => CommandId::SearchFilterExecutableNoToggle,
+            EverythingFilter::Folder => CommandId::SearchFilterFolderNoToggle,
+            EverythingFilter::Picture => CommandId::SearchFilterPictureNoToggle,
+            EverythingFilter::Video => CommandId::SearchFilterVideoNoToggle,
+            EverythingFilter::Custom => {
+                return Err(EverythingError::SDKError(
+                    EverythingSDKError::InvalidParameter,
+                ))
+            }
+        };
+        self.command(command)
+    }
+
+    fn toggle(&self, enable: bool, on: CommandId, off: CommandId) -> EverythingResult<()> {
+        self.command(if enable { on } else { off })
+    }
+
+    fn decode<V>(
+        &self,
+        state: SearchWindowState,
+        decode: impl Fn(u32) -> Result<V, &'static str>,
+    ) -> EverythingResult<V> {
+        let reply = self.state(state)?;
+        Ok(decode(reply).map_err(|_| IpcCodecError::UnknownReply {
+            state: state.into(),
+            reply,
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sys::EVERYTHING_SORT_SIZE_DESCENDING;
+
+    #[test]
+    fn reads_window_state() {
+        let transport = RecordedTransport::new();
+        transport.set_reply(SearchWindowState::MatchPath, 1);
+        transport.set_reply(SearchWindowState::IgnoreWhitespace, 1);
+        transport.set_reply(SearchWindowState::ThumbnailSize, 96);
+        transport.set_reply(SearchWindowState::Sort, EVERYTHING_SORT_SIZE_DESCENDING);
+        transport.set_reply(SearchWindowState::OnTop, 2);
+        transport.set_reply(SearchWindowState::Filter, EVERYTHING_IPC_FILTER_PICTURE);
+        let window = SearchWindow::new(transport);
+
+        assert!(window.is_match_path().unwrap());
+        assert!(!window.is_match_case().unwrap());
+        assert_eq!(window.thumbnail_size().unwrap(), 96);
+        assert_eq!(window.sort().unwrap(), EverythingSort::SizeDescending);
+        assert_eq!(window.on_top().unwrap(), OnTop::WhileSearching);
+        assert_eq!(window.filter().unwrap(), EverythingFilter::Picture);
+        assert_eq!(
+            window.search_flags().unwrap(),
+            IpcSearchFlags::MatchPath | IpcSearchFlags::IgnoreWhitespace
+        );
+        assert_eq!(
+            window.transport().messages()[..2],
+            [
+                (EVERYTHING_WM_IPC, EVERYTHING_IPC_IS_MATCH_PATH, 0),
+                (EVERYTHING_WM_IPC, EVERYTHING_IPC_IS_MATCH_CASE, 0)
+            ]
+        );
+
+        window.transport().set_reply(SearchWindowState::OnTop, 7);
+        assert!(matches!(
+            window.on_top(),
+            Err(EverythingError::IpcCodec(IpcCodecError::UnknownReply {
+                state: EVERYTHING_IPC_GET_ON_TOP,
+                reply: 7
+            }))
+        ));
+        window.transport().set_reply(SearchWindowState::Sort, 99);
+        assert!(matches!(
+            window.sort(),
+            Err(EverythingError::IpcCodec(IpcCodecError::UnknownSort(99)))
+        ));
+    }
+
+    #[test]
+    fn sends_commands() {
+        let window = SearchWindow::new(RecordedTransport::new());
+        window.set_match_case(true).unwrap();
+        window.set_regex(false).unwrap();
+        window.set_filter(EverythingFilter::Audio).unwrap();
+        window.command(CommandId::FilterItem(3)).unwrap();
+        assert_eq!(
+            window.transport().commands(),
+            [
+                CommandId::SearchEnableMatchCase,
+                CommandId::SearchDisableRegex,
+                CommandId::SearchFilterAudioNoToggle,
+                CommandId::FilterItem(3)
+            ]
+        );
+        assert_eq!(
+            window.transport().messages()[0],
+            (WM_COMMAND, EVERYTHING_IPC_ID_SEARCH_ENABLE_MATCH_CASE, 0)
+        );
+
+        for invalid in [
+            window.set_filter(EverythingFilter::Custom),
+            window.command(CommandId::BookmarkItem(1000)),
+        ] {
+            assert!(matches!(
+                invalid,
+                Err(EverythingError::SDKError(
+                    EverythingSDKError::InvalidParameter
+                ))
+            ));
+        }
+        assert_eq!(window.transport().messages().len(), 4);
+
+        window.transport().set_open(false);
+        assert!(matches!(
+            window.command(CommandId::ViewRefresh),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert!(matches!(
+            window.is_regex(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+    }
+}
@@ -0,0 +1,421 @@
+use super::instance::sort_instances;
+use super::{
+    decode_file_name, IpcItemFlags, IpcSearchFlags, Item1, Item2, List1, List2, Query1, Query2,
+    RunHistory, EVERYTHING_IPC_COPYDATAQUERYW, EVERYTHING_IPC_COPYDATA_GET_RUN_COUNTW,
+    EVERYTHING_IPC_COPYDATA_INC_RUN_COUNTW, EVERYTHING_IPC_COPYDATA_QUERY2W,
+    EVERYTHING_IPC_COPYDATA_SET_RUN_COUNTW, EVERYTHING_IPC_EXIT, EVERYTHING_IPC_GET_MAJOR_VERSION,
+};
+use crate::sync::lock;
+use crate::{
+    EverythingError, EverythingRequestFlags, EverythingResult, EverythingSDKError, InMemoryBackend,
+    InMemoryEntry, QueryLimits, ResultKind, SearchBackend,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delivers IPC messages to an Everything instance and returns its replies.
+/// `IpcClient` only deals with encoded messages so the transport can be swapped out, see `LoopbackTransport`.
+pub trait IpcTransport: Send {
+    /// Sends `EVERYTHING_WM_IPC` with `command` as the wParam and `param` as the lParam and returns the result.
+    /// Fails with `EverythingSDKError::Ipc` if Everything is not running.
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32>;
+
+    /// Sends `WM_COPYDATA` with `copydata` as the `dwData` and blocks until a reply with a `dwData` of `reply_id` arrives.
+    /// `encode` is called with the handle of the window that will receive the reply and returns the message body.
+    /// Fails with `EverythingSDKError::Ipc` if Everything is not running and with `EverythingError::CommandRejected(copydata)`
+    /// if it rejects the message, as Everything before 1.4.1 does with `EVERYTHING_IPC_COPYDATA_QUERY2W`.
+    ///
+    /// While waiting, `limits` is checked at least every `QueryLimits::poll_interval` and its error returned once it fails.
+    /// Replies with any other `dwData`, such as late replies to queries given up on, must be discarded.
+    fn send_query(
+        &self,
+        copydata: u32,
+        reply_id: u32,
+        encode: &dyn Fn(u32) -> Vec<u8>,
+        limits: &QueryLimits,
+    ) -> EverythingResult<Vec<u8>>;
+
+    /// Sends `WM_COPYDATA` with `copydata` as the `dwData` and `data` as the body and returns the result,
+    /// for messages that Everything answers right away instead of with a reply message.
+    /// Fails with `EverythingSDKError::Ipc` if Everything is not running.
+    fn send_copydata(&self, copydata: u32, data: &[u8]) -> EverythingResult<u32>;
+
+    /// The name of the Everything instance messages are sent to, `None` for the unnamed instance.
+    fn instance(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the names of the running Everything instances, `None` for the unnamed instance, which comes first.
+    /// Transports that can only see their own instance list it if it answers.
+    fn running_instances(&self) -> EverythingResult<Vec<Option<String>>> {
+        match self.send_command(EVERYTHING_IPC_GET_MAJOR_VERSION, 0) {
+            Ok(_) => Ok(vec![self.instance().map(str::to_string)]),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc)) => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// The window handle the loopback transport passes to `encode`.
+const LOOPBACK_HWND: u32 = 0x00010001;
+
+/// The longest the loopback transport sleeps between looking at its mailbox.
+const LOOPBACK_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// A reply the loopback transport has answered but not delivered yet.
+#[derive(Debug)]
+struct PendingReply {
+    reply_id: u32,
+    arrives: Instant,
+    data: Vec<u8>,
+}
+
+/// The loopback instances that can discover each other, like the windows of one desktop.
+/// Every instance started on it shows up in `IpcTransport::running_instances` while it is running.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::ipc::{IpcTransport, LoopbackDesktop};
+/// use everything_rs::InMemoryBackend;
+///
+/// let desktop = LoopbackDesktop::new();
+/// let unnamed = desktop.start(None, InMemoryBackend::new(Vec::new()));
+/// let build = desktop.start(Some("build"), InMemoryBackend::new(Vec::new()));
+/// assert_eq!(unnamed.running_instances().unwrap(), [None, Some("build".to_string())]);
+///
+/// build.set_running(false);
+/// assert_eq!(unnamed.running_instances().unwrap(), [None]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LoopbackDesktop {
+    instances: Arc<Mutex<Vec<LoopbackInstance>>>,
+}
+
+/// An instance started on a `LoopbackDesktop`, gone once its transport is dropped.
+#[derive(Debug)]
+struct LoopbackInstance {
+    name: Option<String>,
+    running: Weak<AtomicBool>,
+}
+
+impl LoopbackDesktop {
+    pub fn new() -> LoopbackDesktop {
+        LoopbackDesktop::default()
+    }
+
+    /// Starts an instance named `instance`, or the unnamed instance for `None`, answering from `backend`.
+    pub fn start(&self, instance: Option<&str>, backend: InMemoryBackend) -> LoopbackTransport {
+        let running = Arc::new(AtomicBool::new(true));
+        let mut instances = lock(&self.instances);
+        instances.retain(|instance| instance.running.strong_count() > 0);
+        instances.push(LoopbackInstance {
+            name: instance.map(str::to_string),
+            running: Arc::downgrade(&running),
+        });
+        drop(instances);
+
+        LoopbackTransport {
+            backend,
+            instance: instance.map(str::to_string),
+            running,
+            desktop: self.clone(),
+            delay: Mutex::new(Duration::ZERO),
+            mailbox: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn running_instances(&self) -> Vec<Option<String>> {
+        let mut running: Vec<_> = lock(&self.instances)
+            .iter()
+            .filter(|instance| {
+                instance
+                    .running
+                    .upgrade()
+                    .is_some_and(|running| running.load(Ordering::SeqCst))
+            })
+            .map(|instance| instance.name.clone())
+            .collect();
+        sort_instances(&mut running);
+        running
+    }
+}
+
+/// An `IpcTransport` that answers messages in process by decoding them and running them against an `InMemoryBackend`.
+/// Lets the whole IPC path, including the wire format, be tested on any platform.
+/// Like Everything before 1.4.1, it rejects `Query2` when the backend reports an older version, see `InMemoryBackend::set_version`.
+/// `new` starts the unnamed instance alone, start several on a `LoopbackDesktop` to test named instances.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::ipc::{IpcClient, LoopbackTransport};
+/// use everything_rs::{Everything, InMemoryBackend, InMemoryEntry};
+///
+/// let backend = InMemoryBackend::new(vec![InMemoryEntry::file("C:\\test", "test1.txt")]);
+/// let everything = Everything::with_backend(IpcClient::new(LoopbackTransport::new(backend)));
+///
+/// everything.set_search("test");
+/// everything.query().unwrap();
+///
+/// assert_eq!(everything.get_result_full_path(0).unwrap(), "C:\\test\\test1.txt");
+/// ```
+#[derive(Debug)]
+pub struct LoopbackTransport {
+    backend: InMemoryBackend,
+    instance: Option<String>,
+    running: Arc<AtomicBool>,
+    desktop: LoopbackDesktop,
+    delay: Mutex<Duration>,
+    /// Replies in the order they arrive, including late replies to queries that were given up on.
+    mailbox: Mutex<Vec<PendingReply>>,
+}
+
+impl LoopbackTransport {
+    pub fn new(backend: InMemoryBackend) -> LoopbackTransport {
+        LoopbackDesktop::new().start(None, backend)
+    }
+
+    /// The backend answering queries.
+    pub fn backend(&self) -> &InMemoryBackend {
+        &self.backend
+    }
+
+    /// Simulates Everything starting or exiting. While stopped every message fails with `EverythingSDKError::Ipc`.
+    /// `EVERYTHING_IPC_EXIT` stops it too.
+    pub fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    /// Simulates a slow search, replies to queries arrive `delay` after they were sent.
+    /// A query that gives up first leaves its reply to arrive while a later query is waiting.
+    pub fn set_delay(&self, delay: Duration) {
+        *lock(&self.delay) = delay;
+    }
+
+    /// The number of replies that were answered but not delivered yet.
+    pub fn pending_replies(&self) -> usize {
+        lock(&self.mailbox).len()
+    }
+
+    /// Delivers arrived replies until the one for `reply_id` shows up, discarding any others.
+    fn wait_for_reply(&self, reply_id: u32, limits: &QueryLimits) -> EverythingResult<Vec<u8>> {
+        loop {
+            let now = Instant::now();
+            let mut mailbox = lock(&self.mailbox);
+            while mailbox.first().is_some_and(|reply| reply.arrives <= now) {
+                let reply = mailbox.remove(0);
+                if reply.reply_id == reply_id {
+                    return Ok(reply.data);
+                }
+            }
+            let next = mailbox.first().map(|reply| reply.arrives - now);
+            drop(mailbox);
+
+            limits.check()?;
+            let wait = [next, limits.poll_interval(), Some(LOOPBACK_POLL_INTERVAL)];
+            thread::sleep(wait.into_iter().flatten().min().unwrap_or_default());
+        }
+    }
+
+    fn check_running(&self) -> EverythingResult<()> {
+        if self.running.load(Ordering::SeqCst) {
+            Ok(())
+        } else {
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        }
+    }
+
+    /// Answers like `answer_query2` with the name and path sorted by name, the only fields a version 1 reply carries.
+    fn answer_query1(&self, query: Query1) -> EverythingResult<List1> {
+        let list = self.answer_query2(Query2 {
+            reply_hwnd: query.reply_hwnd,
+            reply_copydata_message: query.reply_copydata_message,
+            search_flags: query.search_flags,
+            offset: query.offset,
+            max_results: query.max_results,
+            search: query.search,
+            ..Query2::default()
+        })?;
+        Ok(List1 {
+            total_folders: self.backend.get_total_folder_results(),
+            total_files: self.backend.get_total_file_results(),
+            total_items: list.total_items,
+            offset: list.offset,
+            items: list
+                .items
+                .into_iter()
+                .map(|item| Item1 {
+                    flags: item.flags,
+                    name: item.name.unwrap_or_default(),
+                    path: item.path.unwrap_or_default(),
+                })
+                .collect(),
+        })
+    }
+
+    fn answer_query2(&self, query: Query2) -> EverythingResult<List2> {
+        let backend = &self.backend;
+        backend.set_search(&query.search);
+        backend.set_result_offset(query.offset);
+        backend.set_max_results(query.max_results);
+        backend.set_request_flags(query.request_flags);
+        backend.set_sort(query.sort);
+        backend.set_match_case(query.search_flags.contains(IpcSearchFlags::MatchCase));
+        backend.set_match_path(query.search_flags.contains(IpcSearchFlags::MatchPath));
+        backend.set_match_whole_word(query.search_flags.contains(IpcSearchFlags::MatchWholeWord));
+        backend.set_regex(query.search_flags.contains(IpcSearchFlags::Regex));
+        backend.query()?;
+
+        let (total_items, entries) = backend.results();
+        Ok(List2 {
+            total_items,
+            offset: query.offset,
+            request_flags: query.request_flags,
+            sort: query.sort,
+            items: entries
+                .iter()
+                .map(|entry| item_from_entry(backend, entry, query.request_flags))
+                .collect(),
+        })
+    }
+}
+
+impl IpcTransport for LoopbackTransport {
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        self.check_running()?;
+        let reply = self.backend.send_command(command, param)?;
+        if command == EVERYTHING_IPC_EXIT && reply != 0 {
+            self.set_running(false);
+        }
+        Ok(reply)
+    }
+
+    fn send_query(
+        &self,
+        copydata: u32,
+        reply_id: u32,
+        encode: &dyn Fn(u32) -> Vec<u8>,
+        limits: &QueryLimits,
+    ) -> EverythingResult<Vec<u8>> {
+        self.check_running()?;
+        let body = encode(LOOPBACK_HWND);
+        // A real transport would wait forever for a reply that is addressed elsewhere.
+        let check_addressed = |reply_hwnd, reply_copydata_message| {
+            if reply_hwnd == LOOPBACK_HWND && reply_copydata_message == reply_id {
+                Ok(())
+            } else {
+                Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+            }
+        };
+        let data = match copydata {
+            EVERYTHING_IPC_COPYDATAQUERYW => {
+                let query = Query1::decode(&body)?;
+                check_addressed(query.reply_hwnd, query.reply_copydata_message)?;
+                self.answer_query1(query)?.encode()
+            }
+            // Everything before 1.4.1 does not know QUERY2 and answers 0.
+            EVERYTHING_IPC_COPYDATA_QUERY2W if self.backend.version() >= (1, 4, 1, 0) => {
+                let query = Query2::decode(&body)?;
+                check_addressed(query.reply_hwnd, query.reply_copydata_message)?;
+                self.answer_query2(query)?.encode()
+            }
+            _ => return Err(EverythingError::CommandRejected(copydata)),
+        };
+        let arrives = Instant::now() + *lock(&self.delay);
+        let mut mailbox = lock(&self.mailbox);
+        let position = mailbox.partition_point(|reply| reply.arrives <= arrives);
+        mailbox.insert(
+            position,
+            PendingReply {
+                reply_id,
+                arrives,
+                data,
+            },
+        );
+        drop(mailbox);
+
+        self.wait_for_reply(reply_id, limits)
+    }
+
+    fn send_copydata(&self, copydata: u32, data: &[u8]) -> EverythingResult<u32> {
+        self.check_running()?;
+        let backend = &self.backend;
+        let reply = match copydata {
+            EVERYTHING_IPC_COPYDATA_GET_RUN_COUNTW => {
+                backend.get_run_count(&decode_file_name(data)?)
+            }
+            EVERYTHING_IPC_COPYDATA_SET_RUN_COUNTW => {
+                let history = RunHistory::decode(data)?;
+                backend
+                    .set_run_count(&history.file_name, history.run_count)
+                    .map(|_| 1)
+            }
+            EVERYTHING_IPC_COPYDATA_INC_RUN_COUNTW => {
+                backend.inc_run_count(&decode_file_name(data)?)
+            }
+            // Everything returns 0 for messages it does not know.
+            _ => Ok(0),
+        };
+        // Everything answers 0 to anything it could not carry out.
+        Ok(reply.unwrap_or(0))
+    }
+
+    fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    fn running_instances(&self) -> EverythingResult<Vec<Option<String>>> {
+        Ok(self.desktop.running_instances())
+    }
+}
+
+/// Builds the item Everything would send for `entry`, filling in only the requested fields.
+fn item_from_entry(
+    backend: &InMemoryBackend,
+    entry: &InMemoryEntry,
+    flags: EverythingRequestFlags,
+) -> Item2 {
+    let requested = |flag| flags.contains(flag);
+    let text = |flag, value: &str| requested(flag).then(|| value.to_string());
+    let number = |flag, value| requested(flag).then_some(value);
+
+    Item2 {
+        flags: match entry.kind {
+            ResultKind::File => IpcItemFlags::empty(),
+            ResultKind::Folder => IpcItemFlags::Folder,
+            ResultKind::Volume => IpcItemFlags::Folder | IpcItemFlags::Root,
+        },
+        name: text(EverythingRequestFlags::FileName, &entry.name),
+        path: text(EverythingRequestFlags::Path, &entry.path),
+        full_path: text(
+            EverythingRequestFlags::FullPathAndFileName,
+            &entry.full_path(),
+        ),
+        extension: text(EverythingRequestFlags::Extension, entry.extension()),
+        size: number(EverythingRequestFlags::Size, entry.size),
+        date_created: number(EverythingRequestFlags::DateCreated, entry.date_created),
+        date_modified: number(EverythingRequestFlags::DateModified, entry.date_modified),
+        date_accessed: number(EverythingRequestFlags::DateAccessed, entry.date_accessed),
+        attributes: requested(EverythingRequestFlags::Attributes).then_some(entry.attributes),
+        file_list_file_name: text(EverythingRequestFlags::FileListFileName, ""),
+        run_count: requested(EverythingRequestFlags::RunCount).then(|| backend.run_count(entry)),
+        date_run: number(EverythingRequestFlags::DateRun, entry.date_run),
+        date_recently_changed: number(
+            EverythingRequestFlags::DateRecentlyChanged,
+            entry.date_recently_changed,
+        ),
+        highlighted_name: text(
+            EverythingRequestFlags::HighlightedFileName,
+            &backend.highlight(&entry.name),
+        ),
+        highlighted_path: text(
+            EverythingRequestFlags::HighlightedPath,
+            &backend.highlight(&entry.path),
+        ),
+        highlighted_full_path: text(
+            EverythingRequestFlags::HighlightedFullPathAndFileName,
+            &backend.highlight(&entry.full_path()),
+        ),
+    }
+}
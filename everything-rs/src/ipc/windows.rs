@@ -0,0 +1,294 @@
+#![allow(unsafe_code)]
+
+use super::instance::{instance_class, parse_window_class, sort_instances, window_class};
+use super::{
+    IpcTransport, SearchWindowTransport, EVERYTHING_IPC_SEARCH_CLIENT_WNDCLASS, EVERYTHING_WM_IPC,
+};
+use crate::{EverythingError, EverythingResult, EverythingSDKError, QueryLimits};
+use std::ffi::c_void;
+use std::ptr::{null, null_mut};
+use widestring::U16CString;
+use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM, LRESULT, WAIT_FAILED, WPARAM};
+use windows_sys::Win32::System::DataExchange::COPYDATASTRUCT;
+use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    ChangeWindowMessageFilterEx, CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW,
+    EnumWindows, FindWindowW, GetClassInfoExW, GetClassNameW, GetWindowLongPtrW,
+    MsgWaitForMultipleObjects, PeekMessageW, PostMessageW, RegisterClassExW, SendMessageW,
+    SetWindowLongPtrW, TranslateMessage, GWLP_USERDATA, HWND_MESSAGE, MSG, MSGFLT_ALLOW, PM_REMOVE,
+    QS_ALLINPUT, WM_APP, WM_COPYDATA, WNDCLASSEXW,
+};
+
+/// Class of the message only window that receives query replies.
+const REPLY_WNDCLASS: &str = "EVERYTHING_RS_REPLY";
+
+/// `INFINITE`, waits without a timeout.
+const INFINITE: u32 = u32::MAX;
+
+/// An `IpcTransport` that sends window messages to the Everything taskbar notification window.
+/// Each query creates a message only window to receive the reply, like the SDK's query thread does,
+/// and pumps its messages on the calling thread until the reply arrives or the query is given up on.
+/// The window is destroyed afterwards, so a late reply has nowhere to go.
+///
+/// As a `SearchWindowTransport` it sends to the Everything search window instead.
+///
+/// `new` talks to the unnamed instance, `for_instance` to an instance started with `-instance`.
+#[derive(Debug, Default, Clone)]
+pub struct WindowsTransport {
+    instance: Option<String>,
+}
+
+impl WindowsTransport {
+    pub fn new() -> WindowsTransport {
+        WindowsTransport::default()
+    }
+
+    /// Talks to the Everything instance named `instance`, see `window_class`.
+    pub fn for_instance(instance: &str) -> WindowsTransport {
+        WindowsTransport {
+            instance: Some(instance.to_string()),
+        }
+    }
+
+    fn everything_window(&self) -> EverythingResult<HWND> {
+        find_window(&window_class(self.instance.as_deref()))
+    }
+}
+
+fn wide(text: &str) -> U16CString {
+    // Class names are constants without null characters, nothing is cut off.
+    U16CString::from_str_truncate(text)
+}
+
+/// Adds the instance of a top level window to the `Vec<Option<String>>` pointed to by `lparam` if it is an Everything IPC window.
+unsafe extern "system" fn collect_instance(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let instances = &mut *(lparam as *mut Vec<Option<String>>);
+    // Class names are at most 256 characters.
+    let mut class = [0u16; 257];
+    let len = GetClassNameW(hwnd, class.as_mut_ptr(), class.len() as i32);
+    if len > 0 {
+        let class = String::from_utf16_lossy(&class[..len as usize]);
+        if let Some(instance) = parse_window_class(&class) {
+            instances.push(instance.map(str::to_string));
+        }
+    }
+    // Keep enumerating.
+    1
+}
+
+fn ipc_error() -> EverythingError {
+    EverythingError::SDKError(EverythingSDKError::Ipc)
+}
+
+fn find_window(class: &str) -> EverythingResult<HWND> {
+    // Instance names come from the caller and may contain a null character, which no class matches.
+    let class = U16CString::from_str(class).map_err(|_| ipc_error())?;
+    let hwnd = unsafe { FindWindowW(class.as_ptr(), null()) };
+    if hwnd.is_null() {
+        Err(ipc_error())
+    } else {
+        Ok(hwnd)
+    }
+}
+
+/// Where the window procedure stores the reply, pointed to by the reply window's `GWLP_USERDATA`.
+struct ReplySlot {
+    reply_id: u32,
+    data: Option<Vec<u8>>,
+}
+
+unsafe extern "system" fn reply_window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_COPYDATA {
+        let slot = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *mut ReplySlot;
+        let copydata = &*(lparam as *const COPYDATASTRUCT);
+        // Replies to earlier queries that timed out on the other side are ignored.
+        if !slot.is_null() && copydata.dwData == (*slot).reply_id as usize {
+            let data = if copydata.lpData.is_null() {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(copydata.lpData as *const u8, copydata.cbData as usize)
+                    .to_vec()
+            };
+            (*slot).data = Some(data);
+            // Wake up the message loop, the sent message itself does not make GetMessage return.
+            PostMessageW(hwnd, WM_APP, 0, 0);
+            return 1;
+        }
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+/// Creates the reply window, registering its class the first time.
+unsafe fn create_reply_window() -> EverythingResult<HWND> {
+    let instance = GetModuleHandleW(null());
+    let class = wide(REPLY_WNDCLASS);
+
+    let mut class_info: WNDCLASSEXW = std::mem::zeroed();
+    class_info.cbSize = std::mem::size_of::<WNDCLASSEXW>() as u32;
+    if GetClassInfoExW(instance, class.as_ptr(), &mut class_info) == 0 {
+        class_info.lpfnWndProc = Some(reply_window_proc);
+        class_info.hInstance = instance;
+        class_info.lpszClassName = class.as_ptr();
+        if RegisterClassExW(&class_info) == 0 {
+            return Err(EverythingError::SDKError(
+                EverythingSDKError::RegisterClassEx,
+            ));
+        }
+    }
+
+    let hwnd = CreateWindowExW(
+        0,
+        class.as_ptr(),
+        null(),
+        0,
+        0,
+        0,
+        0,
+        0,
+        HWND_MESSAGE,
+        null_mut(),
+        instance,
+        null(),
+    );
+    if hwnd.is_null() {
+        return Err(EverythingError::SDKError(EverythingSDKError::CreateWindow));
+    }
+
+    // Everything may run elevated, allow it to send us WM_COPYDATA anyway.
+    ChangeWindowMessageFilterEx(hwnd, WM_COPYDATA, MSGFLT_ALLOW, null_mut());
+    Ok(hwnd)
+}
+
+/// Sends the query and pumps the reply window's messages until `slot` is filled or `limits` fails.
+unsafe fn exchange(
+    everything: HWND,
+    hwnd: HWND,
+    copydata: u32,
+    slot: *mut ReplySlot,
+    encode: &dyn Fn(u32) -> Vec<u8>,
+    limits: &QueryLimits,
+) -> EverythingResult<Vec<u8>> {
+    SetWindowLongPtrW(hwnd, GWLP_USERDATA, slot as isize);
+
+    // Window handles are 32 bit values, even on 64 bit windows.
+    let data = encode(hwnd as usize as u32);
+    let message = COPYDATASTRUCT {
+        dwData: copydata as usize,
+        cbData: data.len() as u32,
+        lpData: data.as_ptr() as *mut c_void,
+    };
+    if SendMessageW(
+        everything,
+        WM_COPYDATA,
+        hwnd as WPARAM,
+        &message as *const COPYDATASTRUCT as LPARAM,
+    ) == 0
+    {
+        return Err(EverythingError::CommandRejected(copydata));
+    }
+
+    let mut msg: MSG = std::mem::zeroed();
+    loop {
+        if let Some(reply) = (*slot).data.take() {
+            return Ok(reply);
+        }
+        limits.check()?;
+
+        let timeout = limits.poll_interval().map_or(INFINITE, |interval| {
+            interval.as_millis().min(INFINITE as u128 - 1) as u32
+        });
+        // Wakes up for posted and sent messages, sent ones are dispatched by PeekMessage.
+        if MsgWaitForMultipleObjects(0, null(), 0, timeout, QS_ALLINPUT) == WAIT_FAILED {
+            return Err(ipc_error());
+        }
+        while PeekMessageW(&mut msg, hwnd, 0, 0, PM_REMOVE) != 0 {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+}
+
+impl IpcTransport for WindowsTransport {
+    fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+        let everything = self.everything_window()?;
+        let result = unsafe {
+            SendMessageW(
+                everything,
+                EVERYTHING_WM_IPC,
+                command as WPARAM,
+                param as LPARAM,
+            )
+        };
+        Ok(result as u32)
+    }
+
+    fn send_query(
+        &self,
+        copydata: u32,
+        reply_id: u32,
+        encode: &dyn Fn(u32) -> Vec<u8>,
+        limits: &QueryLimits,
+    ) -> EverythingResult<Vec<u8>> {
+        let everything = self.everything_window()?;
+        let mut slot = ReplySlot {
+            reply_id,
+            data: None,
+        };
+        unsafe {
+            let hwnd = create_reply_window()?;
+            let result = exchange(everything, hwnd, copydata, &mut slot, encode, limits);
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0);
+            DestroyWindow(hwnd);
+            result
+        }
+    }
+
+    fn send_copydata(&self, copydata: u32, data: &[u8]) -> EverythingResult<u32> {
+        let everything = self.everything_window()?;
+        let message = COPYDATASTRUCT {
+            dwData: copydata as usize,
+            cbData: data.len() as u32,
+            lpData: data.as_ptr() as *mut c_void,
+        };
+        let result = unsafe {
+            SendMessageW(
+                everything,
+                WM_COPYDATA,
+                0,
+                &message as *const COPYDATASTRUCT as LPARAM,
+            )
+        };
+        Ok(result as u32)
+    }
+
+    fn instance(&self) -> Option<&str> {
+        self.instance.as_deref()
+    }
+
+    /// Lists the instances with an Everything IPC window on the current desktop.
+    fn running_instances(&self) -> EverythingResult<Vec<Option<String>>> {
+        let mut instances: Vec<Option<String>> = Vec::new();
+        if unsafe { EnumWindows(Some(collect_instance), &mut instances as *mut _ as LPARAM) } == 0 {
+            return Err(ipc_error());
+        }
+        sort_instances(&mut instances);
+        Ok(instances)
+    }
+}
+
+/// Sends to the most recently active search window of the instance.
+impl SearchWindowTransport for WindowsTransport {
+    fn send_message(&self, message: u32, wparam: u32, lparam: u32) -> EverythingResult<u32> {
+        let window = find_window(&instance_class(
+            EVERYTHING_IPC_SEARCH_CLIENT_WNDCLASS,
+            self.instance.as_deref(),
+        ))?;
+        let result = unsafe { SendMessageW(window, message, wparam as WPARAM, lparam as LPARAM) };
+        Ok(result as u32)
+    }
+}
@@ -0,0 +1,141 @@
+//! Little endian readers and writers for the packed IPC structs.
+
+use super::IpcCodecError;
+
+/// Reads DWORDs, QWORDs and UTF-16 strings from a byte slice without panicking on bad input.
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader { bytes, position: 0 }
+    }
+
+    /// A reader starting at `offset` from the start of `bytes`.
+    pub(crate) fn at(bytes: &'a [u8], offset: u32) -> Result<Reader<'a>, IpcCodecError> {
+        if offset as usize > bytes.len() {
+            return Err(IpcCodecError::InvalidOffset(offset));
+        }
+        Ok(Reader {
+            bytes,
+            position: offset as usize,
+        })
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IpcCodecError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(IpcCodecError::Truncated {
+                offset: self.position,
+                needed: len,
+            })?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, IpcCodecError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, IpcCodecError> {
+        let low = self.u32()? as u64;
+        let high = self.u32()? as u64;
+        Ok((high << 32) | low)
+    }
+
+    /// Reads `len` UTF-16 code units followed by a null terminator.
+    pub(crate) fn utf16(&mut self, len: u32) -> Result<String, IpcCodecError> {
+        let byte_len = (len as usize)
+            .checked_mul(2)
+            .ok_or(IpcCodecError::Truncated {
+                offset: self.position,
+                needed: usize::MAX,
+            })?;
+        let units: Vec<u16> = self
+            .take(byte_len)?
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect();
+        let terminator_offset = self.position;
+        if self.take(2)? != [0, 0] {
+            return Err(IpcCodecError::MissingTerminator(terminator_offset));
+        }
+        Ok(String::from_utf16_lossy(&units))
+    }
+
+    /// Reads a DWORD length in characters followed by that many UTF-16 code units and a null terminator.
+    pub(crate) fn counted_utf16(&mut self) -> Result<String, IpcCodecError> {
+        let len = self.u32()?;
+        self.utf16(len)
+    }
+
+    /// Reads UTF-16 code units up to and including a null terminator.
+    pub(crate) fn terminated_utf16(&mut self) -> Result<String, IpcCodecError> {
+        let start = self.position;
+        let mut units = Vec::new();
+        loop {
+            let unit = self
+                .take(2)
+                .map_err(|_| IpcCodecError::MissingTerminator(start))?;
+            let unit = u16::from_le_bytes([unit[0], unit[1]]);
+            if unit == 0 {
+                return Ok(String::from_utf16_lossy(&units));
+            }
+            units.push(unit);
+        }
+    }
+}
+
+/// Appends little endian values to a byte buffer.
+#[derive(Default)]
+pub(crate) struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn u64(&mut self, value: u64) {
+        self.u32(value as u32);
+        self.u32((value >> 32) as u32);
+    }
+
+    /// Overwrites a DWORD that was written earlier, used to fill in offsets.
+    pub(crate) fn patch_u32(&mut self, at: usize, value: u32) {
+        self.bytes[at..at + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes the UTF-16 code units of `text` followed by a null terminator.
+    pub(crate) fn terminated_utf16(&mut self, text: &str) {
+        for unit in text.encode_utf16() {
+            self.bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        self.bytes.extend_from_slice(&[0, 0]);
+    }
+
+    /// Writes the length of `text` in UTF-16 code units, the text and a null terminator.
+    pub(crate) fn counted_utf16(&mut self, text: &str) {
+        self.u32(text.encode_utf16().count() as u32);
+        self.terminated_utf16(text);
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
@@ -1,6 +1,6 @@
 //! # Everything  
-//! This crate provides a safe wrapper around the `everything-sys-bindgen`.  
-//! `everything-sys-bindgen` is a rust binding to the [Everything SDK](https://www.voidtools.com/support/everything/sdk/) that allow IPC communication to the everything service.  
+//! This crate provides a safe wrapper around the IPC interface of the everything service.  
+//! Queries are sent to the service directly by `ipc::IpcClient`, the [Everything SDK](https://www.voidtools.com/support/everything/sdk/) dll is only used by `FfiBackend` behind the `ffi` feature.  
 //! The Everything service indexes files on windows and provides a expressive query syntax to search for files.  
 //! See the [Everything SDK documentation](https://www.voidtools.com/support/everything/sdk/) for more information.  
 //!
@@ -8,8 +8,11 @@
 //! ```rust
 //! use everything_rs::{Everything, EverythingRequestFlags, EverythingSort, EverythingError};
 //!
+//! # #[cfg(not(target_os = "windows"))]
+//! # fn main() {}
+//! # #[cfg(target_os = "windows")]
 //! fn main() -> Result<(), EverythingError> {
-//!    let mut everything = Everything::new();
+//!    let everything = Everything::try_new()?;
 //!
 //!    everything.set_search("test");
 //!
@@ -29,8 +32,8 @@
 //!
 //!    for (i, path) in everything.full_path_iter().flatten().enumerate() {
 //!        let size = everything.get_result_size(i as u32)?;
-//!        let date_created = everything.get_result_created_date(i as u32)?;
-//!        println!("{}: {} {} {}", i, path, size, date_created);
+//!        let date_created = everything.get_result_created_date(i as u32)?.to_system_time();
+//!        println!("{}: {} {} {:?}", i, path, size, date_created);
 //!    }
 //!
 //!    Ok(())
@@ -41,18 +44,57 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+mod admin;
+pub mod asynchronous;
+mod attributes;
+mod backend;
+mod builder;
+mod capabilities;
 mod error;
+mod file_time;
+mod filter;
+mod highlight;
+pub mod ipc;
+mod limits;
+mod paged;
+pub mod query;
+mod request;
+mod result;
+mod run_history;
+pub mod sdk3;
+mod session;
 mod sort;
+mod sync;
+mod sys;
+#[cfg(test)]
+mod test_util;
 
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", feature = "ffi"))]
 extern crate everything_sys_bindgen;
 
+pub use admin::{Admin, TargetMachine};
+pub use attributes::FileAttributes;
+#[cfg(all(target_os = "windows", feature = "ffi"))]
+pub use backend::FfiBackend;
+pub use backend::{
+    Backoff, ConnectionState, InMemoryBackend, InMemoryEntry, ReconnectingBackend, SearchBackend,
+};
 use bitflags::bitflags;
+pub use builder::EverythingBuilder;
+pub use capabilities::{Capabilities, CapabilityWarning, FileInfo, SlowQueryPolicy, Version};
 pub use error::{EverythingError, EverythingResult, EverythingSDKError};
-use everything_sys_bindgen::*;
+pub use file_time::FileTime;
+pub use filter::{EverythingFilter, Filter, Filters, FiltersError};
+pub use highlight::Highlighted;
+pub use limits::{CancellationToken, QueryLimits};
+pub use paged::{PageCursor, Paged};
+pub use query::Query;
+pub use request::SearchRequest;
+pub use result::{ResultKind, ResultPage, SearchResult};
 pub use sort::EverythingSort;
+use std::sync::OnceLock;
 use std::time::Duration;
-use widestring::{U16CStr, U16CString};
+use sys::*;
 
 bitflags! {
     /// Input to the Everything.set_request_flags() function.
@@ -79,72 +121,43 @@ bitflags! {
     }
 }
 
-trait U64Able {
-    fn as_u64(&self) -> u64;
-}
-
-impl U64Able for FILETIME {
-    fn as_u64(&self) -> u64 {
-        ((self.dwHighDateTime as u64) << 32) | (self.dwLowDateTime as u64)
-    }
-}
-
-/// Checks for a null pointer and gets the last everything error if there is one.   
-/// Otherwise, iterate until null is reached and return a string.  
-/// # Arguments  
-///
-/// * `ptr` - A pointer to a u16 string returned by the Everything API.  
-fn parse_string_ptr(ptr: *const u16) -> EverythingResult<String> {
-    if ptr.is_null() {
-        let error_code = Everything::get_last_error();
-        panic!("Error code: {:?}", error_code);
-    }
-
-    Ok(unsafe { U16CStr::from_ptr_str(ptr).to_string_lossy() })
-}
-
 /// A wrapper around the Everything API.  
-/// Calls cleanup on drop.  
-#[derive(Debug)]
-pub struct Everything;
+/// All calls are forwarded to a `SearchBackend`, by default an `ipc::IpcClient` talking to Everything over its window messages on windows.
+pub struct Everything {
+    backend: Box<dyn SearchBackend>,
+    /// Probed by the first search that checks for slow queries.
+    capabilities: OnceLock<Capabilities>,
+}
 
 impl Everything {
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getlasterror/>  
-    pub fn get_last_error() -> EverythingResult<()> {
-        let error_code = unsafe { Everything_GetLastError() };
-        match error_code.try_into().unwrap() {
-            EverythingSDKError::Ok => Ok(()),
-            err => Err(EverythingError::SDKError(err)),
-        }
+    pub fn get_last_error(&self) -> EverythingResult<()> {
+        self.backend.get_last_error()
     }
 
     /// Sleep the current thread until the Everything database is loaded.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_isdbloaded/>  
-    pub fn wait_db_loaded(timeout: Duration) -> EverythingResult<()> {
+    pub fn wait_db_loaded(&self, timeout: Duration) -> EverythingResult<()> {
         let sleep_duration: u64 = 300;
         let mut wait_time: u64 = 0;
 
-        unsafe {
-            while Everything_IsDBLoaded() == 0 {
-                if wait_time >= timeout.as_millis() as u64 {
-                    return Err(EverythingError::DatabaseTimeout);
-                }
-                Everything::get_last_error()?;
-                std::thread::sleep(std::time::Duration::from_millis(sleep_duration));
-                wait_time += sleep_duration;
+        while !self.backend.is_db_loaded() {
+            if wait_time >= timeout.as_millis() as u64 {
+                return Err(EverythingError::DatabaseTimeout);
             }
+            self.get_last_error()?;
+            std::thread::sleep(std::time::Duration::from_millis(sleep_duration));
+            wait_time += sleep_duration;
         }
 
         Ok(())
     }
 
     /// Set the query to be used by the next call to query.  
+    /// A search containing a null character makes the query fail with `EverythingSDKError::InvalidParameter`.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_setsearch/>  
     pub fn set_search(&self, search: &str) {
-        let wide_search = U16CString::from_str(search).expect("Failed to convert search string");
-        unsafe {
-            Everything_SetSearchW(wide_search.as_ptr());
-        }
+        self.backend.set_search(search);
     }
 
     /// Get the current query.  
@@ -158,153 +171,233 @@ impl Everything {
     /// <https://www.voidtools.com/forum/viewtopic.php?t=10099>  
     /// <https://www.voidtools.com/forum/viewtopic.php?t=10860>  
     pub fn get_search(&self) -> EverythingResult<String> {
-        let search_ptr = unsafe { Everything_GetSearchW() };
-        parse_string_ptr(search_ptr)
+        self.backend.get_search()
+    }
+
+    /// Renders `query` and sets it as the search, see `set_search`.  
+    pub fn set_query(&self, query: &Query) {
+        self.set_search(&query.to_string());
+    }
+
+    /// Get the current search as a `Query` so more terms can be added to it.  
+    pub fn get_query(&self) -> EverythingResult<Query> {
+        self.get_search().map(Query::from)
     }
 
     /// Set the sorting to be used by the next call to query.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_setsort/>  
     pub fn set_sort(&self, sort: EverythingSort) {
-        unsafe {
-            Everything_SetSort(sort.into());
-        }
+        self.backend.set_sort(sort);
     }
 
     /// Get the current sorting.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getsort/>  
     pub fn get_sort(&self) -> Option<EverythingSort> {
-        let sort = unsafe { Everything_GetSort() };
-        if let Ok(eve_sort) = sort.try_into() {
-            Some(eve_sort)
-        } else {
-            None
-        }
+        self.backend.get_sort()
     }
 
     /// Check if the sort type is indexed.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_isfastsort/>  
     pub fn is_fast_sort(&self, sort: EverythingSort) -> bool {
-        unsafe { Everything_IsFastSort(sort.into()) != 0 }
+        self.backend.is_fast_sort(sort)
+    }
+
+    /// Set whether the next query matches case.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setmatchcase/>  
+    pub fn set_match_case(&self, enable: bool) {
+        self.backend.set_match_case(enable);
+    }
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmatchcase/>  
+    pub fn get_match_case(&self) -> bool {
+        self.backend.get_match_case()
+    }
+
+    /// Set whether the next query matches against the full path instead of the file name.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setmatchpath/>  
+    pub fn set_match_path(&self, enable: bool) {
+        self.backend.set_match_path(enable);
+    }
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmatchpath/>  
+    pub fn get_match_path(&self) -> bool {
+        self.backend.get_match_path()
+    }
+
+    /// Set whether the next query only matches whole words.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setmatchwholeword/>  
+    pub fn set_match_whole_word(&self, enable: bool) {
+        self.backend.set_match_whole_word(enable);
+    }
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmatchwholeword/>  
+    pub fn get_match_whole_word(&self) -> bool {
+        self.backend.get_match_whole_word()
+    }
+
+    /// Set whether the next search is evaluated as a regular expression.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setregex/>  
+    pub fn set_regex(&self, enable: bool) {
+        self.backend.set_regex(enable);
+    }
+
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getregex/>  
+    pub fn get_regex(&self) -> bool {
+        self.backend.get_regex()
     }
 
     /// See <https://www.voidtools.com/support/everything/sdk/everything_isfileresult/>  
-    pub fn is_result_file(&self, index: DWORD) -> bool {
-        unsafe { Everything_IsFileResult(index) != 0 }
+    pub fn is_result_file(&self, index: u32) -> bool {
+        self.backend.is_result_file(index)
     }
 
     /// See <https://www.voidtools.com/support/everything/sdk/everything_isfolderresult/>  
-    pub fn is_result_folder(&self, index: DWORD) -> bool {
-        unsafe { Everything_IsFolderResult(index) != 0 }
+    pub fn is_result_folder(&self, index: u32) -> bool {
+        self.backend.is_result_folder(index)
     }
 
     /// See <https://www.voidtools.com/support/everything/sdk/everything_isvolumeresult/>  
-    pub fn is_result_volume(&self, index: DWORD) -> bool {
-        unsafe { Everything_IsVolumeResult(index) != 0 }
+    pub fn is_result_volume(&self, index: u32) -> bool {
+        self.backend.is_result_volume(index)
     }
 
     /// Returns the number of visible results in everything's memory.  
     /// Will be 0 until the first call to query.  
     /// Then will equal the number of max results specified by set_max_results.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getnumresults/>  
-    pub fn get_num_results(&self) -> DWORD {
-        unsafe { Everything_GetNumResults() }
+    pub fn get_num_results(&self) -> u32 {
+        self.backend.get_num_results()
     }
 
     /// Returns the number of results returned by the query.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_gettotresults/>  
-    pub fn get_total_results(&self) -> DWORD {
-        unsafe { Everything_GetTotResults() }
+    pub fn get_total_results(&self) -> u32 {
+        self.backend.get_total_results()
+    }
+
+    /// Returns the number of visible file results.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getnumfileresults/>  
+    pub fn get_num_file_results(&self) -> u32 {
+        self.backend.get_num_file_results()
+    }
+
+    /// Returns the number of visible folder results, including volumes.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getnumfolderresults/>  
+    pub fn get_num_folder_results(&self) -> u32 {
+        self.backend.get_num_folder_results()
+    }
+
+    /// Returns the number of files matched by the query.  
+    /// Not available for `EVERYTHING_IPC_QUERY2` queries, check `get_last_error`.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_gettotfileresults/>  
+    pub fn get_total_file_results(&self) -> u32 {
+        self.backend.get_total_file_results()
+    }
+
+    /// Returns the number of folders matched by the query, including volumes.  
+    /// Not available for `EVERYTHING_IPC_QUERY2` queries, check `get_last_error`.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_gettotfolderresults/>  
+    pub fn get_total_folder_results(&self) -> u32 {
+        self.backend.get_total_folder_results()
     }
 
     /// Limit's the number of results returned by the everything.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_setmax/>  
-    pub fn set_max_results(&self, max_results: DWORD) {
-        unsafe {
-            Everything_SetMax(max_results);
-        }
+    pub fn set_max_results(&self, max_results: u32) {
+        self.backend.set_max_results(max_results);
     }
 
     /// Returns the current maximum number of results.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getmax/>  
-    pub fn get_max_results(&self) -> DWORD {
-        unsafe { Everything_GetMax() }
+    pub fn get_max_results(&self) -> u32 {
+        self.backend.get_max_results()
     }
 
     /// Set the index offset that everything will start its result window from.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_setoffset/>  
-    pub fn set_result_offset(&self, offset_results: DWORD) {
-        unsafe {
-            Everything_SetOffset(offset_results);
-        }
+    pub fn set_result_offset(&self, offset_results: u32) {
+        self.backend.set_result_offset(offset_results);
     }
 
     /// Returns the current result offset.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getoffset/>  
-    pub fn get_result_offset(&self) -> DWORD {
-        unsafe { Everything_GetOffset() }
+    pub fn get_result_offset(&self) -> u32 {
+        self.backend.get_result_offset()
     }
 
     /// Set the type of data that the everything service will load.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_setreplywindow/>  
     pub fn set_request_flags(&self, request_flags: EverythingRequestFlags) {
-        unsafe {
-            Everything_SetRequestFlags(request_flags.bits());
-        }
+        self.backend.set_request_flags(request_flags);
     }
 
     /// Returns the current request flags.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getrequestflags/>  
     pub fn get_request_flags(&self) -> EverythingRequestFlags {
-        let request_flags = unsafe { Everything_GetRequestFlags() };
-        EverythingRequestFlags::from_bits_truncate(request_flags)
+        self.backend.get_request_flags()
+    }
+
+    /// Returns the request flags the last query was answered with.  
+    /// Everything may not honor every requested flag, for example on older versions.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultlistrequestflags/>  
+    pub fn get_result_list_request_flags(&self) -> EverythingRequestFlags {
+        self.backend.get_result_list_request_flags()
     }
 
     pub fn query(&self) -> EverythingResult<()> {
-        let result = unsafe { Everything_QueryW(1) };
-        if result == 0 {
-            Everything::get_last_error()
-        } else {
-            Ok(())
-        }
+        self.backend.query()
+    }
+
+    /// Runs the query like `query` but stops waiting once `limits` say so.  
+    /// Fails with `EverythingError::Cancelled` or `EverythingError::QueryTimeout` and leaves no results,
+    /// a reply Everything sends afterwards is discarded.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_query/>  
+    pub fn query_with(&self, limits: &QueryLimits) -> EverythingResult<()> {
+        self.backend.query_with(limits)
     }
 
     /// Reset the query state.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_reset/>  
     pub fn reset(&self) {
-        unsafe {
-            Everything_Reset();
-        }
+        self.backend.reset();
+    }
+
+    /// Sort the current results by path without running the query again.  
+    /// Prefer `set_sort` with `EverythingSort::PathAscending`, which is faster.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_sortresultsbypath/>  
+    pub fn sort_results_by_path(&self) {
+        self.backend.sort_results_by_path();
     }
 
     /// Returns the total number of indexes in the everything result window.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getnumfileresults/>  
     pub fn get_result_count(&self) -> u32 {
-        unsafe { Everything_GetNumResults() }
+        self.backend.get_num_results()
     }
 
     /// Creates a owned string from full path slice for a result at an index.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultfullpathname/>  
     pub fn get_result_full_path(&self, index: u32) -> EverythingResult<String> {
-        let path_length =
-            unsafe { Everything_GetResultFullPathNameW(index, std::ptr::null_mut(), 0) };
-        if path_length == 0 {
-            Everything::get_last_error()?;
-        }
+        self.backend.get_result_full_path(index)
+    }
 
-        // Length does not include null terminator
-        let mut path_buffer = Vec::with_capacity(path_length as usize + 1);
-        unsafe {
-            let count_copied = Everything_GetResultFullPathNameW(
-                index,
-                path_buffer.as_mut_ptr(),
-                path_buffer.len() as u32,
-            );
-            Ok(
-                U16CStr::from_ptr(path_buffer.as_ptr(), count_copied as usize)
-                    .unwrap()
-                    .to_string_lossy(),
-            )
-        }
+    /// Returns the path of the folder containing the result at the index.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultpath/>  
+    pub fn get_result_path(&self, index: u32) -> EverythingResult<String> {
+        self.backend.get_result_path(index)
+    }
+
+    /// Returns an owned copy of the result at the index.  
+    /// Only the fields honored by the last query are read, see `get_result_list_request_flags`.  
+    pub fn get_result(&self, index: u32) -> EverythingResult<SearchResult> {
+        result::read_result(&*self.backend, index)
+    }
+
+    /// Returns owned copies of every result of the last query, see `get_result`.  
+    pub fn results(&self) -> EverythingResult<Vec<SearchResult>> {
+        (0..self.get_result_count())
+            .map(|index| self.get_result(index))
+            .collect()
     }
 
     /// Returns an iterator over the full paths of the results.  
@@ -317,13 +410,7 @@ impl Everything {
     /// Iterates from the pointer to find a null terminator returning an owned string.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultfilename/>  
     pub fn get_result_file_name(&self, index: u32) -> EverythingResult<String> {
-        let result_ptr = unsafe { Everything_GetResultFileNameW(index) };
-
-        if result_ptr.is_null() {
-            Everything::get_last_error()?;
-        }
-
-        parse_string_ptr(result_ptr)
+        self.backend.get_result_file_name(index)
     }
 
     /// Returns an iterator over the file names of the results.  
@@ -335,121 +422,166 @@ impl Everything {
 
     /// Returns the created date of the result at the index.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdatecreated/>  
-    pub fn get_result_created_date(&self, index: u32) -> EverythingResult<u64> {
-        let mut file_time: FILETIME = FILETIME {
-            dwLowDateTime: 0,
-            dwHighDateTime: 0,
-        };
-
-        let success = unsafe { Everything_GetResultDateCreated(index, &mut file_time) };
-
-        if success == 0 {
-            Everything::get_last_error()?;
-        }
-
-        Ok(file_time.as_u64())
+    pub fn get_result_created_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.backend.get_result_created_date(index)
     }
 
     /// Returns the modified date of the result at the index.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdatemodified/>  
-    pub fn get_result_count_modified_date(&self, index: u32) -> EverythingResult<u64> {
-        let mut file_time: FILETIME = FILETIME {
-            dwLowDateTime: 0,
-            dwHighDateTime: 0,
-        };
-
-        let success = unsafe { Everything_GetResultDateModified(index, &mut file_time) };
+    pub fn get_result_count_modified_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.backend.get_result_modified_date(index)
+    }
 
-        if success == 0 {
-            Everything::get_last_error()?;
-        }
+    /// Returns the size of the result at the index.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultsize/>  
+    pub fn get_result_size(&self, index: u32) -> EverythingResult<u64> {
+        self.backend.get_result_size(index)
+    }
 
-        Ok(file_time.as_u64())
+    /// Returns the extension of the result at the index.  
+    /// iterates from a string pointer to find a null terminator returning an owned string.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultextension/>  
+    pub fn get_result_extension(&self, index: u32) -> EverythingResult<String> {
+        self.backend.get_result_extension(index)
     }
 
     /// Returns the last accessed date of the result at the index.  
     /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdateaccessed/>  
-    pub fn get_result_size(&self, index: u32) -> EverythingResult<u64> {
-        let mut size: LARGE_INTEGER = LARGE_INTEGER { QuadPart: 0 };
+    pub fn get_result_accessed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.backend.get_result_accessed_date(index)
+    }
 
-        let success = unsafe { Everything_GetResultSize(index, &mut size) };
+    /// Returns the date the result at the index was last run from Everything.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdaterun/>  
+    pub fn get_result_run_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.backend.get_result_run_date(index)
+    }
 
-        if success == 0 {
-            Everything::get_last_error()?;
-        }
+    /// Returns the date the result at the index was last changed.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultdaterecentlychanged/>  
+    pub fn get_result_recently_changed_date(&self, index: u32) -> EverythingResult<FileTime> {
+        self.backend.get_result_recently_changed_date(index)
+    }
 
-        Ok(unsafe { size.QuadPart as u64 })
+    /// Returns the attributes of the result at the index, such as whether it is hidden.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultattributes/>  
+    pub fn get_result_attributes(&self, index: u32) -> EverythingResult<FileAttributes> {
+        self.backend.get_result_attributes(index)
     }
 
-    /// Returns the extension of the result at the index.  
-    /// iterates from a string pointer to find a null terminator returning an owned string.  
-    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultextension/>  
-    pub fn get_result_extension(&self, index: u32) -> EverythingResult<String> {
-        let result_ptr = unsafe { Everything_GetResultExtensionW(index) };
+    /// Returns the number of times the result at the index was run from Everything.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultruncount/>  
+    pub fn get_result_run_count(&self, index: u32) -> EverythingResult<u32> {
+        self.backend.get_result_run_count(index)
+    }
 
-        if result_ptr.is_null() {
-            Everything::get_last_error()?;
-        }
+    /// Returns the file list the result at the index came from, empty if it is not from a file list.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresultfilelistfilename/>  
+    pub fn get_result_file_list_file_name(&self, index: u32) -> EverythingResult<String> {
+        self.backend.get_result_file_list_file_name(index)
+    }
+
+    /// Returns the file name of the result at the index with the text matched by the search.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresulthighlightedfilename/>  
+    pub fn get_result_highlighted_file_name(&self, index: u32) -> EverythingResult<Highlighted> {
+        self.backend
+            .get_result_highlighted_file_name(index)
+            .map(|markup| Highlighted::parse(&markup))
+    }
 
-        parse_string_ptr(result_ptr)
+    /// Returns the path of the result at the index with the text matched by the search.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresulthighlightedpath/>  
+    pub fn get_result_highlighted_path(&self, index: u32) -> EverythingResult<Highlighted> {
+        self.backend
+            .get_result_highlighted_path(index)
+            .map(|markup| Highlighted::parse(&markup))
     }
 
+    /// Returns the full path of the result at the index with the text matched by the search.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getresulthighlightedfullpathandfilename/>  
+    pub fn get_result_highlighted_full_path(&self, index: u32) -> EverythingResult<Highlighted> {
+        self.backend
+            .get_result_highlighted_full_path(index)
+            .map(|markup| Highlighted::parse(&markup))
+    }
+
+    /// Connects to the running Everything instance over IPC, see `ipc::IpcClient`.  
     /// Waits for the Everything database to be fully loaded before returning an instance.
+    ///
+    /// # Panics
+    /// When Everything is not running or the database does not load within 5 seconds.
+    #[cfg(target_os = "windows")]
+    #[deprecated(
+        note = "panics when Everything is not running, use `try_new` or `builder` instead"
+    )]
+    // `Default` would panic the same way, so it is not implemented.
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Everything {
-        Everything::wait_db_loaded(Duration::from_secs(5)).expect("Everything database not loaded");
-        Everything
+        Everything::try_new().expect("Everything database not loaded")
     }
 
-    pub fn version() -> String {
-        unsafe {
-            let major = Everything_GetMajorVersion();
-            let minor = Everything_GetMinorVersion();
-            let revision = Everything_GetRevision();
-            let build = Everything_GetBuildNumber();
+    /// Connects to the running Everything instance over IPC, see `ipc::IpcClient`.  
+    /// Waits up to 5 seconds for the Everything database to be fully loaded, use `builder` to wait longer or not at all.  
+    /// Returns `EverythingSDKError::Ipc` if Everything is not running and `EverythingError::DatabaseTimeout` if the database does not load in time.  
+    #[cfg(target_os = "windows")]
+    pub fn try_new() -> EverythingResult<Everything> {
+        Everything::builder().build()
+    }
 
-            format!("{}.{}.{}.{}", major, minor, revision, build)
+    /// Connects to the Everything instance started with `-instance name` like `try_new`, or to the unnamed instance for `None`.  
+    /// Each instance has its own index, see `running_instances` for the ones that can be connected to.  
+    #[cfg(target_os = "windows")]
+    pub fn connect(instance: Option<&str>) -> EverythingResult<Everything> {
+        let builder = Everything::builder();
+        match instance {
+            Some(instance) => builder.instance(instance).build(),
+            None => builder.build(),
         }
     }
-}
 
-impl Drop for Everything {
-    fn drop(&mut self) {
-        unsafe {
-            Everything_CleanUp();
+    /// Returns the names of the running Everything instances, `None` for the unnamed instance.  
+    /// See `ipc::IpcTransport::running_instances`.  
+    #[cfg(target_os = "windows")]
+    pub fn running_instances() -> EverythingResult<Vec<Option<String>>> {
+        use crate::ipc::IpcTransport;
+        ipc::WindowsTransport::new().running_instances()
+    }
+
+    /// Configures the backend and the database wait of a new instance, see `EverythingBuilder`.  
+    pub fn builder() -> EverythingBuilder {
+        EverythingBuilder::new()
+    }
+
+    /// Creates an instance that forwards every call to `backend`.  
+    /// Unlike `try_new` this does not wait for the database to load, call `wait_db_loaded` if the backend needs it.  
+    pub fn with_backend<B: SearchBackend + 'static>(backend: B) -> Everything {
+        Everything {
+            backend: Box::new(backend),
+            capabilities: OnceLock::new(),
         }
     }
+
+    /// Returns the version of Everything, `0.0.0.0` if it is not running. See `Everything::capabilities` for what it supports.  
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getmajorversion/>  
+    pub fn version(&self) -> Version {
+        Version::from(self.backend.version())
+    }
 }
 
-impl Default for Everything {
-    fn default() -> Self {
-        Everything::new()
+impl std::fmt::Debug for Everything {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Everything").finish_non_exhaustive()
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, target_os = "windows"))]
 mod tests {
     use super::*;
-    use lazy_static::lazy_static;
     use std::path::Path;
-    use std::sync::Mutex;
-
-    lazy_static! {
-        static ref TEST_EVERYTHING: Mutex<Everything> = Mutex::new(Everything::new());
-    }
 
-    fn setup() -> std::sync::MutexGuard<'static, Everything> {
-        let everything = TEST_EVERYTHING.lock().unwrap();
-        everything.reset();
-        everything
-    }
-
-    #[test]
-    fn parses_string_ptr() {
-        let test_string = "test\0";
-        let test_string_ptr = test_string.encode_utf16().collect::<Vec<u16>>();
-        let test_string_ptr = test_string_ptr.as_ptr();
-        let parsed_string = parse_string_ptr(test_string_ptr).unwrap();
-        assert_eq!(parsed_string, test_string[0..4]);
+    // Every instance keeps its own search state so the tests can run in parallel.
+    fn setup() -> Everything {
+        Everything::try_new().unwrap()
     }
 
     #[test]
@@ -526,12 +658,12 @@ mod tests {
             assert!(size > 0);
 
             let created_date = everything.get_result_created_date(idx).unwrap();
-            assert!(created_date > 0);
+            assert!(created_date > FileTime::from_raw(0));
             assert!(created_date <= last_date_created);
             last_date_created = created_date;
 
             let modified_date = everything.get_result_count_modified_date(idx).unwrap();
-            assert!(modified_date > 0);
+            assert!(modified_date > FileTime::from_raw(0));
         }
     }
 }
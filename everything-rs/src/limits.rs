@@ -0,0 +1,149 @@
+use crate::{EverythingError, EverythingResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often a waiting query looks at its `CancellationToken`.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Gives up on a query from another thread, see `QueryLimits`.
+/// Clones share the same flag and compare equal.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Makes every query using this token fail with `EverythingError::Cancelled`, including the one currently waiting.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &CancellationToken) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+impl std::hash::Hash for CancellationToken {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.cancelled).hash(state);
+    }
+}
+
+/// When to stop waiting for the reply to a query, see `Everything::query_with`.
+/// The default never stops waiting, like `Everything::query`.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::{CancellationToken, QueryLimits};
+/// use std::time::Duration;
+///
+/// let cancel = CancellationToken::new();
+/// let limits = QueryLimits {
+///     cancel: Some(cancel.clone()),
+///     ..QueryLimits::timeout(Duration::from_secs(5))
+/// };
+/// assert!(limits.check().is_ok());
+///
+/// cancel.cancel();
+/// assert!(limits.check().is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct QueryLimits {
+    /// Fails the query with `EverythingError::QueryTimeout` once passed.
+    pub deadline: Option<Instant>,
+    /// Fails the query with `EverythingError::Cancelled` once cancelled.
+    pub cancel: Option<CancellationToken>,
+}
+
+impl QueryLimits {
+    /// A deadline `timeout` from now.
+    pub fn timeout(timeout: Duration) -> QueryLimits {
+        QueryLimits {
+            deadline: Instant::now().checked_add(timeout),
+            cancel: None,
+        }
+    }
+
+    pub fn cancel(cancel: CancellationToken) -> QueryLimits {
+        QueryLimits {
+            deadline: None,
+            cancel: Some(cancel),
+        }
+    }
+
+    /// Fails once the query should be given up on. Cancellation wins over the deadline.
+    pub fn check(&self) -> EverythingResult<()> {
+        if self
+            .cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.is_cancelled())
+        {
+            return Err(EverythingError::Cancelled);
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(EverythingError::QueryTimeout);
+        }
+        Ok(())
+    }
+
+    /// How long a transport may block before calling `check` again, `None` to block until the reply arrives.
+    pub fn poll_interval(&self) -> Option<Duration> {
+        let remaining = self
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match (remaining, &self.cancel) {
+            (None, None) => None,
+            (Some(remaining), None) => Some(remaining),
+            (remaining, Some(_)) => {
+                Some(remaining.map_or(CANCEL_POLL_INTERVAL, |r| r.min(CANCEL_POLL_INTERVAL)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_cancellation_before_the_deadline() {
+        assert!(QueryLimits::default().check().is_ok());
+        assert_eq!(QueryLimits::default().poll_interval(), None);
+
+        let expired = QueryLimits::timeout(Duration::ZERO);
+        assert!(matches!(
+            expired.check(),
+            Err(EverythingError::QueryTimeout)
+        ));
+        assert_eq!(expired.poll_interval(), Some(Duration::ZERO));
+
+        let cancel = CancellationToken::new();
+        let limits = QueryLimits {
+            cancel: Some(cancel.clone()),
+            ..expired
+        };
+        assert_eq!(limits.cancel, Some(cancel.clone()));
+        assert_ne!(limits.cancel, Some(CancellationToken::new()));
+        cancel.cancel();
+        assert!(matches!(limits.check(), Err(EverythingError::Cancelled)));
+
+        let waiting = QueryLimits::cancel(CancellationToken::new());
+        assert_eq!(waiting.poll_interval(), Some(CANCEL_POLL_INTERVAL));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{
+    Everything, EverythingError, EverythingResult, ResultPage, SearchRequest, SearchResult,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use ts_rs::TS;
+
+/// Where a `Paged` iterator stands, see `Paged::cursor`.
+/// Pass it to `Paged::resume` to continue from the same result later, for example after an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, TS)]
+pub struct PageCursor {
+    /// The index of the next result to yield.
+    pub offset: u32,
+    /// The number of results the last page reported, `None` before the first page.
+    pub total_results: Option<u32>,
+}
+
+/// Turns windowed queries into a sequence of results, shared by `Paged` and `asynchronous::PagedStream`.
+#[derive(Debug)]
+pub(crate) struct Pager {
+    request: SearchRequest,
+    page_size: u32,
+    cursor: PageCursor,
+    /// Results of the last page that were not yielded yet, the first is at `cursor.offset`.
+    buffer: VecDeque<SearchResult>,
+    /// Yielded before the buffered results.
+    error: Option<EverythingError>,
+    done: bool,
+}
+
+impl Pager {
+    pub(crate) fn new(request: SearchRequest, page_size: u32) -> Pager {
+        Pager {
+            cursor: PageCursor {
+                offset: request.offset,
+                total_results: None,
+            },
+            request,
+            page_size: page_size.max(1),
+            buffer: VecDeque::new(),
+            error: None,
+            done: false,
+        }
+    }
+
+    pub(crate) fn resume(&mut self, cursor: PageCursor) {
+        self.cursor = cursor;
+        self.buffer.clear();
+        self.error = None;
+        self.done = false;
+    }
+
+    pub(crate) fn cursor(&self) -> PageCursor {
+        self.cursor
+    }
+
+    /// The next item that needs no query, if any.
+    pub(crate) fn pop(&mut self) -> Option<EverythingResult<SearchResult>> {
+        if let Some(err) = self.error.take() {
+            return Some(Err(err));
+        }
+        let result = self.buffer.pop_front()?;
+        self.cursor.offset += 1;
+        Some(Ok(result))
+    }
+
+    /// The query for the next page, `None` once every page was read.
+    pub(crate) fn next_request(&self) -> Option<SearchRequest> {
+        (!self.done).then(|| SearchRequest {
+            offset: self.cursor.offset,
+            max_results: self.page_size,
+            ..self.request.clone()
+        })
+    }
+
+    /// Takes in the reply to `next_request`.
+    /// A failed query or a total that changed since the last page is yielded as an error before the page.
+    pub(crate) fn accept(&mut self, page: EverythingResult<ResultPage>) {
+        let page = match page {
+            Ok(page) => page,
+            Err(err) => {
+                // Stop instead of failing the same way forever, `resume` retries.
+                self.done = true;
+                self.error = Some(err);
+                return;
+            }
+        };
+
+        let previous = self.cursor.total_results.replace(page.total_results);
+        if let Some(previous) = previous.filter(|&previous| previous != page.total_results) {
+            self.error = Some(EverythingError::ResultsChanged {
+                previous,
+                current: page.total_results,
+            });
+        }
+        self.done = (page.results.len() as u32) < self.page_size;
+        self.buffer = page.results.into();
+    }
+}
+
+/// Iterates over every result of a search, querying one page at a time, see `Everything::paged`.
+///
+/// Each item is an owned result. When the number of results changes between pages,
+/// for example because files were created, `EverythingError::ResultsChanged` is yielded once before the page
+/// and iteration goes on with the new total. After a failed query iteration stops, use `cursor` and `resume` to retry.
+#[derive(Debug)]
+pub struct Paged<'a> {
+    everything: &'a Everything,
+    pager: Pager,
+}
+
+impl<'a> Paged<'a> {
+    /// The position of the next result.
+    pub fn cursor(&self) -> PageCursor {
+        self.pager.cursor()
+    }
+
+    /// Continues from `cursor`, returned by `cursor` of this or another `Paged` over the same request.
+    pub fn resume(mut self, cursor: PageCursor) -> Paged<'a> {
+        self.pager.resume(cursor);
+        self
+    }
+}
+
+impl Iterator for Paged<'_> {
+    type Item = EverythingResult<SearchResult>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pager.pop() {
+                return Some(item);
+            }
+            let request = self.pager.next_request()?;
+            let page = self.everything.search(&request);
+            self.pager.accept(page);
+        }
+    }
+}
+
+impl Everything {
+    /// Returns every result of `request` by running it repeatedly with `page_size` results at a time,
+    /// starting at the request's offset. The request's `max_results` is replaced by `page_size`.
+    /// See `Paged` for how changing results and errors are reported.
+    ///
+    /// # Example
+    /// ```rust
+    /// use everything_rs::{Everything, InMemoryBackend, InMemoryEntry, Query};
+    ///
+    /// let everything = Everything::with_backend(InMemoryBackend::new(
+    ///     (0..5).map(|i| InMemoryEntry::file("C:\\logs", &format!("{i}.log"))).collect(),
+    /// ));
+    /// let names: Vec<_> = everything
+    ///     .paged(Query::text(".log"), 2)
+    ///     .map(|result| result.unwrap().file_name.unwrap())
+    ///     .collect();
+    /// assert_eq!(names, ["0.log", "1.log", "2.log", "3.log", "4.log"]);
+    /// ```
+    pub fn paged(&self, request: impl Into<SearchRequest>, page_size: u32) -> Paged<'_> {
+        Paged {
+            everything: self,
+            pager: Pager::new(request.into(), page_size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, IpcTransport, Item1, List1, LoopbackTransport, Query1};
+    use crate::QueryLimits;
+    use crate::{CancellationToken, InMemoryBackend, InMemoryEntry, SlowQueryPolicy};
+    use crate::{EverythingRequestFlags, EverythingSort};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn file_name(i: usize) -> String {
+        format!("file{i:02}.txt")
+    }
+
+    fn numbered_files(count: usize) -> Everything {
+        let files = (0..count)
+            .map(|i| InMemoryEntry::file("C:\\test", &file_name(i)))
+            .collect();
+        Everything::with_backend(IpcClient::new(LoopbackTransport::new(
+            InMemoryBackend::new(files),
+        )))
+    }
+
+    fn name(result: EverythingResult<SearchResult>) -> String {
+        result.unwrap().file_name.unwrap()
+    }
+
+    /// Answers every query with the first `files` file names, ignoring the search.
+    struct Changing {
+        files: Arc<Mutex<usize>>,
+    }
+
+    impl IpcTransport for Changing {
+        fn send_command(&self, _command: u32, _param: u32) -> EverythingResult<u32> {
+            Ok(1)
+        }
+
+        fn send_copydata(&self, _copydata: u32, _data: &[u8]) -> EverythingResult<u32> {
+            Ok(1)
+        }
+
+        fn send_query(
+            &self,
+            _copydata: u32,
+            _reply_id: u32,
+            encode: &dyn Fn(u32) -> Vec<u8>,
+            _limits: &QueryLimits,
+        ) -> EverythingResult<Vec<u8>> {
+            // The pages ask for the name and path sorted by name, which is sent as a version 1 query.
+            let query = Query1::decode(&encode(0))?;
+            let files = *self.files.lock().unwrap();
+            let items = (0..files)
+                .skip(query.offset as usize)
+                .take(query.max_results as usize)
+                .map(|i| Item1 {
+                    name: file_name(i),
+                    path: "C:\\test".to_string(),
+                    ..Item1::default()
+                });
+            Ok(List1 {
+                total_folders: 0,
+                total_files: files as u32,
+                total_items: files as u32,
+                offset: query.offset,
+                items: items.collect(),
+            }
+            .encode())
+        }
+    }
+
+    /// Counts the commands sent through `transport`.
+    struct Counting<T> {
+        transport: T,
+        commands: Arc<AtomicUsize>,
+    }
+
+    impl<T: IpcTransport> IpcTransport for Counting<T> {
+        fn send_command(&self, command: u32, param: u32) -> EverythingResult<u32> {
+            self.commands.fetch_add(1, Ordering::SeqCst);
+            self.transport.send_command(command, param)
+        }
+
+        fn send_copydata(&self, copydata: u32, data: &[u8]) -> EverythingResult<u32> {
+            self.transport.send_copydata(copydata, data)
+        }
+
+        fn send_query(
+            &self,
+            copydata: u32,
+            reply_id: u32,
+            encode: &dyn Fn(u32) -> Vec<u8>,
+            limits: &QueryLimits,
+        ) -> EverythingResult<Vec<u8>> {
+            self.transport
+                .send_query(copydata, reply_id, encode, limits)
+        }
+    }
+
+    #[test]
+    fn reads_every_page() {
+        let everything = numbered_files(7);
+        let mut paged = everything.paged(SearchRequest::new("file"), 3);
+        assert_eq!(paged.cursor(), PageCursor::default());
+
+        let first: Vec<_> = paged.by_ref().take(4).map(name).collect();
+        assert_eq!(
+            first,
+            ["file00.txt", "file01.txt", "file02.txt", "file03.txt"]
+        );
+        assert_eq!(
+            paged.cursor(),
+            PageCursor {
+                offset: 4,
+                total_results: Some(7)
+            }
+        );
+        let rest: Vec<_> = paged.map(name).collect();
+        assert_eq!(rest, ["file04.txt", "file05.txt", "file06.txt"]);
+
+        // Every page was a separate windowed query.
+        assert_eq!(everything.get_result_offset(), 6);
+        assert_eq!(everything.get_max_results(), 3);
+
+        assert_eq!(everything.paged(SearchRequest::new("file"), 7).count(), 7);
+        let from_five = SearchRequest {
+            offset: 5,
+            ..SearchRequest::new("file")
+        };
+        assert_eq!(everything.paged(from_five, 0).count(), 2);
+    }
+
+    #[test]
+    fn reports_changed_totals() {
+        let files = Arc::new(Mutex::new(6));
+        let everything = Everything::with_backend(IpcClient::new(Changing {
+            files: Arc::clone(&files),
+        }));
+        let mut paged = everything.paged(SearchRequest::new("file"), 2);
+        assert_eq!(name(paged.next().unwrap()), "file00.txt");
+        assert_eq!(name(paged.next().unwrap()), "file01.txt");
+
+        *files.lock().unwrap() = 4;
+        assert!(matches!(
+            paged.next(),
+            Some(Err(EverythingError::ResultsChanged {
+                previous: 6,
+                current: 4
+            }))
+        ));
+        let rest: Vec<_> = paged.map(name).collect();
+        assert_eq!(rest, ["file02.txt", "file03.txt"]);
+    }
+
+    #[test]
+    fn resumes_after_an_error() {
+        let everything = numbered_files(5);
+        let cancel = CancellationToken::new();
+        let request = SearchRequest {
+            cancel: Some(cancel.clone()),
+            ..SearchRequest::new("file")
+        };
+        let mut paged = everything.paged(request, 2);
+        let first: Vec<_> = paged.by_ref().take(3).map(name).collect();
+        assert_eq!(first, ["file00.txt", "file01.txt", "file02.txt"]);
+
+        // The rest of the page is still buffered.
+        cancel.cancel();
+        assert_eq!(name(paged.next().unwrap()), "file03.txt");
+        assert!(matches!(
+            paged.next(),
+            Some(Err(EverythingError::Cancelled))
+        ));
+        assert!(paged.next().is_none());
+        let cursor = paged.cursor();
+        assert_eq!(cursor.offset, 4);
+
+        let rest: Vec<_> = everything
+            .paged(SearchRequest::new("file"), 2)
+            .resume(cursor)
+            .map(name)
+            .collect();
+        assert_eq!(rest, ["file04.txt"]);
+    }
+
+    #[test]
+    fn probes_capabilities_once() {
+        let files = (0..5)
+            .map(|i| InMemoryEntry::file("C:\\test", &file_name(i)))
+            .collect();
+        let commands = Arc::new(AtomicUsize::new(0));
+        let everything = Everything::with_backend(IpcClient::new(Counting {
+            transport: LoopbackTransport::new(InMemoryBackend::new(files)),
+            commands: Arc::clone(&commands),
+        }));
+        let request = SearchRequest {
+            sort: EverythingSort::DateCreatedDescending,
+            request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::DateCreated,
+            slow_queries: SlowQueryPolicy::Degrade,
+            ..SearchRequest::new("file")
+        };
+
+        let names: Vec<_> = everything.paged(request.clone(), 2).map(name).collect();
+        assert_eq!(names.len(), 5);
+        let probed = commands.load(Ordering::SeqCst);
+
+        assert_eq!(everything.paged(request, 2).count(), 5);
+        assert_eq!(commands.load(Ordering::SeqCst), probed);
+
+        // The three pages above cost as many commands as a single probe.
+        everything.capabilities().unwrap();
+        assert_eq!(commands.load(Ordering::SeqCst), 2 * probed);
+    }
+}
@@ -0,0 +1,571 @@
+//! # Query
+//! A builder for the Everything search syntax.
+//! See <https://www.voidtools.com/support/everything/searching/> for the syntax being rendered.
+//! `parse` reads a search string back into an `Expr` tree.
+
+mod parser;
+mod render;
+
+pub use parser::{parse, Expr, ExprKind, ParseError, Value};
+
+use render::{quote_text, quote_value, Context, Render};
+use std::fmt;
+
+/// A search built from typed terms.
+/// `to_string` renders it as Everything search syntax with values quoted and escaped as needed,
+/// `Everything::set_query` passes it to `Everything::set_search`.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::query::{Comparison, DateValue, Query};
+///
+/// let query = Query::ext(["jpg", "png"])
+///     .and(Query::size(Comparison::GreaterThan(1024 * 1024)))
+///     .and(Query::date_modified(Comparison::Equal(DateValue::Today)))
+///     .and(Query::path("C:\\foo bar"));
+///
+/// assert_eq!(query.to_string(), "ext:jpg;png size:>1mb dm:today path:\"C:\\foo bar\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Query {
+    node: Node,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Node {
+    /// A single rendered term such as `ext:jpg` or `"foo bar"`.
+    Term(String),
+    /// Search syntax passed through untouched.
+    Raw(String),
+    And(Vec<Node>),
+    Or(Vec<Node>),
+    Not(Box<Node>),
+    /// A modifier such as `case:` applied to a term or group.
+    Modifier(&'static str, Box<Node>),
+}
+
+/// A comparison used by the `size:` and date functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison<T> {
+    Equal(T),
+    GreaterThan(T),
+    AtLeast(T),
+    LessThan(T),
+    AtMost(T),
+    /// An inclusive range, rendered as `start..end`.
+    Between(T, T),
+}
+
+/// A date value understood by `dm:`, `dc:`, `da:` and `dr:`.
+/// Relative values such as `ThisWeek` are evaluated by Everything when the search runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateValue {
+    Today,
+    Yesterday,
+    ThisWeek,
+    ThisMonth,
+    ThisYear,
+    LastWeek,
+    LastMonth,
+    LastYear,
+    /// The last `n` days, `last<n>days`.
+    LastDays(u32),
+    /// The last `n` weeks, `last<n>weeks`.
+    LastWeeks(u32),
+    /// The last `n` months, `last<n>months`.
+    LastMonths(u32),
+    /// The last `n` years, `last<n>years`.
+    LastYears(u32),
+    /// A calendar date, rendered as ISO 8601 `yyyy-mm-dd`.
+    Date {
+        year: u16,
+        month: u8,
+        day: u8,
+    },
+}
+
+/// A file attribute letter used by `attrib:`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Attribute {
+    ReadOnly,
+    Hidden,
+    System,
+    Directory,
+    Archive,
+    Device,
+    Normal,
+    Temporary,
+    SparseFile,
+    ReparsePoint,
+    Compressed,
+    Offline,
+    NotContentIndexed,
+    Encrypted,
+}
+
+impl Attribute {
+    /// The letter Everything uses for the attribute.
+    pub fn letter(self) -> char {
+        match self {
+            Attribute::ReadOnly => 'R',
+            Attribute::Hidden => 'H',
+            Attribute::System => 'S',
+            Attribute::Directory => 'D',
+            Attribute::Archive => 'A',
+            Attribute::Device => 'V',
+            Attribute::Normal => 'N',
+            Attribute::Temporary => 'T',
+            Attribute::SparseFile => 'P',
+            Attribute::ReparsePoint => 'L',
+            Attribute::Compressed => 'C',
+            Attribute::Offline => 'O',
+            Attribute::NotContentIndexed => 'I',
+            Attribute::Encrypted => 'E',
+        }
+    }
+}
+
+impl Query {
+    /// A query that matches everything and renders as an empty string.
+    pub fn new() -> Query {
+        Query {
+            node: Node::And(Vec::new()),
+        }
+    }
+
+    fn term(term: String) -> Query {
+        Query {
+            node: Node::Term(term),
+        }
+    }
+
+    /// Literal text matched against file names, quoted if it contains spaces or operators.
+    /// Wildcards `*` and `?` are kept.
+    pub fn text(text: &str) -> Query {
+        Query::term(quote_text(text))
+    }
+
+    /// Search syntax used as is, for example a string returned by `Everything::get_search`.
+    pub fn raw(search: &str) -> Query {
+        Query {
+            node: Node::Raw(search.trim().to_string()),
+        }
+    }
+
+    /// Parses `search` to check it is valid search syntax, see `parse`.
+    /// The query holds the search rendered back from the parsed tree.
+    pub fn parse(search: &str) -> Result<Query, ParseError> {
+        Ok(Query::raw(&parse(search)?.to_string()))
+    }
+
+    /// Any `function:value` pair, the value is quoted if needed.
+    pub fn function(name: &str, value: &str) -> Query {
+        Query::term(format!("{}:{}", name, quote_value(value)))
+    }
+
+    /// `ext:` matching any of `extensions`.
+    pub fn ext<I, S>(extensions: I) -> Query
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let extensions: Vec<String> = extensions
+            .into_iter()
+            .map(|ext| ext.as_ref().trim_start_matches('.').to_string())
+            .collect();
+        Query::function("ext", &extensions.join(";"))
+    }
+
+    /// `size:` in bytes. Sizes that are a whole number of kilobytes, megabytes or gigabytes are rendered with the unit.
+    pub fn size(size: Comparison<u64>) -> Query {
+        Query::term(format!("size:{}", size.render(|&bytes| format_size(bytes))))
+    }
+
+    /// `dm:`, the date modified.
+    pub fn date_modified(date: Comparison<DateValue>) -> Query {
+        Query::date("dm", date)
+    }
+
+    /// `dc:`, the date created.
+    pub fn date_created(date: Comparison<DateValue>) -> Query {
+        Query::date("dc", date)
+    }
+
+    /// `da:`, the date accessed.
+    pub fn date_accessed(date: Comparison<DateValue>) -> Query {
+        Query::date("da", date)
+    }
+
+    /// `dr:`, the date run.
+    pub fn date_run(date: Comparison<DateValue>) -> Query {
+        Query::date("dr", date)
+    }
+
+    fn date(function: &str, date: Comparison<DateValue>) -> Query {
+        Query::term(format!(
+            "{}:{}",
+            function,
+            date.render(DateValue::to_string)
+        ))
+    }
+
+    /// `path:`, matches `text` against the full path instead of the name.
+    pub fn path(text: &str) -> Query {
+        Query::function("path", text)
+    }
+
+    /// `parent:`, results directly inside the folder `path`.
+    pub fn parent(path: &str) -> Query {
+        Query::function("parent", path)
+    }
+
+    /// `attrib:`, results that have all of `attributes`.
+    pub fn attrib<I>(attributes: I) -> Query
+    where
+        I: IntoIterator<Item = Attribute>,
+    {
+        let letters: String = attributes.into_iter().map(Attribute::letter).collect();
+        Query::term(format!("attrib:{}", letters))
+    }
+
+    /// `regex:`, matches names against a regular expression.
+    pub fn regex(pattern: &str) -> Query {
+        Query::function("regex", pattern)
+    }
+
+    /// Results matching every query.
+    pub fn all<I: IntoIterator<Item = Query>>(queries: I) -> Query {
+        Query {
+            node: Node::And(queries.into_iter().map(|query| query.node).collect()),
+        }
+    }
+
+    /// Results matching any of the queries.
+    pub fn any<I: IntoIterator<Item = Query>>(queries: I) -> Query {
+        Query {
+            node: Node::Or(queries.into_iter().map(|query| query.node).collect()),
+        }
+    }
+
+    /// Results matching both queries.
+    pub fn and(self, other: Query) -> Query {
+        match self.node {
+            Node::And(mut nodes) => {
+                nodes.push(other.node);
+                Query {
+                    node: Node::And(nodes),
+                }
+            }
+            node => Query {
+                node: Node::And(vec![node, other.node]),
+            },
+        }
+    }
+
+    /// Results matching either query.
+    pub fn or(self, other: Query) -> Query {
+        match self.node {
+            Node::Or(mut nodes) => {
+                nodes.push(other.node);
+                Query {
+                    node: Node::Or(nodes),
+                }
+            }
+            node => Query {
+                node: Node::Or(vec![node, other.node]),
+            },
+        }
+    }
+
+    /// Results not matching this query, `!`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Query {
+        Query {
+            node: Node::Not(Box::new(self.node)),
+        }
+    }
+
+    pub(crate) fn modifier(self, modifier: &'static str) -> Query {
+        Query {
+            node: Node::Modifier(modifier, Box::new(self.node)),
+        }
+    }
+
+    /// `case:`, match case.
+    pub fn match_case(self) -> Query {
+        self.modifier("case")
+    }
+
+    /// `wholeword:`, match whole words only.
+    pub fn whole_word(self) -> Query {
+        self.modifier("wholeword")
+    }
+
+    /// `diacritics:`, match accent marks.
+    pub fn diacritics(self) -> Query {
+        self.modifier("diacritics")
+    }
+
+    /// Whether the query renders as an empty string.
+    pub fn is_empty(&self) -> bool {
+        self.node.is_empty()
+    }
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Query::new()
+    }
+}
+
+impl From<&str> for Query {
+    /// See `Query::raw`.
+    fn from(search: &str) -> Self {
+        Query::raw(search)
+    }
+}
+
+impl From<String> for Query {
+    /// See `Query::raw`.
+    fn from(search: String) -> Self {
+        Query::raw(&search)
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.node.render(Context::And))
+    }
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Term(_) => false,
+            Node::Raw(raw) => raw.is_empty(),
+            Node::And(nodes) | Node::Or(nodes) => nodes.iter().all(Node::is_empty),
+            Node::Not(node) | Node::Modifier(_, node) => node.is_empty(),
+        }
+    }
+}
+
+impl Render for Node {
+    fn render(&self, context: Context) -> String {
+        match self {
+            Node::Term(term) => term.clone(),
+            Node::Raw(raw) => {
+                let grouped = match context {
+                    Context::And => false,
+                    Context::Or => raw.contains(char::is_whitespace),
+                    Context::Prefix => raw.contains(|c: char| c.is_whitespace() || c == '|'),
+                };
+                render::group(raw.clone(), grouped)
+            }
+            Node::And(nodes) => render::and(&non_empty(nodes), context),
+            Node::Or(nodes) => render::or(&non_empty(nodes), context),
+            Node::Not(node) if node.is_empty() => String::new(),
+            Node::Not(node) => render::not(node.as_ref()),
+            Node::Modifier(_, node) if node.is_empty() => String::new(),
+            Node::Modifier(modifier, node) => render::modifier(modifier, node.as_ref()),
+        }
+    }
+}
+
+fn non_empty(nodes: &[Node]) -> Vec<&Node> {
+    nodes.iter().filter(|node| !node.is_empty()).collect()
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [(1 << 30, "gb"), (1 << 20, "mb"), (1 << 10, "kb")];
+    UNITS
+        .iter()
+        .find(|(unit, _)| bytes != 0 && bytes.is_multiple_of(*unit))
+        .map(|(unit, name)| format!("{}{}", bytes / unit, name))
+        .unwrap_or_else(|| bytes.to_string())
+}
+
+impl<T> Comparison<T> {
+    fn render(&self, value: impl Fn(&T) -> String) -> String {
+        match self {
+            Comparison::Equal(v) => value(v),
+            Comparison::GreaterThan(v) => format!(">{}", value(v)),
+            Comparison::AtLeast(v) => format!(">={}", value(v)),
+            Comparison::LessThan(v) => format!("<{}", value(v)),
+            Comparison::AtMost(v) => format!("<={}", value(v)),
+            Comparison::Between(start, end) => format!("{}..{}", value(start), value(end)),
+        }
+    }
+}
+
+impl fmt::Display for DateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateValue::Today => write!(f, "today"),
+            DateValue::Yesterday => write!(f, "yesterday"),
+            DateValue::ThisWeek => write!(f, "thisweek"),
+            DateValue::ThisMonth => write!(f, "thismonth"),
+            DateValue::ThisYear => write!(f, "thisyear"),
+            DateValue::LastWeek => write!(f, "lastweek"),
+            DateValue::LastMonth => write!(f, "lastmonth"),
+            DateValue::LastYear => write!(f, "lastyear"),
+            DateValue::LastDays(n) => write!(f, "last{}days", n),
+            DateValue::LastWeeks(n) => write!(f, "last{}weeks", n),
+            DateValue::LastMonths(n) => write!(f, "last{}months", n),
+            DateValue::LastYears(n) => write!(f, "last{}years", n),
+            DateValue::Date { year, month, day } => {
+                write!(f, "{:04}-{:02}-{:02}", year, month, day)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::test_entries;
+    use crate::{Everything, InMemoryBackend};
+
+    #[test]
+    fn renders_functions() {
+        assert_eq!(Query::ext([".jpg", "png"]).to_string(), "ext:jpg;png");
+        assert_eq!(
+            Query::size(Comparison::GreaterThan(1024 * 1024)).to_string(),
+            "size:>1mb"
+        );
+        assert_eq!(
+            Query::size(Comparison::Between(1500, 10 << 30)).to_string(),
+            "size:1500..10gb"
+        );
+        assert_eq!(Query::size(Comparison::AtMost(0)).to_string(), "size:<=0");
+        assert_eq!(
+            Query::date_modified(Comparison::Equal(DateValue::Today)).to_string(),
+            "dm:today"
+        );
+        assert_eq!(
+            Query::date_created(Comparison::Between(
+                DateValue::Date {
+                    year: 2024,
+                    month: 1,
+                    day: 5
+                },
+                DateValue::Date {
+                    year: 2024,
+                    month: 12,
+                    day: 31
+                }
+            ))
+            .to_string(),
+            "dc:2024-01-05..2024-12-31"
+        );
+        assert_eq!(
+            Query::date_accessed(Comparison::AtLeast(DateValue::LastDays(3))).to_string(),
+            "da:>=last3days"
+        );
+        assert_eq!(Query::path("C:\\foo").to_string(), "path:\"C:\\foo\"");
+        assert_eq!(
+            Query::parent("C:\\Program Files").to_string(),
+            "parent:\"C:\\Program Files\""
+        );
+        assert_eq!(
+            Query::attrib([Attribute::Hidden, Attribute::System]).to_string(),
+            "attrib:HS"
+        );
+        assert_eq!(Query::regex("^foo|bar$").to_string(), "regex:\"^foo|bar$\"");
+    }
+
+    #[test]
+    fn quotes_and_escapes_text() {
+        assert_eq!(Query::text("foo").to_string(), "foo");
+        assert_eq!(Query::text("*.txt").to_string(), "*.txt");
+        assert_eq!(Query::text("foo bar").to_string(), "\"foo bar\"");
+        assert_eq!(Query::text("!foo").to_string(), "\"!foo\"");
+        assert_eq!(Query::text("a|b").to_string(), "\"a|b\"");
+        assert_eq!(Query::text("<a>").to_string(), "\"<a>\"");
+        assert_eq!(Query::text("OR").to_string(), "\"OR\"");
+        assert_eq!(Query::text("dm:today").to_string(), "\"dm:today\"");
+        assert_eq!(Query::text("").to_string(), "\"\"");
+        assert_eq!(
+            Query::text("a \"b\"").to_string(),
+            "\"a \"#34:\"b\"#34:\"\""
+        );
+    }
+
+    #[test]
+    fn groups_operators() {
+        let a = || Query::text("a");
+        let b = || Query::text("b");
+        let c = || Query::text("c");
+
+        assert_eq!(a().and(b()).and(c()).to_string(), "a b c");
+        assert_eq!(a().or(b()).or(c()).to_string(), "a|b|c");
+        // `|` binds tighter than a space so only an AND inside an OR needs brackets.
+        assert_eq!(a().and(b().or(c())).to_string(), "a b|c");
+        assert_eq!(a().and(b()).or(c()).to_string(), "<a b>|c");
+        assert_eq!(a().not().to_string(), "!a");
+        assert_eq!(a().or(b()).not().to_string(), "!<a|b>");
+        assert_eq!(a().and(b()).not().to_string(), "!<a b>");
+        assert_eq!(
+            Query::any([a(), Query::all([b(), c().not()])]).to_string(),
+            "a|<b !c>"
+        );
+        assert_eq!(Query::new().and(a()).to_string(), "a");
+        assert_eq!(Query::new().not().to_string(), "");
+        assert!(Query::all([Query::new(), Query::raw("  ")]).is_empty());
+    }
+
+    #[test]
+    fn applies_modifiers() {
+        assert_eq!(Query::text("Foo").match_case().to_string(), "case:Foo");
+        assert_eq!(
+            Query::text("foo").whole_word().diacritics().to_string(),
+            "diacritics:wholeword:foo"
+        );
+        assert_eq!(
+            Query::text("a")
+                .and(Query::text("b"))
+                .match_case()
+                .to_string(),
+            "case:<a b>"
+        );
+        assert_eq!(
+            Query::ext(["txt"])
+                .and(Query::text("Readme").match_case())
+                .to_string(),
+            "ext:txt case:Readme"
+        );
+    }
+
+    #[test]
+    fn groups_raw_search() {
+        let raw = Query::raw("foo bar");
+        assert_eq!(raw.to_string(), "foo bar");
+        assert_eq!(
+            raw.clone().or(Query::text("baz")).to_string(),
+            "<foo bar>|baz"
+        );
+        assert_eq!(Query::raw("a|b").not().to_string(), "!<a|b>");
+        assert_eq!(Query::from("a|b").or(Query::text("c")).to_string(), "a|b|c");
+        assert_eq!(
+            Query::from(String::from("ext:txt"))
+                .and(Query::text("x"))
+                .to_string(),
+            "ext:txt x"
+        );
+    }
+
+    #[test]
+    fn sets_search() {
+        let everything = Everything::with_backend(InMemoryBackend::new(test_entries(&[
+            "test1.txt",
+            "test2.jpg",
+        ])));
+
+        everything.set_query(&Query::text("test").and(Query::text("jpg")));
+        assert_eq!(everything.get_search().unwrap(), "test jpg");
+        everything.query().unwrap();
+        assert_eq!(everything.get_result_file_name(0).unwrap(), "test2.jpg");
+
+        let query = everything.get_query().unwrap().and(Query::text("foo"));
+        assert_eq!(query.to_string(), "test jpg foo");
+    }
+}
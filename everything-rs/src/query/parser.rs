@@ -0,0 +1,801 @@
+use super::render::{self, quote_text, quote_value, Context, Render};
+use super::Comparison;
+use std::fmt;
+use std::ops::Range;
+
+/// Modifiers change how the expression that follows them is matched, for example `case:foo`.
+const MODIFIERS: &[&str] = &[
+    "ascii",
+    "case",
+    "diacritics",
+    "file",
+    "files",
+    "folder",
+    "folders",
+    "noascii",
+    "nocase",
+    "nodiacritics",
+    "nopath",
+    "noregex",
+    "nowfn",
+    "nowholefilename",
+    "nowholeword",
+    "nowildcards",
+    "noww",
+    "path",
+    "regex",
+    "utf8",
+    "wfn",
+    "wholefilename",
+    "wholeword",
+    "wildcards",
+    "ww",
+];
+
+/// Macros stand on their own and expand to a predefined search, for example `audio:`.
+const MACROS: &[&str] = &[
+    "audio", "zip", "doc", "exe", "pic", "video", "quot", "apos", "amp", "lt", "gt",
+];
+
+/// Modifiers that also stand on their own, `folder:` alone matches every folder.
+const STANDALONE_MODIFIERS: &[&str] = &["file", "files", "folder", "folders"];
+
+/// A node of a parsed search with the byte range of the search string it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    /// Expressions separated by whitespace or `AND`, all of them must match. An empty search is an empty `And`.
+    And(Vec<Expr>),
+    /// Expressions separated by `|` or `OR`, any of them must match. Binds tighter than `And`.
+    Or(Vec<Expr>),
+    /// `!expr` or `NOT expr`
+    Not(Box<Expr>),
+    /// `<expr>`
+    Group(Box<Expr>),
+    /// A modifier such as `case:` applied to the expression directly after it.
+    Modifier { name: String, expr: Box<Expr> },
+    /// A macro such as `audio:`, or `file:` and `folder:` on their own.
+    Macro(String),
+    /// A `function:value` pair such as `ext:jpg` or `size:>1mb`.
+    Function { name: String, value: Value },
+    /// Text matched against names, with quotes removed and character macros such as `#34:` decoded.
+    Text(String),
+}
+
+/// The value of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Text(String),
+    /// A value starting with `=`, `<`, `<=`, `>` or `>=`, or a `start..end` range.
+    /// `Comparison::Equal` is written with an explicit `=`.
+    Comparison(Comparison<String>),
+}
+
+/// Errors returned by `parse`, each with the byte range of the offending input.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("Unterminated quote at {}", .0.start)]
+    UnterminatedQuote(Range<usize>),
+    #[error("Unclosed group at {}", .0.start)]
+    UnclosedGroup(Range<usize>),
+    #[error("Unmatched `>` at {}", .0.start)]
+    UnmatchedClose(Range<usize>),
+    #[error("Missing expression after `{operator}` at {}", .span.start)]
+    MissingOperand {
+        operator: String,
+        span: Range<usize>,
+    },
+}
+
+impl ParseError {
+    /// The byte range of the search string the error refers to.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            ParseError::UnterminatedQuote(span)
+            | ParseError::UnclosedGroup(span)
+            | ParseError::UnmatchedClose(span)
+            | ParseError::MissingOperand { span, .. } => span.clone(),
+        }
+    }
+}
+
+/// Parses an Everything search string.
+/// `to_string` on the result renders it back to an equivalent search, normalizing whitespace and quoting.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::query::{parse, ExprKind};
+///
+/// let expr = parse("ext:jpg  <holiday|trip> !\"thumb nail\"").unwrap();
+/// assert!(matches!(expr.kind, ExprKind::And(ref terms) if terms.len() == 3));
+/// assert_eq!(expr.to_string(), "ext:jpg <holiday|trip> !\"thumb nail\"");
+///
+/// let error = parse("<holiday|trip").unwrap_err();
+/// assert_eq!(error.span(), 0..1);
+/// ```
+pub fn parse(search: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser { search, pos: 0 };
+    parser.parse_and(0)
+}
+
+struct Parser<'a> {
+    search: &'a str,
+    pos: usize,
+}
+
+/// A run of a term, quoted runs keep their content as is.
+struct Segment {
+    quoted: bool,
+    span: Range<usize>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.search[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Whether the next character ends the current term.
+    fn at_boundary(&self) -> bool {
+        self.peek()
+            .is_none_or(|c| c.is_whitespace() || c == '|' || c == '>')
+    }
+
+    /// Whether the operator keyword `keyword` starts at the current position.
+    /// Keywords are upper case and stand on their own, `ORDER` and `or` are text.
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.search[self.pos..]
+            .strip_prefix(keyword)
+            .is_some_and(|rest| {
+                rest.chars()
+                    .next()
+                    .is_none_or(|c| c.is_whitespace() || matches!(c, '|' | '<' | '>'))
+            })
+    }
+
+    /// Whether there is no operand at the current position, after an operator.
+    fn at_operand_end(&self) -> bool {
+        matches!(self.peek(), None | Some('|') | Some('>'))
+            || self.at_keyword("AND")
+            || self.at_keyword("OR")
+    }
+
+    fn parse_and(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        let mut terms = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => break,
+                Some('>') if depth > 0 => break,
+                Some('>') => return Err(ParseError::UnmatchedClose(self.pos..self.pos + 1)),
+                Some('|') => return Err(missing_operand("|", self.pos..self.pos + 1)),
+                Some(_) if self.at_keyword("OR") => {
+                    return Err(missing_operand("OR", self.pos..self.pos + 2))
+                }
+                // `AND` is the same as the space between terms.
+                Some(_) if self.at_keyword("AND") => {
+                    let keyword = self.pos..self.pos + 3;
+                    self.pos = keyword.end;
+                    self.skip_whitespace();
+                    if terms.is_empty() || self.at_operand_end() {
+                        return Err(missing_operand("AND", keyword));
+                    }
+                }
+                Some(_) => terms.push(self.parse_or(depth)?),
+            }
+        }
+
+        if terms.len() == 1 {
+            return Ok(terms.remove(0));
+        }
+        let span = match (terms.first(), terms.last()) {
+            (Some(first), Some(last)) => first.span.start..last.span.end,
+            _ => self.pos..self.pos,
+        };
+        Ok(Expr {
+            kind: ExprKind::And(terms),
+            span,
+        })
+    }
+
+    fn parse_or(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        let mut terms = vec![self.parse_unary(depth)?];
+        loop {
+            let before = self.pos;
+            self.skip_whitespace();
+            let len = match self.peek() {
+                Some('|') => 1,
+                Some(_) if self.at_keyword("OR") => 2,
+                _ => {
+                    self.pos = before;
+                    break;
+                }
+            };
+            let operator = self.pos..self.pos + len;
+            self.pos = operator.end;
+            self.skip_whitespace();
+            if self.at_operand_end() {
+                return Err(missing_operand(&self.search[operator.clone()], operator));
+            }
+            terms.push(self.parse_unary(depth)?);
+        }
+
+        if terms.len() == 1 {
+            return Ok(terms.remove(0));
+        }
+        let span = terms[0].span.start..terms[terms.len() - 1].span.end;
+        Ok(Expr {
+            kind: ExprKind::Or(terms),
+            span,
+        })
+    }
+
+    fn parse_unary(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        if self.at_keyword("NOT") {
+            self.pos += 3;
+            self.skip_whitespace();
+            if self.at_operand_end() {
+                return Err(missing_operand("NOT", start..start + 3));
+            }
+            let expr = self.parse_unary(depth)?;
+            return Ok(Expr {
+                span: start..expr.span.end,
+                kind: ExprKind::Not(Box::new(expr)),
+            });
+        }
+        match self.peek() {
+            Some('!') => {
+                self.bump();
+                if self.at_boundary() {
+                    return Err(missing_operand("!", start..self.pos));
+                }
+                let expr = self.parse_unary(depth)?;
+                Ok(Expr {
+                    span: start..expr.span.end,
+                    kind: ExprKind::Not(Box::new(expr)),
+                })
+            }
+            Some('<') => {
+                self.bump();
+                let expr = self.parse_and(depth + 1)?;
+                if self.peek() != Some('>') {
+                    return Err(ParseError::UnclosedGroup(start..start + 1));
+                }
+                self.bump();
+                Ok(Expr {
+                    kind: ExprKind::Group(Box::new(expr)),
+                    span: start..self.pos,
+                })
+            }
+            _ => self.parse_term(depth),
+        }
+    }
+
+    fn parse_term(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        let Some(name) = self.function_name() else {
+            let segments = self.read_segments()?;
+            return Ok(Expr {
+                kind: ExprKind::Text(self.decode(&segments)),
+                span: start..self.pos,
+            });
+        };
+
+        self.pos += name.len() + 1;
+        let lower = name.to_ascii_lowercase();
+        let name = name.to_string();
+        let standalone =
+            MACROS.contains(&lower.as_str()) || STANDALONE_MODIFIERS.contains(&lower.as_str());
+        if standalone && self.at_boundary() {
+            return Ok(Expr {
+                kind: ExprKind::Macro(name),
+                span: start..self.pos,
+            });
+        }
+        if MODIFIERS.contains(&lower.as_str()) {
+            if self.at_boundary() {
+                return Err(missing_operand(&format!("{}:", name), start..self.pos));
+            }
+            let expr = self.parse_unary(depth)?;
+            return Ok(Expr {
+                span: start..expr.span.end,
+                kind: ExprKind::Modifier {
+                    name,
+                    expr: Box::new(expr),
+                },
+            });
+        }
+        let value = self.read_value()?;
+        Ok(Expr {
+            kind: ExprKind::Function { name, value },
+            span: start..self.pos,
+        })
+    }
+
+    /// The name of a `name:` prefix at the current position.
+    /// A single letter is a drive such as `C:` rather than a function.
+    fn function_name(&self) -> Option<&'a str> {
+        let rest = &self.search[self.pos..];
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let len = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))?;
+        (len >= 2 && rest[len..].starts_with(':')).then(|| &rest[..len])
+    }
+
+    fn read_value(&mut self) -> Result<Value, ParseError> {
+        let rest = &self.search[self.pos..];
+        let operator = ["<=", ">=", "<", ">", "="]
+            .into_iter()
+            .find(|operator| rest.starts_with(operator));
+        if let Some(operator) = operator {
+            self.pos += operator.len();
+            let value = self.read_segments()?;
+            let value = self.decode(&value);
+            return Ok(Value::Comparison(match operator {
+                "<=" => Comparison::AtMost(value),
+                ">=" => Comparison::AtLeast(value),
+                "<" => Comparison::LessThan(value),
+                ">" => Comparison::GreaterThan(value),
+                _ => Comparison::Equal(value),
+            }));
+        }
+
+        let mut segments = self.read_segments()?;
+        // A `..` outside of quotes separates the start and end of a range.
+        let split = segments.iter().enumerate().find_map(|(index, segment)| {
+            let text = &self.search[segment.span.clone()];
+            let dots = text.find("..").filter(|_| !segment.quoted)?;
+            Some((index, segment.span.start + dots))
+        });
+        let Some((index, dots)) = split else {
+            return Ok(Value::Text(self.decode(&segments)));
+        };
+
+        let mut end = segments.split_off(index);
+        let middle = &mut end[0];
+        segments.push(Segment {
+            quoted: false,
+            span: middle.span.start..dots,
+        });
+        middle.span.start = dots + 2;
+        Ok(Value::Comparison(Comparison::Between(
+            self.decode(&segments),
+            self.decode(&end),
+        )))
+    }
+
+    /// Reads a term up to the next whitespace, `|`, `<` or `>` outside of quotes.
+    fn read_segments(&mut self) -> Result<Vec<Segment>, ParseError> {
+        let mut segments = Vec::new();
+        loop {
+            match self.peek() {
+                None => break,
+                Some(c) if c.is_whitespace() || matches!(c, '|' | '<' | '>') => break,
+                Some('"') => {
+                    let open = self.pos;
+                    let start = open + 1;
+                    let len = self.search[start..]
+                        .find('"')
+                        .ok_or(ParseError::UnterminatedQuote(open..start))?;
+                    segments.push(Segment {
+                        quoted: true,
+                        span: start..start + len,
+                    });
+                    self.pos = start + len + 1;
+                }
+                Some(_) => {
+                    let start = self.pos;
+                    while self
+                        .peek()
+                        .is_some_and(|c| !c.is_whitespace() && !matches!(c, '|' | '<' | '>' | '"'))
+                    {
+                        self.bump();
+                    }
+                    segments.push(Segment {
+                        quoted: false,
+                        span: start..self.pos,
+                    });
+                }
+            }
+        }
+        Ok(segments)
+    }
+
+    fn decode(&self, segments: &[Segment]) -> String {
+        let mut text = String::new();
+        for segment in segments {
+            let raw = &self.search[segment.span.clone()];
+            if segment.quoted {
+                text.push_str(raw);
+            } else {
+                decode_character_macros(raw, &mut text);
+            }
+        }
+        text
+    }
+}
+
+fn missing_operand(operator: &str, span: Range<usize>) -> ParseError {
+    ParseError::MissingOperand {
+        operator: operator.to_string(),
+        span,
+    }
+}
+
+/// Decodes `#<n>:` and `#x<n>:`, the decimal and hexadecimal character macros.
+fn decode_character_macros(raw: &str, text: &mut String) {
+    let mut rest = raw;
+    while let Some(hash) = rest.find('#') {
+        text.push_str(&rest[..hash]);
+        let after = &rest[hash + 1..];
+        let (digits, radix) = match after.strip_prefix('x') {
+            Some(hex) => (hex, 16),
+            None => (after, 10),
+        };
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        let character = (len > 0 && digits[len..].starts_with(':'))
+            .then(|| u32::from_str_radix(&digits[..len], radix).ok())
+            .flatten()
+            .and_then(char::from_u32);
+        match character {
+            Some(character) => {
+                text.push(character);
+                rest = &digits[len + 1..];
+            }
+            None => {
+                text.push('#');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+}
+
+impl Render for Expr {
+    fn render(&self, context: Context) -> String {
+        match &self.kind {
+            ExprKind::And(terms) => render::and(&terms.iter().collect::<Vec<_>>(), context),
+            ExprKind::Or(terms) => render::or(&terms.iter().collect::<Vec<_>>(), context),
+            ExprKind::Not(expr) => render::not(expr.as_ref()),
+            ExprKind::Group(expr) => format!("<{}>", expr.render(Context::And)),
+            ExprKind::Modifier { name, expr } => render::modifier(name, expr.as_ref()),
+            ExprKind::Macro(name) => format!("{}:", name),
+            ExprKind::Function { name, value } => format!("{}:{}", name, value),
+            ExprKind::Text(text) => quote_text(text),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Context::And))
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Text(text) => f.write_str(&quote_value(text)),
+            Value::Comparison(Comparison::Equal(text)) => write!(f, "={}", quote_value(text)),
+            Value::Comparison(Comparison::GreaterThan(text)) => {
+                write!(f, ">{}", quote_value(text))
+            }
+            Value::Comparison(Comparison::AtLeast(text)) => write!(f, ">={}", quote_value(text)),
+            Value::Comparison(Comparison::LessThan(text)) => write!(f, "<{}", quote_value(text)),
+            Value::Comparison(Comparison::AtMost(text)) => write!(f, "<={}", quote_value(text)),
+            Value::Comparison(Comparison::Between(start, end)) => {
+                write!(f, "{}..{}", quote_value(start), quote_value(end))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::{DateValue, Query};
+    use crate::test_util::xorshift;
+
+    fn text(text: &str, span: Range<usize>) -> Expr {
+        Expr {
+            kind: ExprKind::Text(text.to_string()),
+            span,
+        }
+    }
+
+    /// Parses, renders and parses again, checking the second parse renders the same.
+    fn round_trip(search: &str) -> String {
+        let rendered = parse(search).unwrap().to_string();
+        assert_eq!(
+            parse(&rendered).unwrap().to_string(),
+            rendered,
+            "{}",
+            search
+        );
+        rendered
+    }
+
+    #[test]
+    fn parses_operators() {
+        let expr = parse("a b|c !d").unwrap();
+        assert_eq!(
+            expr,
+            Expr {
+                kind: ExprKind::And(vec![
+                    text("a", 0..1),
+                    Expr {
+                        kind: ExprKind::Or(vec![text("b", 2..3), text("c", 4..5)]),
+                        span: 2..5,
+                    },
+                    Expr {
+                        kind: ExprKind::Not(Box::new(text("d", 7..8))),
+                        span: 6..8,
+                    },
+                ]),
+                span: 0..8,
+            }
+        );
+
+        let expr = parse("<a  b> | c").unwrap();
+        let ExprKind::Or(terms) = expr.kind else {
+            panic!("expected an or, got {:?}", expr.kind);
+        };
+        assert_eq!(terms[0].span, 0..6);
+        assert!(matches!(&terms[0].kind, ExprKind::Group(inner) if inner.span == (1..5)));
+        assert_eq!(terms[1], text("c", 9..10));
+
+        assert_eq!(parse("").unwrap().kind, ExprKind::And(Vec::new()));
+        assert_eq!(parse("  ").unwrap().kind, ExprKind::And(Vec::new()));
+    }
+
+    #[test]
+    fn parses_keyword_operators() {
+        assert!(matches!(parse("a OR b").unwrap().kind, ExprKind::Or(terms) if terms.len() == 2));
+        assert_eq!(
+            parse("a AND b").unwrap().kind,
+            ExprKind::And(vec![text("a", 0..1), text("b", 6..7)])
+        );
+        assert_eq!(
+            parse("NOT a").unwrap(),
+            Expr {
+                kind: ExprKind::Not(Box::new(text("a", 4..5))),
+                span: 0..5,
+            }
+        );
+        assert_eq!(round_trip("a OR <b AND NOT c>"), "a|<b !c>");
+
+        // Only upper case words standing on their own are operators.
+        assert_eq!(round_trip("or ORDER NOTE"), "or ORDER NOTE");
+        assert_eq!(parse("a OR"), Err(missing_operand("OR", 2..4)));
+        assert_eq!(parse("OR a"), Err(missing_operand("OR", 0..2)));
+        assert_eq!(parse("a AND OR b"), Err(missing_operand("AND", 2..5)));
+        assert_eq!(parse("NOT"), Err(missing_operand("NOT", 0..3)));
+    }
+
+    #[test]
+    fn round_trips_quoted_keywords() {
+        assert_eq!(
+            parse("\"AND\" \"OR\"").unwrap().kind,
+            ExprKind::And(vec![text("AND", 0..5), text("OR", 6..10)])
+        );
+        assert_eq!(
+            round_trip("\"AND\" \"OR\" \"NOT\""),
+            "\"AND\" \"OR\" \"NOT\""
+        );
+        assert_eq!(round_trip("a|\"OR\"|b"), "a|\"OR\"|b");
+
+        // The builder and the parser quote the same way.
+        let query = Query::text("OR")
+            .or(Query::text("AND"))
+            .and(Query::text("x:y"));
+        assert_eq!(round_trip(&query.to_string()), query.to_string());
+        assert_eq!(
+            parse(&query.to_string()).unwrap().to_string(),
+            "\"OR\"|\"AND\" \"x:y\""
+        );
+    }
+
+    #[test]
+    fn parses_functions() {
+        let expr =
+            parse("ext:jpg;png size:1mb..10mb dm:>=today parent:\"C:\\Program Files\"").unwrap();
+        let ExprKind::And(terms) = expr.kind else {
+            panic!("expected an and");
+        };
+        let function = |name: &str, value: Value| ExprKind::Function {
+            name: name.to_string(),
+            value,
+        };
+        assert_eq!(
+            terms[0].kind,
+            function("ext", Value::Text("jpg;png".to_string()))
+        );
+        assert_eq!(terms[0].span, 0..11);
+        assert_eq!(
+            terms[1].kind,
+            function(
+                "size",
+                Value::Comparison(Comparison::Between("1mb".to_string(), "10mb".to_string()))
+            )
+        );
+        assert_eq!(
+            terms[2].kind,
+            function(
+                "dm",
+                Value::Comparison(Comparison::AtLeast("today".to_string()))
+            )
+        );
+        assert_eq!(
+            terms[3].kind,
+            function("parent", Value::Text("C:\\Program Files".to_string()))
+        );
+
+        // A drive letter is not a function.
+        assert_eq!(parse("C:\\foo").unwrap(), text("C:\\foo", 0..6));
+        assert_eq!(
+            parse("size:\"1..2\"").unwrap().kind,
+            function("size", Value::Text("1..2".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_modifiers_and_macros() {
+        let expr = parse("case:<Foo Bar> audio: regex:\"^a|b$\"").unwrap();
+        let ExprKind::And(terms) = expr.kind else {
+            panic!("expected an and");
+        };
+        let ExprKind::Modifier { name, expr } = &terms[0].kind else {
+            panic!("expected a modifier");
+        };
+        assert_eq!(name, "case");
+        assert!(matches!(expr.kind, ExprKind::Group(_)));
+        assert_eq!(terms[0].span, 0..14);
+        assert_eq!(terms[1].kind, ExprKind::Macro("audio".to_string()));
+        assert_eq!(
+            parse("folder: a").unwrap().kind,
+            ExprKind::And(vec![
+                Expr {
+                    kind: ExprKind::Macro("folder".to_string()),
+                    span: 0..7,
+                },
+                text("a", 8..9),
+            ])
+        );
+        assert_eq!(
+            terms[2].kind,
+            ExprKind::Modifier {
+                name: "regex".to_string(),
+                expr: Box::new(text("^a|b$", 28..35)),
+            }
+        );
+    }
+
+    #[test]
+    fn unquotes_text() {
+        assert_eq!(
+            parse("\"foo bar\"baz").unwrap().kind,
+            ExprKind::Text("foo barbaz".to_string())
+        );
+        assert_eq!(
+            parse("a#34:b").unwrap().kind,
+            ExprKind::Text("a\"b".to_string())
+        );
+        assert_eq!(
+            parse("a#x41:").unwrap().kind,
+            ExprKind::Text("aA".to_string())
+        );
+        assert_eq!(
+            parse("#tag").unwrap().kind,
+            ExprKind::Text("#tag".to_string())
+        );
+        assert_eq!(
+            parse("\"a|b\"").unwrap().kind,
+            ExprKind::Text("a|b".to_string())
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_spans() {
+        assert_eq!(parse("foo \"bar"), Err(ParseError::UnterminatedQuote(4..5)));
+        assert_eq!(parse("a <b c"), Err(ParseError::UnclosedGroup(2..3)));
+        assert_eq!(parse("a> b"), Err(ParseError::UnmatchedClose(1..2)));
+        assert_eq!(parse("a |"), Err(missing_operand("|", 2..3)));
+        assert_eq!(parse("| a"), Err(missing_operand("|", 0..1)));
+        assert_eq!(parse("a ! b"), Err(missing_operand("!", 2..3)));
+        assert_eq!(parse("x case:"), Err(missing_operand("case:", 2..7)));
+        assert_eq!(
+            parse("a |").unwrap_err().to_string(),
+            "Missing expression after `|` at 2"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for search in [
+            "a b|c !d",
+            "<a b>|c",
+            "!<a|b>",
+            "ext:jpg;png size:1mb..10mb dm:>=today",
+            "parent:\"C:\\Program Files\" case:Foo",
+            "case:<Foo Bar> audio: regex:\"^a|b$\"",
+            "size:..10mb size:<=2gb ext:",
+            "folder: a|b file:x",
+            "\"!foo\" \"dm:today\" \"a\"#34:\"b\"",
+        ] {
+            assert_eq!(round_trip(search), search);
+        }
+
+        assert_eq!(round_trip("  a   b |  c "), "a b|c");
+        assert_eq!(round_trip("\"foo\" \"foo bar\""), "foo \"foo bar\"");
+        assert_eq!(round_trip("a#34:b"), "\"a\"#34:\"b\"");
+    }
+
+    #[test]
+    fn parses_builder_output() {
+        let query = Query::ext(["jpg"])
+            .and(Query::size(Comparison::Between(1024, 2048)))
+            .and(Query::date_modified(Comparison::Equal(DateValue::Today)))
+            .and(Query::path("C:\\foo bar"))
+            .and(Query::text("a").or(Query::text("b c")).not())
+            .and(Query::regex("^x|y$").match_case());
+        let search = query.to_string();
+        assert_eq!(round_trip(&search), search);
+        assert_eq!(Query::parse(&search).unwrap(), Query::raw(&search));
+        assert!(Query::parse("<a").is_err());
+    }
+
+    #[test]
+    fn renders_built_trees() {
+        let and = Expr {
+            kind: ExprKind::And(vec![text("a", 0..0), text("b", 0..0)]),
+            span: 0..0,
+        };
+        let or = Expr {
+            kind: ExprKind::Or(vec![and.clone(), text("c d", 0..0)]),
+            span: 0..0,
+        };
+        assert_eq!(or.to_string(), "<a b>|\"c d\"");
+        let not = Expr {
+            kind: ExprKind::Not(Box::new(or)),
+            span: 0..0,
+        };
+        assert_eq!(not.to_string(), "!<<a b>|\"c d\">");
+    }
+
+    #[test]
+    fn round_trips_random_input() {
+        let mut next = xorshift();
+
+        let alphabet: Vec<char> = "ab :|!<>\\\".#34xéOR".chars().collect();
+        for _ in 0..5000 {
+            let search: String = (0..next() % 16)
+                .map(|_| alphabet[(next() as usize) % alphabet.len()])
+                .collect();
+            if let Ok(expr) = parse(&search) {
+                let rendered = expr.to_string();
+                let reparsed = parse(&rendered).unwrap();
+                assert_eq!(reparsed.to_string(), rendered, "{:?}", search);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Quoting and `< >` grouping shared by `Query` and `Expr`, so both render search syntax the same way.
+
+/// The operator keywords Everything reads in place of a space, `|` and `!`.
+pub(super) const KEYWORDS: [&str; 3] = ["AND", "OR", "NOT"];
+
+/// Where a term is rendered, which decides whether it needs `< >` around it.
+/// Everything binds `|` tighter than the space between terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Context {
+    And,
+    Or,
+    Prefix,
+}
+
+/// A tree that renders as search syntax.
+pub(super) trait Render {
+    fn render(&self, context: Context) -> String;
+}
+
+/// Renders terms that must all match, separated by spaces.
+pub(super) fn and<T: Render>(terms: &[&T], context: Context) -> String {
+    match terms {
+        [term] => term.render(context),
+        terms => group(
+            join(terms, Context::And, " "),
+            context != Context::And && !terms.is_empty(),
+        ),
+    }
+}
+
+/// Renders terms of which any must match, separated by `|`.
+pub(super) fn or<T: Render>(terms: &[&T], context: Context) -> String {
+    match terms {
+        [term] => term.render(context),
+        terms => group(
+            join(terms, Context::Or, "|"),
+            context == Context::Prefix && !terms.is_empty(),
+        ),
+    }
+}
+
+/// Renders `!term`.
+pub(super) fn not<T: Render>(term: &T) -> String {
+    format!("!{}", term.render(Context::Prefix))
+}
+
+/// Renders a modifier such as `case:` applied to `term`.
+pub(super) fn modifier<T: Render>(name: &str, term: &T) -> String {
+    format!("{}:{}", name, term.render(Context::Prefix))
+}
+
+fn join<T: Render>(terms: &[&T], context: Context, separator: &str) -> String {
+    terms
+        .iter()
+        .map(|term| term.render(context))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+pub(super) fn group(text: String, grouped: bool) -> String {
+    if grouped {
+        format!("<{}>", text)
+    } else {
+        text
+    }
+}
+
+/// Whether Everything would read part of `text` as an operator, a function or a character macro.
+fn has_special_characters(text: &str) -> bool {
+    text.contains(|c: char| c.is_whitespace() || matches!(c, '|' | '<' | '>' | '"' | '#' | ':'))
+}
+
+/// Quotes text matched against names if it would otherwise be read as something else,
+/// such as `!foo`, `dm:today` or the `OR` operator.
+pub(super) fn quote_text(text: &str) -> String {
+    let needs_quotes = text.is_empty()
+        || text.starts_with('!')
+        || KEYWORDS.contains(&text)
+        || has_special_characters(text);
+    quote(text, needs_quotes)
+}
+
+/// Quotes the value of a function if it would otherwise end the term or be read as a comparison or range.
+pub(super) fn quote_value(text: &str) -> String {
+    let needs_quotes =
+        has_special_characters(text) || text.starts_with(['<', '>', '=']) || text.contains("..");
+    quote(text, needs_quotes)
+}
+
+/// Quotes `text`, writing any `"` as the `#34:` character macro between quoted parts since it cannot appear inside quotes.
+fn quote(text: &str, needs_quotes: bool) -> String {
+    if !needs_quotes {
+        return text.to_string();
+    }
+    text.split('"')
+        .map(|part| format!("\"{}\"", part))
+        .collect::<Vec<_>>()
+        .join("#34:")
+}
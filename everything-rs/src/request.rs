@@ -0,0 +1,246 @@
+use crate::{CancellationToken, QueryLimits, ResultPage, SearchBackend, SlowQueryPolicy};
+use crate::{Everything, EverythingRequestFlags, EverythingResult, EverythingSort, Query};
+use std::time::{Duration, Instant};
+
+/// Everything needed to run one query, see `Everything::search`.
+/// Defaults to the same settings as a freshly reset SDK.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::{EverythingRequestFlags, EverythingSort, SearchRequest};
+///
+/// let request = SearchRequest {
+///     sort: EverythingSort::SizeDescending,
+///     request_flags: EverythingRequestFlags::FullPathAndFileName | EverythingRequestFlags::Size,
+///     max_results: 10,
+///     ..SearchRequest::new("*.log")
+/// };
+/// assert_eq!(request.search, "*.log");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SearchRequest {
+    pub search: String,
+    pub sort: EverythingSort,
+    pub request_flags: EverythingRequestFlags,
+    pub max_results: u32,
+    pub offset: u32,
+    pub match_case: bool,
+    pub match_path: bool,
+    pub match_whole_word: bool,
+    pub regex: bool,
+    /// Gives up with `EverythingError::QueryTimeout` when the query takes longer, counted from when it is sent.
+    pub timeout: Option<Duration>,
+    /// Gives up with `EverythingError::Cancelled` once cancelled.
+    pub cancel: Option<CancellationToken>,
+    /// Whether to check the sort and request flags against `Everything::capabilities` before sending.
+    pub slow_queries: SlowQueryPolicy,
+}
+
+impl SearchRequest {
+    pub fn new(search: impl Into<String>) -> SearchRequest {
+        SearchRequest {
+            search: search.into(),
+            ..SearchRequest::default()
+        }
+    }
+
+    /// Sets every search parameter of `backend` to the ones of this request.
+    pub(crate) fn apply(&self, backend: &dyn SearchBackend) {
+        backend.set_search(&self.search);
+        backend.set_sort(self.sort);
+        backend.set_request_flags(self.request_flags);
+        backend.set_max_results(self.max_results);
+        backend.set_result_offset(self.offset);
+        backend.set_match_case(self.match_case);
+        backend.set_match_path(self.match_path);
+        backend.set_match_whole_word(self.match_whole_word);
+        backend.set_regex(self.regex);
+    }
+
+    /// The limits of a query sent now.
+    fn limits(&self) -> QueryLimits {
+        QueryLimits {
+            deadline: self
+                .timeout
+                .and_then(|timeout| Instant::now().checked_add(timeout)),
+            cancel: self.cancel.clone(),
+        }
+    }
+}
+
+impl Default for SearchRequest {
+    fn default() -> SearchRequest {
+        SearchRequest {
+            search: String::new(),
+            sort: EverythingSort::NameAscending,
+            request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::Path,
+            max_results: u32::MAX,
+            offset: 0,
+            match_case: false,
+            match_path: false,
+            match_whole_word: false,
+            regex: false,
+            timeout: None,
+            cancel: None,
+            slow_queries: SlowQueryPolicy::Allow,
+        }
+    }
+}
+
+impl From<&Query> for SearchRequest {
+    fn from(query: &Query) -> SearchRequest {
+        SearchRequest::new(query.to_string())
+    }
+}
+
+impl From<Query> for SearchRequest {
+    fn from(query: Query) -> SearchRequest {
+        SearchRequest::from(&query)
+    }
+}
+
+impl Everything {
+    /// Applies every setting of `request`, runs the query within its limits and returns owned copies of the results.
+    /// The settings stay applied afterwards, like calling each setter and `query` would.
+    /// Unless `slow_queries` is `SlowQueryPolicy::Allow` the capabilities of Everything are checked first,
+    /// they are probed by the first such search and kept for the lifetime of this instance.
+    pub fn search(&self, request: &SearchRequest) -> EverythingResult<ResultPage> {
+        let mut degraded;
+        let (request, warnings) = match request.slow_queries {
+            SlowQueryPolicy::Allow => (request, Vec::new()),
+            SlowQueryPolicy::Warn => (request, self.cached_capabilities()?.review(request)),
+            SlowQueryPolicy::Degrade => {
+                degraded = request.clone();
+                let warnings = self.cached_capabilities()?.degrade(&mut degraded);
+                (&degraded, warnings)
+            }
+        };
+        request.apply(&*self.backend);
+        self.query_with(&request.limits())?;
+        // Only known after a version 1 query, otherwise the backend reports `InvalidCall`.
+        let totals = (
+            self.get_total_folder_results(),
+            self.get_total_file_results(),
+        );
+        let totals = self.get_last_error().ok().map(|_| totals);
+        Ok(ResultPage {
+            total_results: self.get_total_results(),
+            total_folders: totals.map(|(folders, _)| folders),
+            total_files: totals.map(|(_, files)| files),
+            offset: request.offset,
+            results: self.results()?,
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, LoopbackTransport};
+    use crate::test_util::test_entries;
+    use crate::{CapabilityWarning, EverythingError, FileInfo, InMemoryBackend, InMemoryEntry};
+
+    #[test]
+    fn applies_every_setting() {
+        let everything = Everything::with_backend(InMemoryBackend::new(vec![
+            InMemoryEntry::file("C:\\test", "b.txt"),
+            InMemoryEntry::file("C:\\test", "a.txt"),
+            InMemoryEntry::file("C:\\test", "A.TXT"),
+        ]));
+
+        let request = SearchRequest {
+            sort: EverythingSort::NameDescending,
+            request_flags: EverythingRequestFlags::FileName,
+            max_results: 1,
+            offset: 1,
+            match_case: true,
+            ..SearchRequest::from(Query::text("txt"))
+        };
+        let page = everything.search(&request).unwrap();
+
+        assert_eq!(page.total_results, 2);
+        assert_eq!(page.offset, 1);
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.results[0].file_name.as_deref(), Some("a.txt"));
+        assert_eq!(page.results[0].full_path, None);
+        assert!(everything.get_match_case());
+        assert_eq!(everything.get_sort(), Some(EverythingSort::NameDescending));
+
+        let page = everything.search(&SearchRequest::new("TXT")).unwrap();
+        assert_eq!(page.total_results, 3);
+        assert!(!everything.get_match_case());
+        assert_eq!(
+            page.results[2].full_path.as_deref(),
+            Some("C:\\test\\b.txt")
+        );
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let cancelled = SearchRequest {
+            cancel: Some(cancel),
+            ..SearchRequest::new("TXT")
+        };
+        assert!(matches!(
+            everything.search(&cancelled),
+            Err(EverythingError::Cancelled)
+        ));
+        assert_eq!(everything.get_num_results(), 0);
+    }
+
+    #[test]
+    fn checks_slow_queries() {
+        let everything = Everything::with_backend(InMemoryBackend::new(vec![
+            InMemoryEntry::file("C:\\test", "a.txt"),
+            InMemoryEntry::file("C:\\test", "b.txt"),
+        ]));
+        let request = SearchRequest {
+            sort: EverythingSort::DateCreatedDescending,
+            request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::DateCreated,
+            ..SearchRequest::new("txt")
+        };
+        let warnings = [
+            CapabilityWarning::SlowSort(EverythingSort::DateCreatedDescending),
+            CapabilityWarning::UnindexedFileInfo(FileInfo::DateCreated),
+        ];
+
+        assert!(everything.search(&request).unwrap().warnings.is_empty());
+
+        let warned = SearchRequest {
+            slow_queries: SlowQueryPolicy::Warn,
+            ..request.clone()
+        };
+        let page = everything.search(&warned).unwrap();
+        assert_eq!(page.warnings, warnings);
+        assert!(page.results[0].date_created.is_some());
+
+        let degraded = SearchRequest {
+            slow_queries: SlowQueryPolicy::Degrade,
+            ..request
+        };
+        let page = everything.search(&degraded).unwrap();
+        assert_eq!(page.warnings, warnings);
+        assert_eq!(page.results[0].date_created, None);
+        assert_eq!(page.results[0].file_name.as_deref(), Some("a.txt"));
+        assert_eq!(everything.get_sort(), Some(EverythingSort::NameAscending));
+    }
+
+    #[test]
+    fn reports_file_and_folder_totals() {
+        let backend = InMemoryBackend::new(test_entries(&["test", "test1.txt", "test2.jpg"]));
+        let everything = Everything::with_backend(IpcClient::new(LoopbackTransport::new(backend)));
+
+        let page = everything.search(&SearchRequest::new("test")).unwrap();
+        assert_eq!(page.total_results, 3);
+        assert_eq!((page.total_folders, page.total_files), (Some(1), Some(2)));
+
+        // A QUERY2 reply does not count folders and files.
+        let request = SearchRequest {
+            sort: EverythingSort::NameDescending,
+            ..SearchRequest::new("test")
+        };
+        let page = everything.search(&request).unwrap();
+        assert_eq!(page.total_results, 3);
+        assert_eq!((page.total_folders, page.total_files), (None, None));
+    }
+}
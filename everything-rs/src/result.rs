@@ -0,0 +1,280 @@
+use crate::{
+    CapabilityWarning, EverythingError, EverythingRequestFlags, EverythingResult,
+    EverythingSDKError, FileAttributes, FileTime, Highlighted, SearchBackend,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Whether a result is a file, folder or volume.
+/// See <https://www.voidtools.com/support/everything/sdk/everything_isfileresult/>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub enum ResultKind {
+    File,
+    Folder,
+    Volume,
+}
+
+/// An owned copy of one result, returned by `Everything::results`.
+/// Fields are `Some` when their request flag was honored by the last query,
+/// see <https://www.voidtools.com/support/everything/sdk/everything_getresultlistrequestflags/>
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub struct SearchResult {
+    pub kind: ResultKind,
+    pub file_name: Option<String>,
+    /// Available when either `FullPathAndFileName` or both `FileName` and `Path` were requested.
+    pub full_path: Option<String>,
+    pub extension: Option<String>,
+    pub size: Option<u64>,
+    pub date_created: Option<FileTime>,
+    pub date_modified: Option<FileTime>,
+    pub path: Option<String>,
+    pub date_accessed: Option<FileTime>,
+    pub date_run: Option<FileTime>,
+    pub date_recently_changed: Option<FileTime>,
+    #[ts(type = "number | null")]
+    pub attributes: Option<FileAttributes>,
+    pub run_count: Option<u32>,
+    pub file_list_file_name: Option<String>,
+    pub highlighted_file_name: Option<Highlighted>,
+    pub highlighted_path: Option<Highlighted>,
+    pub highlighted_full_path: Option<Highlighted>,
+}
+
+/// The results of one `SearchRequest`, returned by `Everything::search`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+pub struct ResultPage {
+    /// The number of results matching the search, ignoring `max_results` and `offset`.
+    pub total_results: u32,
+    /// The number of folders matching the search, including volumes.
+    /// `None` when the reply did not count them, Everything only does for the version 1 query, see `ipc::needs_query2`.
+    pub total_folders: Option<u32>,
+    /// The number of files matching the search, `None` whenever `total_folders` is.
+    pub total_files: Option<u32>,
+    /// The index of the first result in `results`.
+    pub offset: u32,
+    pub results: Vec<SearchResult>,
+    /// What `SearchRequest::slow_queries` found slow or changed, empty with `SlowQueryPolicy::Allow`.
+    pub warnings: Vec<CapabilityWarning>,
+}
+
+/// Reads the result at `index` from the last query of `backend`, see `Everything::get_result`.
+pub(crate) fn read_result(
+    backend: &dyn SearchBackend,
+    index: u32,
+) -> EverythingResult<SearchResult> {
+    let flags = backend.get_result_list_request_flags();
+    let read = |flag: EverythingRequestFlags| flags.contains(flag);
+
+    let kind = if backend.is_result_volume(index) {
+        ResultKind::Volume
+    } else if backend.is_result_folder(index) {
+        ResultKind::Folder
+    } else if backend.is_result_file(index) {
+        ResultKind::File
+    } else {
+        // None of the kinds match when the index is out of range.
+        backend.get_last_error()?;
+        return Err(EverythingError::SDKError(EverythingSDKError::InvalidIndex));
+    };
+
+    let has_full_path = read(EverythingRequestFlags::FullPathAndFileName)
+        || read(EverythingRequestFlags::FileName | EverythingRequestFlags::Path);
+    Ok(SearchResult {
+        kind,
+        file_name: read(EverythingRequestFlags::FileName)
+            .then(|| backend.get_result_file_name(index))
+            .transpose()?,
+        full_path: has_full_path
+            .then(|| backend.get_result_full_path(index))
+            .transpose()?,
+        extension: read(EverythingRequestFlags::Extension)
+            .then(|| backend.get_result_extension(index))
+            .transpose()?,
+        size: read(EverythingRequestFlags::Size)
+            .then(|| backend.get_result_size(index))
+            .transpose()?,
+        date_created: read(EverythingRequestFlags::DateCreated)
+            .then(|| backend.get_result_created_date(index))
+            .transpose()?,
+        date_modified: read(EverythingRequestFlags::DateModified)
+            .then(|| backend.get_result_modified_date(index))
+            .transpose()?,
+        path: read(EverythingRequestFlags::Path)
+            .then(|| backend.get_result_path(index))
+            .transpose()?,
+        date_accessed: read(EverythingRequestFlags::DateAccessed)
+            .then(|| backend.get_result_accessed_date(index))
+            .transpose()?,
+        date_run: read(EverythingRequestFlags::DateRun)
+            .then(|| backend.get_result_run_date(index))
+            .transpose()?,
+        date_recently_changed: read(EverythingRequestFlags::DateRecentlyChanged)
+            .then(|| backend.get_result_recently_changed_date(index))
+            .transpose()?,
+        attributes: read(EverythingRequestFlags::Attributes)
+            .then(|| backend.get_result_attributes(index))
+            .transpose()?,
+        run_count: read(EverythingRequestFlags::RunCount)
+            .then(|| backend.get_result_run_count(index))
+            .transpose()?,
+        file_list_file_name: read(EverythingRequestFlags::FileListFileName)
+            .then(|| backend.get_result_file_list_file_name(index))
+            .transpose()?,
+        highlighted_file_name: read(EverythingRequestFlags::HighlightedFileName)
+            .then(|| backend.get_result_highlighted_file_name(index))
+            .map(|markup| markup.map(|markup| Highlighted::parse(&markup)))
+            .transpose()?,
+        highlighted_path: read(EverythingRequestFlags::HighlightedPath)
+            .then(|| backend.get_result_highlighted_path(index))
+            .map(|markup| markup.map(|markup| Highlighted::parse(&markup)))
+            .transpose()?,
+        highlighted_full_path: read(EverythingRequestFlags::HighlightedFullPathAndFileName)
+            .then(|| backend.get_result_highlighted_full_path(index))
+            .map(|markup| markup.map(|markup| Highlighted::parse(&markup)))
+            .transpose()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{IpcClient, LoopbackTransport};
+    use crate::test_util::test_entries;
+    use crate::{Everything, EverythingRequestFlags, InMemoryBackend, InMemoryEntry};
+
+    fn backend() -> InMemoryBackend {
+        InMemoryBackend::new(test_entries(&["C:", "test", "test1.txt"]))
+    }
+
+    fn row(
+        kind: ResultKind,
+        path: &str,
+        name: &str,
+        extension: &str,
+        (size, date_created, date_modified): (u64, u64, u64),
+    ) -> SearchResult {
+        SearchResult {
+            kind,
+            file_name: Some(name.to_string()),
+            full_path: Some(InMemoryEntry::file(path, name).full_path()),
+            extension: Some(extension.to_string()),
+            size: Some(size),
+            date_created: Some(FileTime::from_raw(date_created)),
+            date_modified: Some(FileTime::from_raw(date_modified)),
+            path: Some(path.to_string()),
+            date_accessed: None,
+            date_run: None,
+            date_recently_changed: None,
+            attributes: None,
+            run_count: None,
+            file_list_file_name: None,
+            highlighted_file_name: None,
+            highlighted_path: None,
+            highlighted_full_path: None,
+        }
+    }
+
+    fn expected(flags: EverythingRequestFlags) -> Vec<SearchResult> {
+        let all = vec![
+            row(ResultKind::Volume, "", "C:", "", (0, 0, 0)),
+            row(ResultKind::Folder, "C:", "test", "", (0, 0, 0)),
+            row(
+                ResultKind::File,
+                "C:\\test\\test_dir",
+                "test1.txt",
+                "txt",
+                (10, 300, 30),
+            ),
+        ];
+        all.into_iter()
+            .map(|result| SearchResult {
+                file_name: result
+                    .file_name
+                    .filter(|_| flags.contains(EverythingRequestFlags::FileName)),
+                path: result
+                    .path
+                    .filter(|_| flags.contains(EverythingRequestFlags::Path)),
+                extension: result
+                    .extension
+                    .filter(|_| flags.contains(EverythingRequestFlags::Extension)),
+                size: result
+                    .size
+                    .filter(|_| flags.contains(EverythingRequestFlags::Size)),
+                ..result
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_honored_fields() {
+        let backends: Vec<Everything> = vec![
+            Everything::with_backend(backend()),
+            Everything::with_backend(IpcClient::new(LoopbackTransport::new(backend()))),
+        ];
+        for everything in backends {
+            let flags = EverythingRequestFlags::FileName
+                | EverythingRequestFlags::Path
+                | EverythingRequestFlags::DateCreated
+                | EverythingRequestFlags::DateModified;
+            everything.set_request_flags(flags);
+            everything.query().unwrap();
+            assert_eq!(everything.get_result_list_request_flags(), flags);
+            assert_eq!(everything.results().unwrap(), expected(flags));
+
+            let flags = EverythingRequestFlags::FullPathAndFileName
+                | EverythingRequestFlags::Extension
+                | EverythingRequestFlags::Size
+                | EverythingRequestFlags::DateCreated
+                | EverythingRequestFlags::DateModified;
+            everything.set_request_flags(flags);
+            everything.query().unwrap();
+            assert_eq!(everything.results().unwrap(), expected(flags));
+            assert!(everything.get_result(3).is_err());
+        }
+    }
+
+    #[test]
+    fn skips_full_path_without_name_and_path() {
+        let everything = Everything::with_backend(backend());
+        everything.set_request_flags(EverythingRequestFlags::Size);
+        everything.query().unwrap();
+
+        let result = everything.get_result(2).unwrap();
+        assert_eq!(result.kind, ResultKind::File);
+        assert_eq!(result.full_path, None);
+        assert_eq!(result.size, Some(10));
+    }
+
+    #[test]
+    fn serializes() {
+        let everything = Everything::with_backend(backend());
+        everything
+            .set_request_flags(EverythingRequestFlags::FileName | EverythingRequestFlags::Path);
+        everything.set_search("test1");
+        everything.query().unwrap();
+
+        let json = serde_json::to_value(everything.results().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "kind": "File",
+                "file_name": "test1.txt",
+                "full_path": "C:\\test\\test_dir\\test1.txt",
+                "extension": null,
+                "size": null,
+                "date_created": null,
+                "date_modified": null,
+                "path": "C:\\test\\test_dir",
+                "date_accessed": null,
+                "date_run": null,
+                "date_recently_changed": null,
+                "attributes": null,
+                "run_count": null,
+                "file_list_file_name": null,
+                "highlighted_file_name": null,
+                "highlighted_path": null,
+                "highlighted_full_path": null,
+            }])
+        );
+    }
+}
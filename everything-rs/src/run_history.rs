@@ -0,0 +1,141 @@
+use crate::ipc::{EVERYTHING_IPC_DELETE_RUN_HISTORY, EVERYTHING_IPC_SAVE_RUN_HISTORY};
+use crate::{Everything, EverythingResult};
+
+impl Everything {
+    /// Returns how many times the file with the full path `file_name` was run, 0 if it never was.
+    /// Everything looks the path up ignoring case, the file does not need to be indexed.
+    /// Fails with `EverythingSDKError::InvalidParameter` for an empty path or one containing a null character.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_getruncountfromfilename/>
+    pub fn get_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.backend.get_run_count(file_name)
+    }
+
+    /// Sets the run count of `file_name`, which results report with `EverythingRequestFlags::RunCount`
+    /// and the `EverythingSort::RunCount*` sorts order by.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_setruncountfromfilename/>
+    pub fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()> {
+        self.backend.set_run_count(file_name, run_count)
+    }
+
+    /// Records a launch of `file_name` and returns its new run count.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_incruncountfromfilename/>
+    ///
+    /// # Example
+    /// ```rust
+    /// use everything_rs::{Everything, EverythingSort, InMemoryBackend, InMemoryEntry, SearchRequest};
+    ///
+    /// let everything = Everything::with_backend(InMemoryBackend::new(vec![
+    ///     InMemoryEntry::file("C:\\tools", "a.exe"),
+    ///     InMemoryEntry::file("C:\\tools", "b.exe"),
+    /// ]));
+    /// assert_eq!(everything.inc_run_count("C:\\tools\\b.exe").unwrap(), 1);
+    ///
+    /// let request = SearchRequest {
+    ///     sort: EverythingSort::RunCountDescending,
+    ///     ..SearchRequest::new(".exe")
+    /// };
+    /// let page = everything.search(&request).unwrap();
+    /// assert_eq!(page.results[0].file_name.as_deref(), Some("b.exe"));
+    /// ```
+    pub fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.backend.inc_run_count(file_name)
+    }
+
+    /// Saves the run history to disk.
+    /// Fails with `EverythingError::CommandRejected` if Everything did not save it.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_saverunhistory/>
+    pub fn save_run_history(&self) -> EverythingResult<()> {
+        self.admin().action(EVERYTHING_IPC_SAVE_RUN_HISTORY)
+    }
+
+    /// Deletes the run history from memory and disk, every run count is 0 afterwards.
+    /// Fails with `EverythingError::CommandRejected` if Everything did not delete it.
+    /// See <https://www.voidtools.com/support/everything/sdk/everything_deleterunhistory/>
+    pub fn delete_run_history(&self) -> EverythingResult<()> {
+        self.admin().action(EVERYTHING_IPC_DELETE_RUN_HISTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ipc::{IpcClient, LoopbackTransport};
+    use crate::{
+        Everything, EverythingError, EverythingRequestFlags, EverythingSDKError, EverythingSort,
+        InMemoryBackend, InMemoryEntry, SearchRequest,
+    };
+
+    /// Three tools of which only `b.exe` was run before, with Everything `running` or not.
+    fn launched_tools(running: bool) -> Everything {
+        let mut launched = InMemoryEntry::file("C:\\tools", "b.exe");
+        launched.run_count = 2;
+        let transport = LoopbackTransport::new(InMemoryBackend::new(vec![
+            InMemoryEntry::file("C:\\tools", "a.exe"),
+            launched,
+            InMemoryEntry::file("C:\\tools", "c.exe"),
+        ]));
+        transport.set_running(running);
+        Everything::with_backend(IpcClient::new(transport))
+    }
+
+    fn ranked(everything: &Everything) -> Vec<(String, u32)> {
+        let request = SearchRequest {
+            sort: EverythingSort::RunCountDescending,
+            request_flags: EverythingRequestFlags::FileName | EverythingRequestFlags::RunCount,
+            ..SearchRequest::new(".exe")
+        };
+        let page = everything.search(&request).unwrap();
+        page.results
+            .into_iter()
+            .map(|result| (result.file_name.unwrap(), result.run_count.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn records_launches_over_ipc() {
+        let everything = launched_tools(true);
+        assert_eq!(everything.get_run_count("C:\\tools\\b.exe").unwrap(), 2);
+        assert_eq!(everything.get_run_count("C:\\tools\\a.exe").unwrap(), 0);
+
+        assert_eq!(everything.inc_run_count("C:\\TOOLS\\C.EXE").unwrap(), 1);
+        everything.set_run_count("C:\\tools\\a.exe", 5).unwrap();
+        assert_eq!(
+            ranked(&everything),
+            [
+                ("a.exe".to_string(), 5),
+                ("b.exe".to_string(), 2),
+                ("c.exe".to_string(), 1)
+            ]
+        );
+
+        // Run history belongs to Everything and outlives the search state.
+        everything.reset();
+        assert_eq!(everything.get_run_count("c:\\tools\\c.exe").unwrap(), 1);
+        everything.save_run_history().unwrap();
+        everything.delete_run_history().unwrap();
+        assert!(ranked(&everything).iter().all(|(_, count)| *count == 0));
+    }
+
+    #[test]
+    fn reports_failures() {
+        let everything = launched_tools(true);
+        for file_name in ["", "C:\\a\0.exe"] {
+            assert!(matches!(
+                everything.inc_run_count(file_name),
+                Err(EverythingError::SDKError(
+                    EverythingSDKError::InvalidParameter
+                ))
+            ));
+        }
+        assert!(everything.get_last_error().is_err());
+
+        let stopped = launched_tools(false);
+        assert!(matches!(
+            stopped.set_run_count("C:\\tools\\a.exe", 1),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+        assert!(matches!(
+            stopped.save_run_history(),
+            Err(EverythingError::SDKError(EverythingSDKError::Ipc))
+        ));
+    }
+}
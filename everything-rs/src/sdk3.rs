@@ -0,0 +1,107 @@
+//! # SDK3
+//! A client for the named pipe interface of Everything 1.5, the protocol behind the 1.5 SDK (`Everything3.c`).
+//! Unlike the window message IPC it reaches named instances, sorts and reads results by property
+//! and reports the folder and file counts and total size of a search.
+//!
+//! Every request and response is a message: a DWORD code, a DWORD payload size and the payload.
+//! Numbers are little endian and text is UTF-8. A response with `EVERYTHING3_RESPONSE_OK_MORE_DATA`
+//! carries part of the payload, the rest follows until `EVERYTHING3_RESPONSE_OK`.
+//!
+//! `Sdk3Client` sends requests through an `Sdk3Transport`. `StreamTransport` frames them over any byte stream,
+//! `PipeTransport` connects to the pipe of an Everything instance on windows and
+//! `UnixSocketTransport` connects to a unix domain socket so a fake server can answer them in tests.
+
+#![deny(unsafe_code)]
+
+mod client;
+mod codec;
+mod search;
+mod transport;
+
+pub use client::Sdk3Client;
+pub use codec::{read_message, write_message, Message};
+pub use search::{
+    PropertyColumn, PropertyRequest, PropertyRequestFlags, PropertySort, PropertyValue, Sdk3Item,
+    Sdk3ResultList, Sdk3Search, Sdk3SearchFlags, ValueType,
+};
+#[cfg(target_os = "windows")]
+pub use transport::PipeTransport;
+#[cfg(unix)]
+pub use transport::UnixSocketTransport;
+pub use transport::{pipe_name, Sdk3Transport, StreamTransport};
+
+/// The pipe an unnamed Everything instance listens on.
+pub const EVERYTHING3_PIPE_NAME: &str = r"\\.\PIPE\Everything IPC";
+
+/// Returns the version of the pipe protocol.
+pub const EVERYTHING3_COMMAND_GET_IPC_PIPE_VERSION: u32 = 0;
+pub const EVERYTHING3_COMMAND_GET_MAJOR_VERSION: u32 = 1;
+pub const EVERYTHING3_COMMAND_GET_MINOR_VERSION: u32 = 2;
+pub const EVERYTHING3_COMMAND_GET_REVISION: u32 = 3;
+pub const EVERYTHING3_COMMAND_GET_BUILD_NUMBER: u32 = 4;
+/// Returns one of the `EVERYTHING_IPC_TARGET_MACHINE_*` values.
+pub const EVERYTHING3_COMMAND_GET_TARGET_MACHINE: u32 = 5;
+/// Takes a property name and returns its id.
+pub const EVERYTHING3_COMMAND_FIND_PROPERTY_FROM_NAME: u32 = 6;
+/// Takes an `Sdk3Search` and returns an `Sdk3ResultList`.
+pub const EVERYTHING3_COMMAND_SEARCH: u32 = 7;
+pub const EVERYTHING3_COMMAND_IS_DB_LOADED: u32 = 8;
+pub const EVERYTHING3_COMMAND_IS_PROPERTY_INDEXED: u32 = 9;
+pub const EVERYTHING3_COMMAND_IS_PROPERTY_FAST_SORT: u32 = 10;
+/// Takes a property id and returns its localized name.
+pub const EVERYTHING3_COMMAND_GET_PROPERTY_NAME: u32 = 11;
+pub const EVERYTHING3_COMMAND_GET_PROPERTY_CANONICAL_NAME: u32 = 12;
+pub const EVERYTHING3_COMMAND_GET_PROPERTY_TYPE: u32 = 13;
+pub const EVERYTHING3_COMMAND_IS_RESULT_CHANGE: u32 = 14;
+/// Takes a file name and returns its run count.
+pub const EVERYTHING3_COMMAND_GET_RUN_COUNT: u32 = 15;
+/// Takes a run count followed by a file name.
+pub const EVERYTHING3_COMMAND_SET_RUN_COUNT: u32 = 16;
+/// Takes a file name and returns its new run count.
+pub const EVERYTHING3_COMMAND_INC_RUN_COUNT: u32 = 17;
+/// Takes a folder path and returns its size as a QWORD.
+pub const EVERYTHING3_COMMAND_GET_FOLDER_SIZE: u32 = 18;
+
+/// Part of the payload, the rest follows in more messages.
+pub const EVERYTHING3_RESPONSE_OK_MORE_DATA: u32 = 100;
+pub const EVERYTHING3_RESPONSE_OK: u32 = 200;
+pub const EVERYTHING3_RESPONSE_ERROR_BAD_REQUEST: u32 = 400;
+pub const EVERYTHING3_RESPONSE_ERROR_CANCELLED: u32 = 401;
+pub const EVERYTHING3_RESPONSE_ERROR_NOT_FOUND: u32 = 404;
+pub const EVERYTHING3_RESPONSE_ERROR_OUT_OF_MEMORY: u32 = 500;
+pub const EVERYTHING3_RESPONSE_ERROR_INVALID_COMMAND: u32 = 501;
+
+// Other properties can be looked up by name with `Sdk3Client::find_property`.
+pub const EVERYTHING3_PROPERTY_ID_NAME: u32 = 0;
+pub const EVERYTHING3_PROPERTY_ID_PATH: u32 = 1;
+pub const EVERYTHING3_PROPERTY_ID_SIZE: u32 = 2;
+pub const EVERYTHING3_PROPERTY_ID_EXTENSION: u32 = 3;
+pub const EVERYTHING3_PROPERTY_ID_TYPE: u32 = 4;
+pub const EVERYTHING3_PROPERTY_ID_DATE_MODIFIED: u32 = 5;
+pub const EVERYTHING3_PROPERTY_ID_DATE_CREATED: u32 = 6;
+pub const EVERYTHING3_PROPERTY_ID_DATE_ACCESSED: u32 = 7;
+pub const EVERYTHING3_PROPERTY_ID_ATTRIBUTES: u32 = 8;
+pub const EVERYTHING3_PROPERTY_ID_DATE_RECENTLY_CHANGED: u32 = 9;
+pub const EVERYTHING3_PROPERTY_ID_RUN_COUNT: u32 = 10;
+pub const EVERYTHING3_PROPERTY_ID_DATE_RUN: u32 = 11;
+pub const EVERYTHING3_PROPERTY_ID_FILE_LIST_NAME: u32 = 12;
+
+/// Passed as `Sdk3Search::count` to return every result.
+pub const EVERYTHING3_ALL_RESULTS: u64 = u64::MAX;
+
+/// Errors from talking to Everything over its pipe.
+#[derive(Debug, thiserror::Error)]
+pub enum Sdk3Error {
+    #[error("Everything pipe failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("SDK3 data truncated: needed {needed} bytes at offset {offset}")]
+    Truncated { offset: usize, needed: usize },
+    #[error("SDK3 text at offset {0} is not valid UTF-8")]
+    InvalidUtf8(usize),
+    #[error("Unknown property value type {0}")]
+    UnknownValueType(u8),
+    #[error("Everything answered command {command} with response {code}")]
+    Rejected { command: u32, code: u32 },
+    #[error("SDK3 blob of {len} bytes does not fit a {value_type:?} value")]
+    BlobTooLong { len: usize, value_type: ValueType },
+}
@@ -0,0 +1,402 @@
+use super::codec::{Reader, Writer};
+use super::*;
+use crate::sync::lock;
+use crate::{EverythingError, EverythingResult, TargetMachine, Version};
+use std::sync::{Mutex, MutexGuard};
+
+/// A client for the pipe interface of Everything 1.5, see the module documentation.
+/// Requests are sent one at a time through `T`, use `PipeTransport` to talk to a running Everything instance.
+///
+/// # Example
+/// ```rust,no_run
+/// # #[cfg(target_os = "windows")]
+/// # fn main() -> Result<(), everything_rs::EverythingError> {
+/// use everything_rs::sdk3::*;
+///
+/// let client = Sdk3Client::new(PipeTransport::connect(None).map_err(Sdk3Error::from)?);
+/// let results = client.search(&Sdk3Search::new("*.rs"))?;
+/// for index in 0..results.items.len() {
+///     println!("{:?}", results.text(index, EVERYTHING3_PROPERTY_ID_NAME));
+/// }
+/// # Ok(())
+/// # }
+/// # #[cfg(not(target_os = "windows"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct Sdk3Client<T: Sdk3Transport> {
+    transport: Mutex<T>,
+}
+
+impl<T: Sdk3Transport> Sdk3Client<T> {
+    pub fn new(transport: T) -> Sdk3Client<T> {
+        Sdk3Client {
+            transport: Mutex::new(transport),
+        }
+    }
+
+    pub fn into_transport(self) -> T {
+        self.transport
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn transport(&self) -> MutexGuard<'_, T> {
+        lock(&self.transport)
+    }
+
+    fn request(&self, command: u32, payload: &[u8]) -> EverythingResult<Vec<u8>> {
+        Ok(self.transport().request(command, payload)?)
+    }
+
+    fn dword(&self, command: u32, payload: &[u8]) -> EverythingResult<u32> {
+        let response = self.request(command, payload)?;
+        Ok(Reader::new(&response).u32()?)
+    }
+
+    /// The version of the pipe protocol Everything speaks.
+    pub fn pipe_version(&self) -> EverythingResult<u32> {
+        self.dword(EVERYTHING3_COMMAND_GET_IPC_PIPE_VERSION, &[])
+    }
+
+    pub fn version(&self) -> EverythingResult<Version> {
+        Ok(Version::new(
+            self.dword(EVERYTHING3_COMMAND_GET_MAJOR_VERSION, &[])?,
+            self.dword(EVERYTHING3_COMMAND_GET_MINOR_VERSION, &[])?,
+            self.dword(EVERYTHING3_COMMAND_GET_REVISION, &[])?,
+            self.dword(EVERYTHING3_COMMAND_GET_BUILD_NUMBER, &[])?,
+        ))
+    }
+
+    pub fn target_machine(&self) -> EverythingResult<TargetMachine> {
+        Ok(self
+            .dword(EVERYTHING3_COMMAND_GET_TARGET_MACHINE, &[])?
+            .into())
+    }
+
+    pub fn is_db_loaded(&self) -> EverythingResult<bool> {
+        Ok(self.dword(EVERYTHING3_COMMAND_IS_DB_LOADED, &[])? != 0)
+    }
+
+    /// Looks up the id of a property by its name, such as `Size` or `Date Modified`.
+    /// Returns `None` if Everything does not know the property.
+    pub fn find_property(&self, name: &str) -> EverythingResult<Option<u32>> {
+        match self.dword(EVERYTHING3_COMMAND_FIND_PROPERTY_FROM_NAME, name.as_bytes()) {
+            Err(EverythingError::Sdk3(Sdk3Error::Rejected {
+                code: EVERYTHING3_RESPONSE_ERROR_NOT_FOUND,
+                ..
+            })) => Ok(None),
+            result => result.map(Some),
+        }
+    }
+
+    /// The localized name of a property.
+    pub fn property_name(&self, property_id: u32) -> EverythingResult<String> {
+        let response = self.request(
+            EVERYTHING3_COMMAND_GET_PROPERTY_NAME,
+            &property_id.to_le_bytes(),
+        )?;
+        Ok(Reader::new(&response).rest_utf8()?)
+    }
+
+    /// Whether the values of a property are kept in the database rather than read from disk when searching.
+    pub fn is_property_indexed(&self, property_id: u32) -> EverythingResult<bool> {
+        let indexed = self.dword(
+            EVERYTHING3_COMMAND_IS_PROPERTY_INDEXED,
+            &property_id.to_le_bytes(),
+        )?;
+        Ok(indexed != 0)
+    }
+
+    /// Whether sorting by a property is instant.
+    pub fn is_property_fast_sort(&self, property_id: u32) -> EverythingResult<bool> {
+        let fast = self.dword(
+            EVERYTHING3_COMMAND_IS_PROPERTY_FAST_SORT,
+            &property_id.to_le_bytes(),
+        )?;
+        Ok(fast != 0)
+    }
+
+    /// Returns how many times the file with the full path `file_name` was run, 0 if it never was.
+    pub fn get_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.dword(EVERYTHING3_COMMAND_GET_RUN_COUNT, file_name.as_bytes())
+    }
+
+    pub fn set_run_count(&self, file_name: &str, run_count: u32) -> EverythingResult<()> {
+        let mut writer = Writer::default();
+        writer.u32(run_count);
+        writer.bytes(file_name.as_bytes());
+        self.request(EVERYTHING3_COMMAND_SET_RUN_COUNT, &writer.into_bytes())?;
+        Ok(())
+    }
+
+    /// Records a launch of `file_name` and returns its new run count.
+    pub fn inc_run_count(&self, file_name: &str) -> EverythingResult<u32> {
+        self.dword(EVERYTHING3_COMMAND_INC_RUN_COUNT, file_name.as_bytes())
+    }
+
+    /// The size of everything in the folder at `path`, from the index.
+    pub fn folder_size(&self, path: &str) -> EverythingResult<u64> {
+        let response = self.request(EVERYTHING3_COMMAND_GET_FOLDER_SIZE, path.as_bytes())?;
+        Ok(Reader::new(&response).u64()?)
+    }
+
+    /// Runs `search` and returns the results with the requested properties.
+    pub fn search(&self, search: &Sdk3Search) -> EverythingResult<Sdk3ResultList> {
+        let response = self.request(EVERYTHING3_COMMAND_SEARCH, &search.encode())?;
+        Ok(Sdk3ResultList::decode(&response)?)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ipc::IpcItemFlags;
+    use crate::test_util::test_tree;
+    use crate::{InMemoryEntry, ResultKind};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::PathBuf;
+    use std::thread::{self, JoinHandle};
+
+    /// The longest payload the fake server sends in one message, so longer ones arrive in parts.
+    const CHUNK_SIZE: usize = 16;
+
+    /// Answers a request the way Everything would, from `entries`.
+    fn answer(entries: &[InMemoryEntry], request: &Message) -> (u32, Vec<u8>) {
+        let ok = |payload: Vec<u8>| (EVERYTHING3_RESPONSE_OK, payload);
+        let dword = |value: u32| ok(value.to_le_bytes().to_vec());
+        match request.code {
+            EVERYTHING3_COMMAND_GET_IPC_PIPE_VERSION => dword(1),
+            EVERYTHING3_COMMAND_GET_MAJOR_VERSION => dword(1),
+            EVERYTHING3_COMMAND_GET_MINOR_VERSION => dword(5),
+            EVERYTHING3_COMMAND_GET_REVISION => dword(0),
+            EVERYTHING3_COMMAND_GET_BUILD_NUMBER => dword(1383),
+            EVERYTHING3_COMMAND_IS_DB_LOADED => dword(1),
+            EVERYTHING3_COMMAND_FIND_PROPERTY_FROM_NAME => match &request.payload[..] {
+                b"Size" => dword(EVERYTHING3_PROPERTY_ID_SIZE),
+                _ => (EVERYTHING3_RESPONSE_ERROR_NOT_FOUND, Vec::new()),
+            },
+            EVERYTHING3_COMMAND_GET_PROPERTY_NAME => ok(b"Size".to_vec()),
+            EVERYTHING3_COMMAND_SEARCH => match Sdk3Search::decode(&request.payload) {
+                Ok(search) => ok(search_entries(entries, &search).encode().unwrap()),
+                Err(_) => (EVERYTHING3_RESPONSE_ERROR_BAD_REQUEST, Vec::new()),
+            },
+            _ => (EVERYTHING3_RESPONSE_ERROR_INVALID_COMMAND, Vec::new()),
+        }
+    }
+
+    fn search_entries(entries: &[InMemoryEntry], search: &Sdk3Search) -> Sdk3ResultList {
+        let mut matches: Vec<&InMemoryEntry> = entries
+            .iter()
+            .filter(|entry| entry.name.contains(&search.search))
+            .collect();
+        if let Some(sort) = search.sorts.first() {
+            match sort.property_id {
+                EVERYTHING3_PROPERTY_ID_SIZE => matches.sort_by_key(|entry| entry.size),
+                _ => matches.sort_by_key(|entry| &entry.name),
+            }
+            if sort.descending {
+                matches.reverse();
+            }
+        }
+
+        let columns: Vec<PropertyColumn> = search
+            .properties
+            .iter()
+            .map(|property| PropertyColumn {
+                property_id: property.property_id,
+                flags: property.flags,
+                value_type: match property.property_id {
+                    EVERYTHING3_PROPERTY_ID_NAME | EVERYTHING3_PROPERTY_ID_PATH => {
+                        ValueType::PString
+                    }
+                    EVERYTHING3_PROPERTY_ID_SIZE => ValueType::U64,
+                    _ => ValueType::Null,
+                },
+            })
+            .collect();
+        let items = matches
+            .iter()
+            .skip(search.offset as usize)
+            .take(search.count.min(usize::MAX as u64) as usize)
+            .map(|entry| Sdk3Item {
+                flags: match entry.kind {
+                    ResultKind::File => IpcItemFlags::empty(),
+                    _ => IpcItemFlags::Folder,
+                },
+                values: columns
+                    .iter()
+                    .map(|column| match column.property_id {
+                        EVERYTHING3_PROPERTY_ID_NAME => PropertyValue::Text(entry.name.clone()),
+                        EVERYTHING3_PROPERTY_ID_PATH => PropertyValue::Text(entry.path.clone()),
+                        EVERYTHING3_PROPERTY_ID_SIZE => PropertyValue::U64(entry.size),
+                        _ => PropertyValue::Null,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let folders = matches
+            .iter()
+            .filter(|entry| entry.kind != ResultKind::File)
+            .count() as u64;
+        Sdk3ResultList {
+            folder_result_count: Some(folders),
+            file_result_count: Some(matches.len() as u64 - folders),
+            total_size: search
+                .flags
+                .contains(Sdk3SearchFlags::TotalSize)
+                .then(|| matches.iter().map(|entry| entry.size).sum()),
+            offset: search.offset,
+            sorts: search.sorts.clone(),
+            columns,
+            items,
+        }
+    }
+
+    /// Starts a fake Everything listening on a unix socket that serves one connection.
+    fn serve(name: &str) -> (PathBuf, JoinHandle<()>) {
+        let path = std::env::temp_dir().join(format!(
+            "everything-sdk3-{}-{}.sock",
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let entries = test_tree();
+            // The client hanging up ends the loop.
+            while let Ok(request) = read_message(&mut stream) {
+                let (code, payload) = answer(&entries, &request);
+                let mut chunks = payload.chunks(CHUNK_SIZE).peekable();
+                while let Some(chunk) = chunks.next() {
+                    let code = match chunks.peek() {
+                        Some(_) => EVERYTHING3_RESPONSE_OK_MORE_DATA,
+                        None => code,
+                    };
+                    let message = Message {
+                        code,
+                        payload: chunk.to_vec(),
+                    };
+                    write_message(&mut stream, &message).unwrap();
+                }
+                if payload.is_empty() {
+                    let message = Message {
+                        code,
+                        payload: Vec::new(),
+                    };
+                    write_message(&mut stream, &message).unwrap();
+                }
+            }
+        });
+        (path, server)
+    }
+
+    fn connect(name: &str) -> (Sdk3Client<UnixSocketTransport>, PathBuf, JoinHandle<()>) {
+        let (path, server) = serve(name);
+        let client = Sdk3Client::new(UnixSocketTransport::connect(&path).unwrap());
+        (client, path, server)
+    }
+
+    fn finish(client: Sdk3Client<UnixSocketTransport>, path: PathBuf, server: JoinHandle<()>) {
+        drop(client.into_transport().into_inner());
+        server.join().unwrap();
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn talks_to_a_fake_server() {
+        let (client, path, server) = connect("info");
+        assert_eq!(client.pipe_version().unwrap(), 1);
+        assert_eq!(client.version().unwrap(), Version::new(1, 5, 0, 1383));
+        assert!(client.is_db_loaded().unwrap());
+        assert_eq!(
+            client.find_property("Size").unwrap(),
+            Some(EVERYTHING3_PROPERTY_ID_SIZE)
+        );
+        assert_eq!(client.find_property("Missing").unwrap(), None);
+        assert_eq!(
+            client.property_name(EVERYTHING3_PROPERTY_ID_SIZE).unwrap(),
+            "Size"
+        );
+        assert!(matches!(
+            client.folder_size("C:\\test"),
+            Err(EverythingError::Sdk3(Sdk3Error::Rejected {
+                command: EVERYTHING3_COMMAND_GET_FOLDER_SIZE,
+                code: EVERYTHING3_RESPONSE_ERROR_INVALID_COMMAND,
+            }))
+        ));
+        finish(client, path, server);
+    }
+
+    #[test]
+    fn searches_over_the_socket() {
+        let (client, path, server) = connect("search");
+        let search = Sdk3Search {
+            flags: Sdk3SearchFlags::TotalSize,
+            sorts: vec![PropertySort {
+                property_id: EVERYTHING3_PROPERTY_ID_SIZE,
+                descending: true,
+            }],
+            properties: vec![
+                PropertyRequest::new(EVERYTHING3_PROPERTY_ID_NAME),
+                PropertyRequest::new(EVERYTHING3_PROPERTY_ID_SIZE),
+            ],
+            count: 2,
+            ..Sdk3Search::new("test")
+        };
+        let results = client.search(&search).unwrap();
+
+        assert_eq!(results.folder_result_count, Some(2));
+        assert_eq!(results.file_result_count, Some(3));
+        assert_eq!(results.total_results(), Some(5));
+        assert_eq!(results.total_size, Some(60));
+        assert_eq!(results.items.len(), 2);
+        assert_eq!(results.items[0].flags, IpcItemFlags::empty());
+        assert_eq!(
+            results.text(0, EVERYTHING3_PROPERTY_ID_NAME),
+            Some("test2.jpg")
+        );
+        assert_eq!(
+            results.text(1, EVERYTHING3_PROPERTY_ID_NAME),
+            Some("test3.md")
+        );
+        assert_eq!(
+            results.value(1, EVERYTHING3_PROPERTY_ID_SIZE),
+            Some(&PropertyValue::U64(20))
+        );
+
+        // The connection stays usable for the next request.
+        let results = client.search(&Sdk3Search::new(".txt")).unwrap();
+        let names: Vec<_> = (0..results.items.len())
+            .map(|index| results.text(index, EVERYTHING3_PROPERTY_ID_NAME).unwrap())
+            .collect();
+        assert_eq!(names, ["test1.txt"]);
+        assert_eq!(
+            results.text(0, EVERYTHING3_PROPERTY_ID_PATH),
+            Some("C:\\test\\test_dir")
+        );
+        assert_eq!(results.total_size, None);
+        finish(client, path, server);
+    }
+
+    #[test]
+    fn fails_when_the_server_hangs_up() {
+        let path = std::env::temp_dir().join(format!(
+            "everything-sdk3-{}-hangup.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = thread::spawn(move || drop(listener.accept().unwrap()));
+        let client = Sdk3Client::new(StreamTransport::new(UnixStream::connect(&path).unwrap()));
+        server.join().unwrap();
+
+        assert!(matches!(
+            client.is_db_loaded(),
+            Err(EverythingError::Sdk3(Sdk3Error::Io(_)))
+        ));
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,245 @@
+//! Message framing and the little endian readers and writers for SDK3 payloads.
+
+use super::Sdk3Error;
+use std::io::{self, Read, Write};
+
+/// Size of the message header: the code and the payload size.
+const HEADER_SIZE: usize = 2 * 4;
+
+/// One request or response on the pipe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// An `EVERYTHING3_COMMAND_*` for a request, an `EVERYTHING3_RESPONSE_*` for a response.
+    pub code: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Writes `message` as one header and payload write so it is not split across pipe messages.
+pub fn write_message(stream: &mut impl Write, message: &Message) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + message.payload.len());
+    bytes.extend_from_slice(&message.code.to_le_bytes());
+    bytes.extend_from_slice(&(message.payload.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&message.payload);
+    stream.write_all(&bytes)?;
+    stream.flush()
+}
+
+/// Reads one message, failing with `UnexpectedEof` if the stream ends inside it.
+/// The payload is read as it arrives, so a bogus size does not allocate up front.
+pub fn read_message(stream: &mut impl Read) -> io::Result<Message> {
+    let mut header = [0; HEADER_SIZE];
+    stream.read_exact(&mut header)?;
+    let code = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+    let mut payload = Vec::new();
+    stream.take(size as u64).read_to_end(&mut payload)?;
+    if payload.len() != size as usize {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(Message { code, payload })
+}
+
+/// Reads the values of a payload without panicking on bad input.
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader { bytes, position: 0 }
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Sdk3Error> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(Sdk3Error::Truncated {
+                offset: self.position,
+                needed: len,
+            })?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, Sdk3Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, Sdk3Error> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, Sdk3Error> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, Sdk3Error> {
+        let low = self.u32()? as u64;
+        let high = self.u32()? as u64;
+        Ok((high << 32) | low)
+    }
+
+    /// Reads a length the way `Everything3.c` packs it: a byte below 0xFF,
+    /// otherwise 0xFF and the rest as a WORD below 0xFFFF, otherwise 0xFFFF and the rest as a QWORD.
+    pub(crate) fn len_vlq(&mut self) -> Result<u64, Sdk3Error> {
+        let byte = self.u8()?;
+        if byte < 0xFF {
+            return Ok(byte as u64);
+        }
+        let word = self.u16()?;
+        if word < 0xFFFF {
+            return Ok(0xFF + word as u64);
+        }
+        Ok((0xFF + 0xFFFF_u64).saturating_add(self.u64()?))
+    }
+
+    /// Reads `len` bytes.
+    pub(crate) fn bytes(&mut self, len: u64) -> Result<&'a [u8], Sdk3Error> {
+        let len = usize::try_from(len).map_err(|_| Sdk3Error::Truncated {
+            offset: self.position,
+            needed: usize::MAX,
+        })?;
+        self.take(len)
+    }
+
+    /// Reads `len` bytes of UTF-8 text.
+    pub(crate) fn utf8(&mut self, len: u64) -> Result<String, Sdk3Error> {
+        let start = self.position;
+        let bytes = self.bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| Sdk3Error::InvalidUtf8(start))
+    }
+
+    /// Reads a `len_vlq` length followed by that many bytes of UTF-8 text.
+    pub(crate) fn pstring(&mut self) -> Result<String, Sdk3Error> {
+        let len = self.len_vlq()?;
+        self.utf8(len)
+    }
+
+    /// Reads the rest of the payload as UTF-8 text.
+    pub(crate) fn rest_utf8(&mut self) -> Result<String, Sdk3Error> {
+        self.utf8(self.remaining() as u64)
+    }
+}
+
+/// Appends little endian values to a payload.
+#[derive(Default)]
+pub(crate) struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub(crate) fn u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn u64(&mut self, value: u64) {
+        self.u32(value as u32);
+        self.u32((value >> 32) as u32);
+    }
+
+    /// Writes a length packed like `Reader::len_vlq` reads it.
+    pub(crate) fn len_vlq(&mut self, value: u64) {
+        if value < 0xFF {
+            return self.u8(value as u8);
+        }
+        self.u8(0xFF);
+        let value = value - 0xFF;
+        if value < 0xFFFF {
+            return self.u16(value as u16);
+        }
+        self.u16(0xFFFF);
+        self.u64(value - 0xFFFF);
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Writes the length of `text` in bytes as a `len_vlq` followed by the text.
+    pub(crate) fn pstring(&mut self, text: &str) {
+        self.len_vlq(text.len() as u64);
+        self.bytes(text.as_bytes());
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_messages() {
+        let message = Message {
+            code: 7,
+            payload: vec![1, 2, 3],
+        };
+        let mut bytes = Vec::new();
+        write_message(&mut bytes, &message).unwrap();
+        assert_eq!(bytes, [7, 0, 0, 0, 3, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(read_message(&mut bytes.as_slice()).unwrap(), message);
+
+        // A message cut short is an error rather than a short payload.
+        for len in 0..bytes.len() {
+            let err = read_message(&mut &bytes[..len]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof, "length {}", len);
+        }
+        let huge = [0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF];
+        assert!(read_message(&mut huge.as_slice()).is_err());
+    }
+
+    #[test]
+    fn packs_lengths() {
+        let cases: [(u64, &[u8]); 5] = [
+            (0, &[0]),
+            (0xFE, &[0xFE]),
+            (0xFF, &[0xFF, 0, 0]),
+            (0xFF + 0xFFFE, &[0xFF, 0xFE, 0xFF]),
+            (
+                0xFF + 0xFFFF + 2,
+                &[0xFF, 0xFF, 0xFF, 2, 0, 0, 0, 0, 0, 0, 0],
+            ),
+        ];
+        for (value, bytes) in cases {
+            let mut writer = Writer::default();
+            writer.len_vlq(value);
+            assert_eq!(writer.into_bytes(), bytes, "{}", value);
+
+            let mut reader = Reader::new(bytes);
+            assert_eq!(reader.len_vlq().unwrap(), value);
+            assert_eq!(reader.remaining(), 0);
+        }
+
+        let mut reader = Reader::new(&[3, b'a', 0xFF, b'b']);
+        assert!(matches!(reader.pstring(), Err(Sdk3Error::InvalidUtf8(1))));
+        let mut reader = Reader::new(&[9, b'a']);
+        assert!(matches!(
+            reader.pstring(),
+            Err(Sdk3Error::Truncated { offset: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,644 @@
+use super::codec::{Reader, Writer};
+use super::{
+    Sdk3Error, EVERYTHING3_ALL_RESULTS, EVERYTHING3_PROPERTY_ID_NAME, EVERYTHING3_PROPERTY_ID_PATH,
+};
+use crate::ipc::IpcItemFlags;
+use bitflags::bitflags;
+
+/// Sizes are sent as QWORDs, this crate always sets it.
+const SEARCH_FLAG_64BIT: u32 = 0x00004000;
+const SORT_FLAG_DESCENDING: u32 = 0x00000001;
+
+// Which parts of a result list were sent.
+const RESULT_LIST_FOLDER_RESULT_COUNT: u32 = 0x00000001;
+const RESULT_LIST_FILE_RESULT_COUNT: u32 = 0x00000002;
+const RESULT_LIST_TOTAL_SIZE: u32 = 0x00000004;
+const RESULT_LIST_VIEWPORT: u32 = 0x00000008;
+const RESULT_LIST_SORT: u32 = 0x00000010;
+const RESULT_LIST_PROPERTY_REQUEST: u32 = 0x00000020;
+
+bitflags! {
+    /// The search flags of an `Sdk3Search`.
+    /// Folders are listed first when sorting ascending unless one of the `FoldersFirst*` flags says otherwise.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct Sdk3SearchFlags: u32 {
+        const MatchCase = 0x00000001;
+        const MatchWholeWords = 0x00000002;
+        const MatchPath = 0x00000004;
+        const Regex = 0x00000008;
+        const MatchDiacritics = 0x00000010;
+        const MatchPrefix = 0x00000020;
+        const MatchSuffix = 0x00000040;
+        const IgnorePunctuation = 0x00000080;
+        const IgnoreWhitespace = 0x00000100;
+        const FoldersFirstAlways = 0x00000200;
+        const FoldersFirstNever = 0x00000400;
+        const FoldersFirstDescending = 0x00000600;
+        /// Adds up the size of every result, see `Sdk3ResultList::total_size`.
+        const TotalSize = 0x00000800;
+        const HideResultOmissions = 0x00001000;
+        /// Sorts folders and files together.
+        const SortMix = 0x00002000;
+        /// Runs the search even when it matches the previous one.
+        const Force = 0x00008000;
+    }
+}
+
+bitflags! {
+    /// How a property of the results is returned.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct PropertyRequestFlags: u32 {
+        /// The value as the text Everything would display.
+        const Format = 0x00000001;
+        /// The value as text with the matched parts surrounded by `*`, see `Highlighted`.
+        const Highlight = 0x00000002;
+    }
+}
+
+/// A property to sort the results by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PropertySort {
+    pub property_id: u32,
+    pub descending: bool,
+}
+
+/// A property to return for every result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PropertyRequest {
+    pub property_id: u32,
+    pub flags: PropertyRequestFlags,
+}
+
+impl PropertyRequest {
+    /// Requests the raw value of `property_id`.
+    pub fn new(property_id: u32) -> PropertyRequest {
+        PropertyRequest {
+            property_id,
+            flags: PropertyRequestFlags::empty(),
+        }
+    }
+}
+
+/// How the values of a property are laid out in a result list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    Null,
+    Byte,
+    Word,
+    Dword,
+    U64,
+    /// Text prefixed with its length in bytes.
+    PString,
+    /// A size, always a QWORD since this crate asks for 64 bit sizes.
+    SizeT,
+    /// Bytes prefixed with a byte length.
+    Blob8,
+    /// Bytes prefixed with a WORD length.
+    Blob16,
+}
+
+impl From<ValueType> for u8 {
+    fn from(value_type: ValueType) -> Self {
+        match value_type {
+            ValueType::Null => 0,
+            ValueType::Byte => 1,
+            ValueType::Word => 2,
+            ValueType::Dword => 3,
+            ValueType::U64 => 5,
+            ValueType::PString => 8,
+            ValueType::SizeT => 11,
+            ValueType::Blob8 => 16,
+            ValueType::Blob16 => 19,
+        }
+    }
+}
+
+impl TryFrom<u8> for ValueType {
+    type Error = Sdk3Error;
+
+    fn try_from(value_type: u8) -> Result<Self, Self::Error> {
+        let value_type = match value_type {
+            0 => ValueType::Null,
+            1 => ValueType::Byte,
+            2 => ValueType::Word,
+            3 => ValueType::Dword,
+            5 => ValueType::U64,
+            8 => ValueType::PString,
+            11 => ValueType::SizeT,
+            16 => ValueType::Blob8,
+            19 => ValueType::Blob16,
+            _ => Err(Sdk3Error::UnknownValueType(value_type))?,
+        };
+        Ok(value_type)
+    }
+}
+
+/// The value of one property of one result.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PropertyValue {
+    Null,
+    Byte(u8),
+    Word(u16),
+    Dword(u32),
+    /// A `U64` or `SizeT` value, dates are raw FILETIME values.
+    U64(u64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl PropertyValue {
+    /// The value of a number of any width.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            PropertyValue::Byte(value) => Some(value as u64),
+            PropertyValue::Word(value) => Some(value as u64),
+            PropertyValue::Dword(value) => Some(value as u64),
+            PropertyValue::U64(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn read(reader: &mut Reader, value_type: ValueType) -> Result<PropertyValue, Sdk3Error> {
+        Ok(match value_type {
+            ValueType::Null => PropertyValue::Null,
+            ValueType::Byte => PropertyValue::Byte(reader.u8()?),
+            ValueType::Word => PropertyValue::Word(reader.u16()?),
+            ValueType::Dword => PropertyValue::Dword(reader.u32()?),
+            ValueType::U64 | ValueType::SizeT => PropertyValue::U64(reader.u64()?),
+            ValueType::PString => PropertyValue::Text(reader.pstring()?),
+            ValueType::Blob8 => {
+                let len = reader.u8()?;
+                PropertyValue::Blob(reader.bytes(len as u64)?.to_vec())
+            }
+            ValueType::Blob16 => {
+                let len = reader.u16()?;
+                PropertyValue::Blob(reader.bytes(len as u64)?.to_vec())
+            }
+        })
+    }
+
+    /// Writes the value as `value_type`, a value of another type is written as zero or empty.
+    /// Fails with `Sdk3Error::BlobTooLong` if a blob does not fit the length of `value_type`.
+    fn write(&self, writer: &mut Writer, value_type: ValueType) -> Result<(), Sdk3Error> {
+        let number = self.as_u64().unwrap_or_default();
+        let blob = match self {
+            PropertyValue::Blob(blob) => blob.as_slice(),
+            _ => &[],
+        };
+        match value_type {
+            ValueType::Null => {}
+            ValueType::Byte => writer.u8(number as u8),
+            ValueType::Word => writer.u16(number as u16),
+            ValueType::Dword => writer.u32(number as u32),
+            ValueType::U64 | ValueType::SizeT => writer.u64(number),
+            ValueType::PString => writer.pstring(self.as_str().unwrap_or_default()),
+            ValueType::Blob8 => {
+                writer.u8(blob_len(blob, value_type)?);
+                writer.bytes(blob);
+            }
+            ValueType::Blob16 => {
+                writer.u16(blob_len(blob, value_type)?);
+                writer.bytes(blob);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn blob_len<T: TryFrom<usize>>(blob: &[u8], value_type: ValueType) -> Result<T, Sdk3Error> {
+    T::try_from(blob.len()).map_err(|_| Sdk3Error::BlobTooLong {
+        len: blob.len(),
+        value_type,
+    })
+}
+
+/// The payload of `EVERYTHING3_COMMAND_SEARCH`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sdk3Search {
+    pub search: String,
+    pub flags: Sdk3SearchFlags,
+    /// Index of the first result to return.
+    pub offset: u64,
+    /// `EVERYTHING3_ALL_RESULTS` to return every result.
+    pub count: u64,
+    /// Sorts applied in order, the first one decides unless two results are equal.
+    pub sorts: Vec<PropertySort>,
+    pub properties: Vec<PropertyRequest>,
+}
+
+impl Sdk3Search {
+    /// Searches for `search` returning the name and path of every result sorted by name.
+    pub fn new(search: &str) -> Sdk3Search {
+        Sdk3Search {
+            search: search.to_string(),
+            flags: Sdk3SearchFlags::empty(),
+            offset: 0,
+            count: EVERYTHING3_ALL_RESULTS,
+            sorts: vec![PropertySort {
+                property_id: EVERYTHING3_PROPERTY_ID_NAME,
+                descending: false,
+            }],
+            properties: vec![
+                PropertyRequest::new(EVERYTHING3_PROPERTY_ID_NAME),
+                PropertyRequest::new(EVERYTHING3_PROPERTY_ID_PATH),
+            ],
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.u32(self.flags.bits() | SEARCH_FLAG_64BIT);
+        writer.pstring(&self.search);
+        writer.u64(self.offset);
+        writer.u64(self.count);
+        writer.len_vlq(self.sorts.len() as u64);
+        for sort in &self.sorts {
+            writer.u32(sort.property_id);
+            writer.u32(if sort.descending {
+                SORT_FLAG_DESCENDING
+            } else {
+                0
+            });
+        }
+        writer.len_vlq(self.properties.len() as u64);
+        for property in &self.properties {
+            writer.u32(property.property_id);
+            writer.u32(property.flags.bits());
+        }
+        writer.into_bytes()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Sdk3Search, Sdk3Error> {
+        let mut reader = Reader::new(bytes);
+        let flags = Sdk3SearchFlags::from_bits_retain(reader.u32()? & !SEARCH_FLAG_64BIT);
+        let search = reader.pstring()?;
+        let offset = reader.u64()?;
+        let count = reader.u64()?;
+        let sorts = read_list(&mut reader, 8, |reader| {
+            Ok(PropertySort {
+                property_id: reader.u32()?,
+                descending: reader.u32()? & SORT_FLAG_DESCENDING != 0,
+            })
+        })?;
+        let properties = read_list(&mut reader, 8, |reader| {
+            Ok(PropertyRequest {
+                property_id: reader.u32()?,
+                flags: PropertyRequestFlags::from_bits_retain(reader.u32()?),
+            })
+        })?;
+        Ok(Sdk3Search {
+            search,
+            flags,
+            offset,
+            count,
+            sorts,
+            properties,
+        })
+    }
+}
+
+/// A property returned for every result and how its values are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PropertyColumn {
+    pub property_id: u32,
+    pub flags: PropertyRequestFlags,
+    pub value_type: ValueType,
+}
+
+/// One result, with a value for each of the list's `columns`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Sdk3Item {
+    /// `Folder` for a folder, `Folder | Root` for a volume.
+    pub flags: IpcItemFlags,
+    pub values: Vec<PropertyValue>,
+}
+
+/// The response to `EVERYTHING3_COMMAND_SEARCH`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Sdk3ResultList {
+    /// Number of folders found, including volumes.
+    pub folder_result_count: Option<u64>,
+    pub file_result_count: Option<u64>,
+    /// The size of every result added up, only sent for `Sdk3SearchFlags::TotalSize`.
+    pub total_size: Option<u64>,
+    /// Index of the first item in the full result list.
+    pub offset: u64,
+    /// The sorts the results are in, which may differ from the ones requested.
+    pub sorts: Vec<PropertySort>,
+    /// The properties every item has a value for, in order.
+    pub columns: Vec<PropertyColumn>,
+    pub items: Vec<Sdk3Item>,
+}
+
+impl Sdk3ResultList {
+    /// Number of results found, `None` unless both the folder and file count were sent.
+    pub fn total_results(&self) -> Option<u64> {
+        Some(self.folder_result_count? + self.file_result_count?)
+    }
+
+    /// The value of `property_id` for the item at `index`, `None` if the property was not returned.
+    pub fn value(&self, index: usize, property_id: u32) -> Option<&PropertyValue> {
+        let column = self
+            .columns
+            .iter()
+            .position(|column| column.property_id == property_id)?;
+        self.items.get(index)?.values.get(column)
+    }
+
+    /// The value of `property_id` as text for the item at `index`.
+    pub fn text(&self, index: usize, property_id: u32) -> Option<&str> {
+        self.value(index, property_id)?.as_str()
+    }
+
+    /// Encodes the list the way the service sends it: the parts listed in the valid flags,
+    /// then each item as a flags byte and its values in column order.
+    /// Fails with `Sdk3Error::BlobTooLong` if a blob is too long for its column.
+    pub fn encode(&self) -> Result<Vec<u8>, Sdk3Error> {
+        let mut valid = RESULT_LIST_VIEWPORT | RESULT_LIST_SORT | RESULT_LIST_PROPERTY_REQUEST;
+        if self.folder_result_count.is_some() {
+            valid |= RESULT_LIST_FOLDER_RESULT_COUNT;
+        }
+        if self.file_result_count.is_some() {
+            valid |= RESULT_LIST_FILE_RESULT_COUNT;
+        }
+        if self.total_size.is_some() {
+            valid |= RESULT_LIST_TOTAL_SIZE;
+        }
+
+        let mut writer = Writer::default();
+        writer.u32(valid);
+        for value in [
+            self.folder_result_count,
+            self.file_result_count,
+            self.total_size,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            writer.u64(value);
+        }
+        writer.u64(self.offset);
+        writer.u64(self.items.len() as u64);
+        writer.len_vlq(self.sorts.len() as u64);
+        for sort in &self.sorts {
+            writer.u32(sort.property_id);
+            writer.u32(if sort.descending {
+                SORT_FLAG_DESCENDING
+            } else {
+                0
+            });
+        }
+        writer.len_vlq(self.columns.len() as u64);
+        for column in &self.columns {
+            writer.u32(column.property_id);
+            writer.u32(column.flags.bits());
+            writer.u8(column.value_type.into());
+        }
+
+        for item in &self.items {
+            writer.u8(item.flags.bits() as u8);
+            for (index, column) in self.columns.iter().enumerate() {
+                let value = item.values.get(index).unwrap_or(&PropertyValue::Null);
+                value.write(&mut writer, column.value_type)?;
+            }
+        }
+        Ok(writer.into_bytes())
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Sdk3ResultList, Sdk3Error> {
+        let mut reader = Reader::new(bytes);
+        let valid = reader.u32()?;
+        let mut optional = |flag| -> Result<Option<u64>, Sdk3Error> {
+            (valid & flag != 0).then(|| reader.u64()).transpose()
+        };
+        let folder_result_count = optional(RESULT_LIST_FOLDER_RESULT_COUNT)?;
+        let file_result_count = optional(RESULT_LIST_FILE_RESULT_COUNT)?;
+        let total_size = optional(RESULT_LIST_TOTAL_SIZE)?;
+        let (offset, count) = match valid & RESULT_LIST_VIEWPORT {
+            0 => (0, 0),
+            _ => (reader.u64()?, reader.u64()?),
+        };
+        let mut sorts = Vec::new();
+        if valid & RESULT_LIST_SORT != 0 {
+            sorts = read_list(&mut reader, 8, |reader| {
+                Ok(PropertySort {
+                    property_id: reader.u32()?,
+                    descending: reader.u32()? & SORT_FLAG_DESCENDING != 0,
+                })
+            })?;
+        }
+        let mut columns = Vec::new();
+        if valid & RESULT_LIST_PROPERTY_REQUEST != 0 {
+            columns = read_list(&mut reader, 9, |reader| {
+                Ok(PropertyColumn {
+                    property_id: reader.u32()?,
+                    flags: PropertyRequestFlags::from_bits_retain(reader.u32()?),
+                    value_type: ValueType::try_from(reader.u8()?)?,
+                })
+            })?;
+        }
+
+        // Every item has at least its flags byte.
+        let items = read_list_of(&mut reader, count, 1, |reader| {
+            let flags = IpcItemFlags::from_bits_retain(reader.u8()? as u32);
+            let values = columns
+                .iter()
+                .map(|column| PropertyValue::read(reader, column.value_type))
+                .collect::<Result<_, _>>()?;
+            Ok(Sdk3Item { flags, values })
+        })?;
+
+        Ok(Sdk3ResultList {
+            folder_result_count,
+            file_result_count,
+            total_size,
+            offset,
+            sorts,
+            columns,
+            items,
+        })
+    }
+}
+
+/// Reads a `len_vlq` count followed by that many entries of at least `min_size` bytes.
+fn read_list<T>(
+    reader: &mut Reader,
+    min_size: usize,
+    read: impl FnMut(&mut Reader) -> Result<T, Sdk3Error>,
+) -> Result<Vec<T>, Sdk3Error> {
+    let count = reader.len_vlq()?;
+    read_list_of(reader, count, min_size, read)
+}
+
+/// Reads `count` entries, failing before allocating when they cannot fit in the rest of the payload.
+fn read_list_of<T>(
+    reader: &mut Reader,
+    count: u64,
+    min_size: usize,
+    mut read: impl FnMut(&mut Reader) -> Result<T, Sdk3Error>,
+) -> Result<Vec<T>, Sdk3Error> {
+    let needed = usize::try_from(count)
+        .ok()
+        .and_then(|count| count.checked_mul(min_size));
+    if needed.is_none_or(|needed| needed > reader.remaining()) {
+        return Err(Sdk3Error::Truncated {
+            offset: reader.position(),
+            needed: needed.unwrap_or(usize::MAX),
+        });
+    }
+    (0..count).map(|_| read(reader)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk3::EVERYTHING3_PROPERTY_ID_SIZE;
+
+    #[test]
+    fn encodes_search() {
+        let search = Sdk3Search {
+            flags: Sdk3SearchFlags::MatchCase | Sdk3SearchFlags::TotalSize,
+            offset: 2,
+            count: 10,
+            sorts: vec![PropertySort {
+                property_id: EVERYTHING3_PROPERTY_ID_SIZE,
+                descending: true,
+            }],
+            properties: vec![PropertyRequest {
+                property_id: EVERYTHING3_PROPERTY_ID_NAME,
+                flags: PropertyRequestFlags::Highlight,
+            }],
+            ..Sdk3Search::new("ab")
+        };
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&0x4801u32.to_le_bytes());
+        expected.extend([2, b'a', b'b']);
+        expected.extend_from_slice(&2u64.to_le_bytes());
+        expected.extend_from_slice(&10u64.to_le_bytes());
+        expected.push(1);
+        for dword in [2u32, 1] {
+            expected.extend_from_slice(&dword.to_le_bytes());
+        }
+        expected.push(1);
+        for dword in [0u32, 2] {
+            expected.extend_from_slice(&dword.to_le_bytes());
+        }
+
+        let bytes = search.encode();
+        assert_eq!(bytes, expected);
+        assert_eq!(Sdk3Search::decode(&bytes).unwrap(), search);
+    }
+
+    fn list() -> Sdk3ResultList {
+        Sdk3ResultList {
+            folder_result_count: Some(1),
+            file_result_count: Some(2),
+            total_size: None,
+            offset: 0,
+            sorts: vec![PropertySort {
+                property_id: EVERYTHING3_PROPERTY_ID_NAME,
+                descending: false,
+            }],
+            columns: vec![
+                PropertyColumn {
+                    property_id: EVERYTHING3_PROPERTY_ID_NAME,
+                    flags: PropertyRequestFlags::empty(),
+                    value_type: ValueType::PString,
+                },
+                PropertyColumn {
+                    property_id: EVERYTHING3_PROPERTY_ID_SIZE,
+                    flags: PropertyRequestFlags::empty(),
+                    value_type: ValueType::U64,
+                },
+            ],
+            items: vec![Sdk3Item {
+                flags: IpcItemFlags::Folder,
+                values: vec![PropertyValue::Text("é".to_string()), PropertyValue::U64(5)],
+            }],
+        }
+    }
+
+    #[test]
+    fn decodes_result_list() {
+        let mut bytes = Vec::new();
+        // valid flags: both counts, viewport, sort and property requests
+        bytes.extend_from_slice(&0x3Bu32.to_le_bytes());
+        for qword in [1u64, 2, 0, 1] {
+            bytes.extend_from_slice(&qword.to_le_bytes());
+        }
+        bytes.push(1);
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.push(2);
+        bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 8]);
+        bytes.extend_from_slice(&[2, 0, 0, 0, 0, 0, 0, 0, 5]);
+        // item: folder, "é", 5
+        bytes.extend_from_slice(&[1, 2, 0xC3, 0xA9]);
+        bytes.extend_from_slice(&5u64.to_le_bytes());
+
+        let list = Sdk3ResultList::decode(&bytes).unwrap();
+        assert_eq!(list, self::list());
+        assert_eq!(list.encode().unwrap(), bytes);
+        assert_eq!(list.total_results(), Some(3));
+        assert_eq!(list.text(0, EVERYTHING3_PROPERTY_ID_NAME), Some("é"));
+        assert_eq!(
+            list.value(0, EVERYTHING3_PROPERTY_ID_SIZE)
+                .and_then(PropertyValue::as_u64),
+            Some(5)
+        );
+        assert_eq!(list.value(0, EVERYTHING3_PROPERTY_ID_PATH), None);
+    }
+
+    #[test]
+    fn rejects_malformed_lists() {
+        let bytes = list().encode().unwrap();
+        for len in 0..bytes.len() {
+            assert!(
+                Sdk3ResultList::decode(&bytes[..len]).is_err(),
+                "length {}",
+                len
+            );
+        }
+
+        let mut bad_count = bytes.clone();
+        bad_count[28..36].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            Sdk3ResultList::decode(&bad_count),
+            Err(Sdk3Error::Truncated { .. })
+        ));
+
+        let mut bad_type = bytes;
+        bad_type[54] = 99;
+        assert!(matches!(
+            Sdk3ResultList::decode(&bad_type),
+            Err(Sdk3Error::UnknownValueType(99))
+        ));
+    }
+
+    #[test]
+    fn rejects_blobs_longer_than_their_length() {
+        let mut list = list();
+        list.columns[1].value_type = ValueType::Blob8;
+        list.items[0].values[1] = PropertyValue::Blob(vec![7; 255]);
+        let decoded = Sdk3ResultList::decode(&list.encode().unwrap()).unwrap();
+        assert_eq!(decoded, list);
+
+        list.items[0].values[1] = PropertyValue::Blob(vec![7; 256]);
+        assert!(matches!(
+            list.encode(),
+            Err(Sdk3Error::BlobTooLong {
+                len: 256,
+                value_type: ValueType::Blob8
+            })
+        ));
+        list.columns[1].value_type = ValueType::Blob16;
+        assert!(list.encode().is_ok());
+    }
+}
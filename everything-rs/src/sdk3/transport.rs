@@ -0,0 +1,139 @@
+use super::codec::{read_message, write_message, Message};
+use super::{
+    Sdk3Error, EVERYTHING3_PIPE_NAME, EVERYTHING3_RESPONSE_OK, EVERYTHING3_RESPONSE_OK_MORE_DATA,
+};
+use std::io::{Read, Write};
+
+/// Delivers SDK3 requests to an Everything instance and returns its responses.
+/// `Sdk3Client` only deals with payloads so the connection can be swapped out, see `StreamTransport`.
+pub trait Sdk3Transport: Send {
+    /// Sends `command` with `payload` and returns the payload of the response.
+    /// Fails with `Sdk3Error::Rejected` for any response other than `EVERYTHING3_RESPONSE_OK`.
+    fn request(&mut self, command: u32, payload: &[u8]) -> Result<Vec<u8>, Sdk3Error>;
+}
+
+/// Returns the pipe an Everything instance listens on, `instance` is the name passed to `-instance`.
+///
+/// # Example
+/// ```rust
+/// use everything_rs::sdk3::pipe_name;
+///
+/// assert_eq!(pipe_name(None), r"\\.\PIPE\Everything IPC");
+/// assert_eq!(pipe_name(Some("1.5a")), r"\\.\PIPE\Everything IPC (1.5a)");
+/// ```
+pub fn pipe_name(instance: Option<&str>) -> String {
+    match instance {
+        Some(instance) => format!("{} ({})", EVERYTHING3_PIPE_NAME, instance),
+        None => EVERYTHING3_PIPE_NAME.to_string(),
+    }
+}
+
+/// The inverse of `pipe_name` for a pipe listed in `\\.\PIPE\`, returns the instance it belongs to
+/// or `None` if it is not an Everything pipe. `Some(None)` is the unnamed instance.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_pipe_file_name(file_name: &str) -> Option<Option<&str>> {
+    let pipe = EVERYTHING3_PIPE_NAME.rsplit('\\').next()?;
+    let suffix = file_name.strip_prefix(pipe)?;
+    if suffix.is_empty() {
+        return Some(None);
+    }
+    let instance = suffix.strip_prefix(" (")?.strip_suffix(')')?;
+    Some(Some(instance))
+}
+
+/// An `Sdk3Transport` that frames requests over a byte stream, one request at a time.
+#[derive(Debug)]
+pub struct StreamTransport<S> {
+    stream: S,
+}
+
+impl<S: Read + Write + Send> StreamTransport<S> {
+    pub fn new(stream: S) -> StreamTransport<S> {
+        StreamTransport { stream }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Read + Write + Send> Sdk3Transport for StreamTransport<S> {
+    fn request(&mut self, command: u32, payload: &[u8]) -> Result<Vec<u8>, Sdk3Error> {
+        let request = Message {
+            code: command,
+            payload: payload.to_vec(),
+        };
+        write_message(&mut self.stream, &request)?;
+
+        let mut data = Vec::new();
+        loop {
+            let response = read_message(&mut self.stream)?;
+            data.extend_from_slice(&response.payload);
+            match response.code {
+                EVERYTHING3_RESPONSE_OK_MORE_DATA => continue,
+                EVERYTHING3_RESPONSE_OK => return Ok(data),
+                code => return Err(Sdk3Error::Rejected { command, code }),
+            }
+        }
+    }
+}
+
+/// Talks to the named pipe of a running Everything 1.5 instance.
+#[cfg(target_os = "windows")]
+pub type PipeTransport = StreamTransport<std::fs::File>;
+
+#[cfg(target_os = "windows")]
+impl PipeTransport {
+    /// Opens the pipe of `instance`, or of the unnamed instance for `None`, see `pipe_name`.
+    /// Fails with `NotFound` if that instance is not running.
+    pub fn connect(instance: Option<&str>) -> std::io::Result<PipeTransport> {
+        let pipe = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(pipe_name(instance))?;
+        Ok(StreamTransport::new(pipe))
+    }
+
+    /// Returns the names of the instances with an Everything pipe, `None` for the unnamed instance, which comes first.
+    pub fn running_instances() -> std::io::Result<Vec<Option<String>>> {
+        let mut instances = Vec::new();
+        for entry in std::fs::read_dir(r"\\.\PIPE\")? {
+            let file_name = entry?.file_name();
+            if let Some(instance) = file_name.to_str().and_then(parse_pipe_file_name) {
+                instances.push(instance.map(str::to_string));
+            }
+        }
+        crate::ipc::instance::sort_instances(&mut instances);
+        Ok(instances)
+    }
+}
+
+/// Talks to a server listening on a unix domain socket, for running the protocol against a fake server in tests.
+#[cfg(unix)]
+pub type UnixSocketTransport = StreamTransport<std::os::unix::net::UnixStream>;
+
+#[cfg(unix)]
+impl UnixSocketTransport {
+    pub fn connect(path: impl AsRef<std::path::Path>) -> std::io::Result<UnixSocketTransport> {
+        Ok(StreamTransport::new(
+            std::os::unix::net::UnixStream::connect(path)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pipe_file_names() {
+        for instance in [None, Some("1.5a"), Some("build (x64)")] {
+            let name = pipe_name(instance);
+            let file_name = name.rsplit('\\').next().unwrap();
+            assert_eq!(parse_pipe_file_name(file_name), Some(instance));
+        }
+        assert_eq!(parse_pipe_file_name("Everything IPCs"), None);
+        assert_eq!(parse_pipe_file_name("Everything IPC (1.5a"), None);
+        assert_eq!(parse_pipe_file_name("other"), None);
+    }
+}
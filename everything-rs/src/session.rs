@@ -0,0 +1,115 @@
+//! Exclusive, reference counted access to process-global state such as the Everything SDK's.
+//! `FfiBackend` opens a `Session` on the SDK's state so each instance can run queries without seeing another's.
+#![cfg_attr(not(all(target_os = "windows", feature = "ffi")), allow(dead_code))]
+
+use crate::sync::lock;
+use std::sync::{Mutex, MutexGuard};
+
+/// Process-global state shared by every `Session` opened on it.
+/// Only one `SessionGuard` exists at a time and `cleanup` runs when the last session is dropped.
+#[derive(Debug)]
+pub(crate) struct GlobalState {
+    /// The number of open sessions, locked while a guard is held.
+    sessions: Mutex<usize>,
+    cleanup: fn(),
+}
+
+impl GlobalState {
+    pub(crate) const fn new(cleanup: fn()) -> GlobalState {
+        GlobalState {
+            sessions: Mutex::new(0),
+            cleanup,
+        }
+    }
+
+    fn sessions(&self) -> MutexGuard<'_, usize> {
+        // The count stays correct even if a holder panicked.
+        lock(&self.sessions)
+    }
+}
+
+/// A reference counted handle to a `GlobalState`.
+#[derive(Debug)]
+pub(crate) struct Session {
+    state: &'static GlobalState,
+}
+
+impl Session {
+    pub(crate) fn open(state: &'static GlobalState) -> Session {
+        *state.sessions() += 1;
+        Session { state }
+    }
+
+    /// Blocks until no other session holds the state.
+    pub(crate) fn lock(&self) -> SessionGuard<'_> {
+        SessionGuard {
+            _sessions: self.state.sessions(),
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let mut sessions = self.state.sessions();
+        *sessions -= 1;
+        if *sessions == 0 {
+            (self.state.cleanup)();
+        }
+    }
+}
+
+/// Exclusive access to the state of a `Session` until dropped.
+#[derive(Debug)]
+pub(crate) struct SessionGuard<'a> {
+    _sessions: MutexGuard<'a, usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn cleans_up_after_the_last_session() {
+        static CLEANUPS: AtomicUsize = AtomicUsize::new(0);
+        static STATE: GlobalState = GlobalState::new(|| {
+            CLEANUPS.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let first = Session::open(&STATE);
+        let second = Session::open(&STATE);
+        drop(first);
+        assert_eq!(CLEANUPS.load(Ordering::SeqCst), 0);
+        drop(second);
+        assert_eq!(CLEANUPS.load(Ordering::SeqCst), 1);
+
+        drop(Session::open(&STATE));
+        assert_eq!(CLEANUPS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn gives_one_session_access_at_a_time() {
+        static STATE: GlobalState = GlobalState::new(|| {});
+        static INSIDE: AtomicUsize = AtomicUsize::new(0);
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                thread::spawn(|| {
+                    let session = Session::open(&STATE);
+                    for _ in 0..10 {
+                        let _guard = session.lock();
+                        assert_eq!(INSIDE.fetch_add(1, Ordering::SeqCst), 0);
+                        thread::sleep(Duration::from_micros(50));
+                        INSIDE.fetch_sub(1, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(*STATE.sessions(), 0);
+    }
+}
@@ -1,6 +1,4 @@
-extern crate everything_sys_bindgen;
-
-use everything_sys_bindgen::*;
+use crate::sys::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
@@ -0,0 +1,10 @@
+//! Locking shared by every module that keeps state behind a `Mutex`.
+
+use std::sync::{Mutex, MutexGuard};
+
+/// Locks `mutex` even if a previous holder panicked, the state it guards stays usable after a failed call.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
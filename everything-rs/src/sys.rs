@@ -0,0 +1,69 @@
+//! Constants and types from the Everything SDK.
+//! With the `ffi` feature on windows these are re-exported from `everything-sys-bindgen`.
+//! Otherwise the values are copied from `Everything.h` so the crate does not need the bindings or the dll.
+
+#[cfg(all(target_os = "windows", feature = "ffi"))]
+pub use everything_sys_bindgen::*;
+
+#[cfg(not(all(target_os = "windows", feature = "ffi")))]
+#[allow(dead_code)]
+mod constants {
+    pub const EVERYTHING_OK: u32 = 0;
+    pub const EVERYTHING_ERROR_MEMORY: u32 = 1;
+    pub const EVERYTHING_ERROR_IPC: u32 = 2;
+    pub const EVERYTHING_ERROR_REGISTERCLASSEX: u32 = 3;
+    pub const EVERYTHING_ERROR_CREATEWINDOW: u32 = 4;
+    pub const EVERYTHING_ERROR_CREATETHREAD: u32 = 5;
+    pub const EVERYTHING_ERROR_INVALIDINDEX: u32 = 6;
+    pub const EVERYTHING_ERROR_INVALIDCALL: u32 = 7;
+    pub const EVERYTHING_ERROR_INVALIDREQUEST: u32 = 8;
+    pub const EVERYTHING_ERROR_INVALIDPARAMETER: u32 = 9;
+    pub const EVERYTHING_SORT_NAME_ASCENDING: u32 = 1;
+    pub const EVERYTHING_SORT_NAME_DESCENDING: u32 = 2;
+    pub const EVERYTHING_SORT_PATH_ASCENDING: u32 = 3;
+    pub const EVERYTHING_SORT_PATH_DESCENDING: u32 = 4;
+    pub const EVERYTHING_SORT_SIZE_ASCENDING: u32 = 5;
+    pub const EVERYTHING_SORT_SIZE_DESCENDING: u32 = 6;
+    pub const EVERYTHING_SORT_EXTENSION_ASCENDING: u32 = 7;
+    pub const EVERYTHING_SORT_EXTENSION_DESCENDING: u32 = 8;
+    pub const EVERYTHING_SORT_TYPE_NAME_ASCENDING: u32 = 9;
+    pub const EVERYTHING_SORT_TYPE_NAME_DESCENDING: u32 = 10;
+    pub const EVERYTHING_SORT_DATE_CREATED_ASCENDING: u32 = 11;
+    pub const EVERYTHING_SORT_DATE_CREATED_DESCENDING: u32 = 12;
+    pub const EVERYTHING_SORT_DATE_MODIFIED_ASCENDING: u32 = 13;
+    pub const EVERYTHING_SORT_DATE_MODIFIED_DESCENDING: u32 = 14;
+    pub const EVERYTHING_SORT_ATTRIBUTES_ASCENDING: u32 = 15;
+    pub const EVERYTHING_SORT_ATTRIBUTES_DESCENDING: u32 = 16;
+    pub const EVERYTHING_SORT_FILE_LIST_FILENAME_ASCENDING: u32 = 17;
+    pub const EVERYTHING_SORT_FILE_LIST_FILENAME_DESCENDING: u32 = 18;
+    pub const EVERYTHING_SORT_RUN_COUNT_ASCENDING: u32 = 19;
+    pub const EVERYTHING_SORT_RUN_COUNT_DESCENDING: u32 = 20;
+    pub const EVERYTHING_SORT_DATE_RECENTLY_CHANGED_ASCENDING: u32 = 21;
+    pub const EVERYTHING_SORT_DATE_RECENTLY_CHANGED_DESCENDING: u32 = 22;
+    pub const EVERYTHING_SORT_DATE_ACCESSED_ASCENDING: u32 = 23;
+    pub const EVERYTHING_SORT_DATE_ACCESSED_DESCENDING: u32 = 24;
+    pub const EVERYTHING_SORT_DATE_RUN_ASCENDING: u32 = 25;
+    pub const EVERYTHING_SORT_DATE_RUN_DESCENDING: u32 = 26;
+    pub const EVERYTHING_REQUEST_FILE_NAME: u32 = 1;
+    pub const EVERYTHING_REQUEST_PATH: u32 = 2;
+    pub const EVERYTHING_REQUEST_FULL_PATH_AND_FILE_NAME: u32 = 4;
+    pub const EVERYTHING_REQUEST_EXTENSION: u32 = 8;
+    pub const EVERYTHING_REQUEST_SIZE: u32 = 16;
+    pub const EVERYTHING_REQUEST_DATE_CREATED: u32 = 32;
+    pub const EVERYTHING_REQUEST_DATE_MODIFIED: u32 = 64;
+    pub const EVERYTHING_REQUEST_DATE_ACCESSED: u32 = 128;
+    pub const EVERYTHING_REQUEST_ATTRIBUTES: u32 = 256;
+    pub const EVERYTHING_REQUEST_FILE_LIST_FILE_NAME: u32 = 512;
+    pub const EVERYTHING_REQUEST_RUN_COUNT: u32 = 1024;
+    pub const EVERYTHING_REQUEST_DATE_RUN: u32 = 2048;
+    pub const EVERYTHING_REQUEST_DATE_RECENTLY_CHANGED: u32 = 4096;
+    pub const EVERYTHING_REQUEST_HIGHLIGHTED_FILE_NAME: u32 = 8192;
+    pub const EVERYTHING_REQUEST_HIGHLIGHTED_PATH: u32 = 16384;
+    pub const EVERYTHING_REQUEST_HIGHLIGHTED_FULL_PATH_AND_FILE_NAME: u32 = 32768;
+    pub const EVERYTHING_TARGET_MACHINE_X86: u32 = 1;
+    pub const EVERYTHING_TARGET_MACHINE_X64: u32 = 2;
+    pub const EVERYTHING_TARGET_MACHINE_ARM: u32 = 3;
+}
+
+#[cfg(not(all(target_os = "windows", feature = "ffi")))]
+pub use constants::*;
@@ -0,0 +1,52 @@
+//! Helpers shared by the test modules.
+
+use crate::{Everything, InMemoryBackend, InMemoryEntry};
+
+/// Returns a xorshift generator so fuzz tests are deterministic without a rng dependency.
+pub(crate) fn xorshift() -> impl FnMut() -> u64 {
+    let mut state = 0x2545F4914F6CDD1Du64;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
+
+/// The entries most tests search: the `C:` volume, the `C:\test` and `C:\test\test_dir` folders,
+/// `test1.txt` and `test2.jpg` in `test_dir` and `test3.md` in `test`.
+/// The files differ in size and were created in the order test2, test3, test1 and modified in the reverse order.
+pub(crate) fn test_tree() -> Vec<InMemoryEntry> {
+    let file = |path, name, size, date_created, date_modified| InMemoryEntry {
+        size,
+        date_created,
+        date_modified,
+        ..InMemoryEntry::file(path, name)
+    };
+    vec![
+        InMemoryEntry::volume("C:"),
+        InMemoryEntry::folder("C:\\", "test"),
+        InMemoryEntry::folder("C:\\test", "test_dir"),
+        file("C:\\test\\test_dir", "test1.txt", 10, 300, 30),
+        file("C:\\test\\test_dir", "test2.jpg", 30, 100, 10),
+        file("C:\\test", "test3.md", 20, 200, 20),
+    ]
+}
+
+/// The entries of `test_tree` named one of `names`, for tests that only check a few of them.
+pub(crate) fn test_entries(names: &[&str]) -> Vec<InMemoryEntry> {
+    test_tree()
+        .into_iter()
+        .filter(|entry| names.contains(&entry.name.as_str()))
+        .collect()
+}
+
+/// An `InMemoryBackend` searching `test_tree`.
+pub(crate) fn test_backend() -> InMemoryBackend {
+    InMemoryBackend::new(test_tree())
+}
+
+/// The file names of the current results of `everything`.
+pub(crate) fn names(everything: &Everything) -> Vec<String> {
+    everything.name_iter().map(Result::unwrap).collect()
+}